@@ -0,0 +1,231 @@
+//! Command-line diagnostic tool for the [battery](https://crates.io/crates/battery) crate.
+//!
+//! It is intentionally built on top of the public `battery` crate interface only,
+//! so it works as an end-to-end check of the library on the current machine.
+
+use std::env;
+use std::io;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+use battery::units::human::{HumanEnergy, HumanPercent, HumanPower, HumanTime};
+use battery::{Battery, BatteryInfo, Config, Manager, RoundingMode};
+
+const USAGE: &str = "Usage: battery-cli <COMMAND>
+
+Commands:
+    list                        Print a table with all available batteries
+    watch [--interval <TIME>]   Print updated batteries information periodically (default interval is 1s)
+    report                      Print the full diagnostic information for all batteries
+    json [--schema]             Print all batteries as a JSON array, or the JSON schema of that array
+    help                        Print this message
+
+Interval examples: `2s`, `500ms`, `1m`, `2` (seconds)";
+
+enum Command {
+    List,
+    Watch(Duration),
+    Report,
+    Json,
+    JsonSchema,
+    Help,
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<_>>();
+    let command = match parse_args(&args) {
+        Ok(command) => command,
+        Err(msg) => {
+            eprintln!("{}\n\n{}", msg, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(e) = run(command) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    match args.first().map(String::as_str) {
+        Some("list") if args.len() == 1 => Ok(Command::List),
+        Some("report") if args.len() == 1 => Ok(Command::Report),
+        Some("json") => match &args[1..] {
+            [] => Ok(Command::Json),
+            [flag] if flag == "--schema" => Ok(Command::JsonSchema),
+            _ => Err("Invalid arguments for the `json` command".to_string()),
+        },
+        Some("help") | Some("--help") | Some("-h") => Ok(Command::Help),
+        Some("watch") => match &args[1..] {
+            [] => Ok(Command::Watch(Duration::from_secs(1))),
+            [flag, value] if flag == "--interval" || flag == "-i" => parse_interval(value).map(Command::Watch),
+            _ => Err("Invalid arguments for the `watch` command".to_string()),
+        },
+        Some(command) => Err(format!("Unknown command or arguments: `{}`", command)),
+        None => Err("Command is required".to_string()),
+    }
+}
+
+fn parse_interval(value: &str) -> Result<Duration, String> {
+    let split_at = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split_at);
+    let multiplier = match unit {
+        "ms" => 1,
+        "s" | "" => 1_000,
+        "m" => 60_000,
+        _ => return Err(format!("Invalid interval: `{}`", value)),
+    };
+
+    match number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+    {
+        Some(0) | None => Err(format!("Invalid interval: `{}`", value)),
+        Some(millis) => Ok(Duration::from_millis(millis)),
+    }
+}
+
+fn run(command: Command) -> battery::Result<()> {
+    match command {
+        Command::Help => {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        // Schema does not depend on the batteries, no need to create a manager
+        Command::JsonSchema => return print_json(&schemars::schema_for!(Vec<BatteryInfo>)),
+        _ => {}
+    }
+
+    let config = match command {
+        // Refresh must fail a few times in a row before the battery information is considered stale
+        Command::Watch(interval) => Config::new().max_data_age(interval * 3),
+        _ => Config::new(),
+    };
+    let manager = Manager::with_config(config)?;
+    match command {
+        Command::List => list(&manager),
+        Command::Watch(interval) => watch(&manager, interval),
+        Command::Report => report(&manager),
+        Command::Json => json(&manager),
+        Command::JsonSchema | Command::Help => unreachable!(),
+    }
+}
+
+fn list(manager: &Manager) -> battery::Result<()> {
+    let batteries = manager.batteries()?.collect::<battery::Result<Vec<_>>>()?;
+    if batteries.is_empty() {
+        println!("No batteries found");
+    } else {
+        print_table(&batteries);
+    }
+
+    Ok(())
+}
+
+fn watch(manager: &Manager, interval: Duration) -> battery::Result<()> {
+    let mut batteries = manager.batteries()?.collect::<battery::Result<Vec<_>>>()?;
+    if batteries.is_empty() {
+        println!("No batteries found");
+        return Ok(());
+    }
+
+    let mut stale = vec![false; batteries.len()];
+    loop {
+        print_table(&batteries);
+        println!();
+        thread::sleep(interval);
+
+        for (idx, battery) in batteries.iter_mut().enumerate() {
+            // Keeping the stale data, battery might be back on a next refresh
+            if let Err(e) = manager.refresh(battery) {
+                eprintln!("Unable to refresh battery #{}: {}", idx, e);
+            }
+
+            if battery.is_stale() != stale[idx] {
+                stale[idx] = battery.is_stale();
+                if stale[idx] {
+                    eprintln!("Battery #{} information is stale", idx);
+                } else {
+                    eprintln!("Battery #{} information is up to date again", idx);
+                }
+            }
+        }
+    }
+}
+
+fn report(manager: &Manager) -> battery::Result<()> {
+    let mut found = false;
+
+    // Not stopping on the first error, since all of them are important for diagnostics
+    for (idx, battery) in manager.batteries()?.enumerate() {
+        found = true;
+        match battery {
+            Ok(battery) => {
+                println!("Battery #{}:\n{:#?}", idx, battery);
+                print_anomalies(&battery);
+            }
+            Err(e) => println!("Battery #{}:\nUnable to fetch battery information: {}\n", idx, e),
+        }
+    }
+
+    if !found {
+        println!("No batteries found");
+    }
+
+    Ok(())
+}
+
+fn json(manager: &Manager) -> battery::Result<()> {
+    let batteries = manager.batteries()?.collect::<battery::Result<Vec<_>>>()?;
+    let snapshots = batteries.iter().map(BatteryInfo::from).collect::<Vec<_>>();
+
+    // Empty array for the zero-battery machines, so the output is always a valid JSON
+    print_json(&snapshots)
+}
+
+fn print_json<T: serde::Serialize>(value: &T) -> battery::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::from)?;
+    println!("{}", json);
+
+    Ok(())
+}
+
+fn print_anomalies(battery: &Battery) {
+    let anomalies = battery.validate();
+    if anomalies.is_empty() {
+        println!("Anomalies: none\n");
+    } else {
+        let anomalies = anomalies.iter().map(ToString::to_string).collect::<Vec<_>>();
+        println!("Anomalies: {}\n", anomalies.join(", "));
+    }
+}
+
+fn print_table(batteries: &[Battery]) {
+    println!("#   Vendor           Model            State         Charge  Health    Energy     Rate  Time");
+
+    for (idx, battery) in batteries.iter().enumerate() {
+        println!(
+            "{:<3} {:<16} {:<16} {:<12} {:>7} {:>7} {:>9} {:>8}  {}",
+            idx,
+            battery.vendor().unwrap_or("-"),
+            battery.model().unwrap_or("-"),
+            battery.state(),
+            HumanPercent(battery.state_of_charge(), RoundingMode::Round),
+            HumanPercent(battery.state_of_health(), RoundingMode::Round),
+            HumanEnergy(battery.energy()),
+            HumanPower(battery.energy_rate()),
+            format_time(battery),
+        );
+    }
+}
+
+fn format_time(battery: &Battery) -> String {
+    match (battery.time_to_full(), battery.time_to_empty()) {
+        (Some(time), _) => format!("full in {}", HumanTime(time)),
+        (None, Some(time)) => format!("empty in {}", HumanTime(time)),
+        (None, None) => "-".to_string(),
+    }
+}
@@ -27,6 +27,21 @@ pub unsafe extern "C" fn battery_get_state_of_charge(ptr: *const Battery) -> lib
     battery.state_of_charge().get::<percent>()
 }
 
+/// Returns battery state of charge in tenths of a percent, from `0` to `1000`.
+///
+/// Value is rounded to the nearest integer, half away from zero.
+///
+/// # Panics
+///
+/// This function will panic if passed pointer is `NULL`
+#[no_mangle]
+pub unsafe extern "C" fn battery_get_state_of_charge_permille(ptr: *const Battery) -> u16 {
+    assert!(!ptr.is_null());
+    let battery = &*ptr;
+
+    battery.state_of_charge_permille()
+}
+
 /// Returns battery energy (in `joule`).
 ///
 /// # Panics
@@ -51,6 +51,7 @@ mod battery;
 mod errors;
 mod iterator;
 mod manager;
+mod snapshot;
 mod state;
 mod technology;
 
@@ -76,5 +77,6 @@ pub use self::battery::*;
 pub use self::errors::{battery_have_last_error, battery_last_error_length, battery_last_error_message};
 pub use self::iterator::*;
 pub use self::manager::*;
+pub use self::snapshot::*;
 pub use self::state::*;
 pub use self::technology::*;
@@ -0,0 +1,61 @@
+use crate::battery::*;
+use crate::Battery;
+
+/// All numeric battery values, filled at once by the [battery_get_snapshot](fn.battery_get_snapshot.html)
+/// function instead of calling the separate `battery_get_*` function for each one of them.
+///
+/// Values are in the same units as the corresponding getters return.
+/// Unavailable values are marked with `NaN` for the floating point fields
+/// and with the max possible value for the `u32` type (`4294967295`) for the integer ones.
+///
+/// Struct has the C layout, fields are laid out in the declaration order.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BatterySnapshot {
+    // DO NOT RE-ORDER FIELDS IN THIS STRUCT, IT WILL AFFECT FFI USERS!
+    /// Energy, in `joule`.
+    pub energy: libc::c_float,
+    /// Energy when the battery is considered full, in `joule`.
+    pub energy_full: libc::c_float,
+    /// Energy the battery is designed to hold when it is considered full, in `joule`.
+    pub energy_full_design: libc::c_float,
+    /// Voltage, in `V`.
+    pub voltage: libc::c_float,
+    /// Energy rate, in `W`.
+    pub energy_rate: libc::c_float,
+    /// State of charge, as a percentage value from `0.0` to `100.0`.
+    pub state_of_charge: libc::c_float,
+    /// State of health, as a percentage value from `0.0` to `100.0`.
+    pub state_of_health: libc::c_float,
+    /// Temperature, in Kelvin, or `NaN` if not available.
+    pub temperature: libc::c_float,
+    /// Cycles count, or `4294967295` if not available.
+    pub cycle_count: u32,
+}
+
+/// Fills the `out` struct with all numeric battery values,
+/// which are the same as the corresponding `battery_get_*` functions return.
+///
+/// # Returns
+///
+/// `true` if the struct was filled, `false` if any of passed pointers is `NULL`,
+/// in which case `out` is left untouched.
+#[no_mangle]
+pub unsafe extern "C" fn battery_get_snapshot(ptr: *const Battery, out: *mut BatterySnapshot) -> bool {
+    if ptr.is_null() || out.is_null() {
+        return false;
+    }
+    *out = BatterySnapshot {
+        energy: battery_get_energy(ptr),
+        energy_full: battery_get_energy_full(ptr),
+        energy_full_design: battery_get_energy_full_design(ptr),
+        voltage: battery_get_voltage(ptr),
+        energy_rate: battery_get_energy_rate(ptr),
+        state_of_charge: battery_get_state_of_charge(ptr),
+        state_of_health: battery_get_state_of_health(ptr),
+        temperature: battery_get_temperature(ptr),
+        cycle_count: battery_get_cycle_count(ptr),
+    };
+
+    true
+}
@@ -0,0 +1,40 @@
+//! Compares the regular batteries enumeration with the `Manager::full_scan`.
+//!
+//! Run it in release mode; syscall counts for each approach can be checked with
+//!
+//! ```text
+//! strace -c -e trace=%file,read,close ./target/release/examples/full_scan iter
+//! strace -c -e trace=%file,read,close ./target/release/examples/full_scan scan
+//! ```
+
+extern crate battery;
+
+use std::env;
+use std::time::Instant;
+
+const ROUNDS: u32 = 1_000;
+
+fn main() -> battery::Result<()> {
+    let manager = battery::Manager::new()?;
+    let mode = env::args().nth(1).unwrap_or_default();
+
+    if mode.is_empty() || mode == "iter" {
+        let started = Instant::now();
+        for _ in 0..ROUNDS {
+            for battery in manager.batteries()? {
+                battery?;
+            }
+        }
+        println!("Manager::batteries: {:?} per round", started.elapsed() / ROUNDS);
+    }
+
+    if mode.is_empty() || mode == "scan" {
+        let started = Instant::now();
+        for _ in 0..ROUNDS {
+            manager.full_scan()?;
+        }
+        println!("Manager::full_scan: {:?} per round", started.elapsed() / ROUNDS);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,407 @@
+//! Asynchronous wrappers around the blocking [Manager](../struct.Manager.html) API.
+//!
+//! Available only with the `async` feature enabled, which requires Rust 1.39 or newer.
+//!
+//! All the blocking operations are executed on a dedicated background thread,
+//! owned by the [Manager](struct.Manager.html) instance, so this module does not depend
+//! on any specific async runtime and returned futures can be awaited from any of them.
+//!
+//! ## Example
+//!
+//! ```edition2018,ignore
+//! # async fn example() -> battery::Result<()> {
+//! let manager = battery::r#async::Manager::new().await?;
+//! for battery in manager.batteries().await? {
+//!     let (result, battery) = manager.refresh(battery).await;
+//!     result?;
+//!     println!("{:?}", battery);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::thread;
+
+use crate::{Battery, Error, Result};
+
+type Job = Box<dyn FnOnce(&crate::Manager) + Send>;
+
+/// Asynchronous manager for batteries available in system.
+///
+/// Manager spawns a background thread, which executes all the blocking operations.
+/// Thread is stopped when manager is dropped.
+pub struct Manager {
+    jobs: Mutex<mpsc::Sender<Job>>,
+}
+
+impl Manager {
+    /// Creates new manager instance.
+    pub async fn new() -> Result<Manager> {
+        let (jobs, receiver) = mpsc::channel::<Job>();
+        let (task, completer) = task();
+
+        thread::Builder::new()
+            .name("battery-async".to_string())
+            .spawn(move || {
+                let manager = match crate::Manager::new() {
+                    Ok(manager) => {
+                        completer.complete(Ok(()));
+                        manager
+                    }
+                    Err(e) => return completer.complete(Err(e)),
+                };
+
+                // Loop ends when all senders (and, therefore, the async manager) are dropped
+                for job in receiver {
+                    job(&manager);
+                }
+            })?;
+
+        task.await?;
+
+        Ok(Manager {
+            jobs: Mutex::new(jobs),
+        })
+    }
+
+    /// Gets all available [batteries](../struct.Battery.html).
+    ///
+    /// Batteries are returned in the same order as
+    /// the [Manager::batteries](../struct.Manager.html#method.batteries) method yields them.
+    pub async fn batteries(&self) -> Result<Vec<Battery>> {
+        self.execute(|manager| manager.batteries()?.collect()).await
+    }
+
+    /// Refresh battery information.
+    ///
+    /// Battery is moved into the background thread while refreshing,
+    /// and it is returned back together with the refresh result,
+    /// so the stale information is not lost if refresh fails.
+    ///
+    /// If the background thread had terminated, an error is returned
+    /// together with the untouched battery; manager is unusable after that.
+    pub async fn refresh(&self, battery: Battery) -> (Result<()>, Battery) {
+        // Battery is kept in the shared slot instead of being moved into the job,
+        // so it can be taken back even if the job was never executed
+        let slot = Arc::new(Mutex::new(Some(battery)));
+        let shared = slot.clone();
+        let result = self
+            .execute(move |manager| {
+                let mut battery = match shared.lock() {
+                    Ok(battery) => battery,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                battery.as_mut().map_or(Ok(()), |battery| manager.refresh(battery))
+            })
+            .await;
+
+        let battery = match slot.lock() {
+            Ok(mut battery) => battery.take(),
+            Err(poisoned) => poisoned.into_inner().take(),
+        };
+
+        (result, battery.expect("Battery is taken from the slot only once"))
+    }
+
+    fn execute<T, F>(&self, f: F) -> Task<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&crate::Manager) -> Result<T> + Send + 'static,
+    {
+        let (task, completer) = task();
+        let job: Job = Box::new(move |manager| completer.complete(f(manager)));
+
+        // If sending had failed, `job` is dropped together with the `completer`,
+        // and the task will resolve into an error
+        let _ = self.jobs.lock().expect("Jobs channel lock is poisoned").send(job);
+
+        task
+    }
+}
+
+impl fmt::Debug for Manager {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AsyncManager").finish()
+    }
+}
+
+struct Shared<T> {
+    value: Option<Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// Future resolved by the background thread with the help of the paired `Completer`.
+struct Task<T>(Arc<Mutex<Shared<T>>>);
+
+/// Completes the paired `Task`.
+///
+/// If dropped without calling `complete` (ex. background thread had panicked),
+/// task resolves into an error.
+struct Completer<T>(Option<Arc<Mutex<Shared<T>>>>);
+
+fn task<T>() -> (Task<T>, Completer<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: None,
+        waker: None,
+    }));
+
+    (Task(shared.clone()), Completer(Some(shared)))
+}
+
+impl<T> Future for Task<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut shared = self.0.lock().expect("Task lock is poisoned");
+        match shared.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Completer<T> {
+    fn complete(mut self, value: Result<T>) {
+        if let Some(shared) = self.0.take() {
+            Self::resolve(&shared, value);
+        }
+    }
+
+    fn resolve(shared: &Mutex<Shared<T>>, value: Result<T>) {
+        let waker = {
+            let mut shared = match shared.lock() {
+                Ok(shared) => shared,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+            shared.value = Some(value);
+            shared.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Completer<T> {
+    fn drop(&mut self) {
+        if let Some(shared) = self.0.take() {
+            let inner = io::Error::from(io::ErrorKind::BrokenPipe);
+            Self::resolve(&shared, Err(Error::new(inner, "Background thread had terminated")));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::mem;
+    use std::pin::Pin;
+    use std::sync::{Arc, Condvar, Mutex};
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{task, Manager};
+    use crate::Battery;
+
+    type Signal = (Mutex<bool>, Condvar);
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone_waker, wake, wake_by_ref, drop_waker);
+
+    fn raw_waker(signal: Arc<Signal>) -> RawWaker {
+        RawWaker::new(Arc::into_raw(signal) as *const (), &VTABLE)
+    }
+
+    unsafe fn clone_waker(ptr: *const ()) -> RawWaker {
+        let signal = Arc::from_raw(ptr as *const Signal);
+        let cloned = signal.clone();
+        mem::forget(signal);
+        raw_waker(cloned)
+    }
+
+    unsafe fn wake(ptr: *const ()) {
+        wake_by_ref(ptr);
+        drop_waker(ptr);
+    }
+
+    unsafe fn wake_by_ref(ptr: *const ()) {
+        let signal = &*(ptr as *const Signal);
+        *signal.0.lock().unwrap() = true;
+        signal.1.notify_one();
+    }
+
+    unsafe fn drop_waker(ptr: *const ()) {
+        drop(Arc::from_raw(ptr as *const Signal));
+    }
+
+    fn signal_waker(signal: &Arc<Signal>) -> Waker {
+        unsafe { Waker::from_raw(raw_waker(signal.clone())) }
+    }
+
+    fn wait(signal: &Signal) {
+        let mut woken = signal.0.lock().unwrap();
+        while !*woken {
+            woken = signal.1.wait(woken).unwrap();
+        }
+        *woken = false;
+    }
+
+    /// Minimal executor, which parks current thread until the future wakes it up.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let signal = Arc::new((Mutex::new(false), Condvar::new()));
+        let waker = signal_waker(&signal);
+        let mut cx = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = Pin::as_mut(&mut future).poll(&mut cx) {
+                return output;
+            }
+
+            wait(&signal);
+        }
+    }
+
+    /// Same as `block_on`, but each poll happens on a new thread,
+    /// as in the multi-threaded runtimes, which are moving tasks between the worker threads.
+    fn block_on_threads<F>(future: F) -> F::Output
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let signal = Arc::new((Mutex::new(false), Condvar::new()));
+        let mut future = Box::pin(future);
+
+        loop {
+            let worker_signal = signal.clone();
+            let (poll, returned) = thread::spawn(move || {
+                let waker = signal_waker(&worker_signal);
+                let mut cx = Context::from_waker(&waker);
+                let poll = Pin::as_mut(&mut future).poll(&mut cx);
+                (poll, future)
+            })
+            .join()
+            .unwrap();
+
+            match poll {
+                Poll::Ready(output) => return output,
+                Poll::Pending => future = returned,
+            }
+            wait(&signal);
+        }
+    }
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn assert_send<T: Send>() {}
+
+    #[test]
+    fn test_handles_are_send() {
+        assert_send_sync::<Manager>();
+        assert_send::<Battery>();
+    }
+
+    #[test]
+    fn test_task_completed_from_another_thread() {
+        let (task, completer) = task();
+        let handle = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            completer.complete(Ok(42));
+        });
+
+        assert_eq!(42, block_on(task).unwrap());
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn test_task_dropped_completer() {
+        let (task, completer) = task::<()>();
+        thread::spawn(move || drop(completer)).join().unwrap();
+
+        assert!(block_on(task).is_err());
+    }
+
+    #[test]
+    fn test_manager_shared_between_threads() {
+        let manager = Arc::new(block_on(Manager::new()).unwrap());
+        let handles = (0..4)
+            .map(|_| {
+                let manager = manager.clone();
+                thread::spawn(move || {
+                    for battery in block_on(manager.batteries()).unwrap() {
+                        let (result, _battery) = block_on(manager.refresh(battery));
+                        result.unwrap();
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_manager_on_multi_threaded_runtime() {
+        let manager = Arc::new(block_on_threads(Manager::new()).unwrap());
+        let handles = (0..4)
+            .map(|_| {
+                let manager = manager.clone();
+                thread::spawn(move || {
+                    block_on_threads(async move {
+                        for battery in manager.batteries().await.unwrap() {
+                            let (result, _battery) = manager.refresh(battery).await;
+                            result.unwrap();
+                        }
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_refresh_with_terminated_thread() {
+        use std::fs;
+
+        use crate::platform::Device;
+        use crate::Result;
+
+        let root = tempfile::tempdir().unwrap();
+        let files = [
+            ("type", "Battery"),
+            ("status", "Discharging"),
+            ("energy_now", "25000000"),
+            ("energy_full", "50000000"),
+            ("energy_full_design", "50000000"),
+            ("voltage_now", "12000000"),
+        ];
+        for (name, value) in files.iter() {
+            fs::write(root.path().join(name), value).unwrap();
+        }
+        let battery = Battery::from(Device::try_from(root.path().to_path_buf()).unwrap());
+
+        let manager = block_on(Manager::new()).unwrap();
+        let panicked = block_on(manager.execute(|_| -> Result<()> { panic!("Background thread is terminated") }));
+        assert!(panicked.is_err());
+
+        let (result, battery) = block_on(manager.refresh(battery));
+        assert!(result.is_err());
+        assert_eq!(battery.energy(), battery.energy_full() / 2.0);
+    }
+}
@@ -4,6 +4,7 @@ use std::borrow::Cow;
 use std::error::Error as StdError;
 use std::fmt;
 use std::io;
+use std::num::{ParseFloatError, ParseIntError};
 use std::result;
 
 pub type Result<T> = result::Result<T, Error>;
@@ -20,6 +21,25 @@ pub struct Error {
 }
 
 impl Error {
+    /// Creates an error from the I/O error, same as the `From<io::Error>` conversion does.
+    pub fn io(e: io::Error) -> Error {
+        Error::from(e)
+    }
+
+    /// Creates an error for the operations, which are not supported by the current platform or battery.
+    ///
+    /// Its [kind](#method.kind) is `io::ErrorKind::Other`, since the dedicated kind is not available
+    /// for the supported Rust versions.
+    pub fn not_supported<T>(description: T) -> Error
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Error {
+            source: io::Error::from(io::ErrorKind::Other),
+            description: Some(description.into()),
+        }
+    }
+
     #[allow(unused)]
     pub(crate) fn new<T>(e: io::Error, description: T) -> Error
     where
@@ -52,6 +72,11 @@ impl Error {
             description: Some(description.into()),
         }
     }
+
+    /// Kind of the underlying I/O error.
+    pub fn kind(&self) -> io::ErrorKind {
+        self.source.kind()
+    }
 }
 
 impl StdError for Error {
@@ -78,6 +103,72 @@ impl From<io::Error> for Error {
     }
 }
 
+impl From<ParseIntError> for Error {
+    fn from(e: ParseIntError) -> Self {
+        Error {
+            source: io::Error::new(io::ErrorKind::InvalidData, e),
+            description: None,
+        }
+    }
+}
+
+impl From<ParseFloatError> for Error {
+    fn from(e: ParseFloatError) -> Self {
+        Error {
+            source: io::Error::new(io::ErrorKind::InvalidData, e),
+            description: None,
+        }
+    }
+}
+
+/// Failed read of a single battery value.
+///
+/// Collected by the [Manager::refresh](../struct.Manager.html#method.refresh) instead of failing the whole refresh,
+/// if the [partial refresh](../struct.Config.html#method.partial_refresh) is enabled,
+/// see [Battery::field_errors](../struct.Battery.html#method.field_errors).
+#[derive(Debug)]
+pub struct FieldError {
+    field: &'static str,
+    error: Error,
+}
+
+impl FieldError {
+    #[cfg(target_os = "linux")]
+    pub(crate) fn new(field: &'static str, error: Error) -> FieldError {
+        FieldError {
+            field,
+            error,
+        }
+    }
+
+    /// Name of the `Battery` method, which value had failed to be read (ex. `"energy_rate"`).
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// Read error.
+    pub fn error(&self) -> &Error {
+        &self.error
+    }
+
+    #[cfg(target_os = "linux")]
+    pub(crate) fn into_error(self) -> Error {
+        self.error
+    }
+}
+
+impl StdError for FieldError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unable to read `{}` value: {}", self.field, self.error)
+    }
+}
+
 #[cfg(any(target_os = "dragonfly", target_os = "freebsd"))]
 mod nix_impl {
     use std::io;
@@ -107,3 +198,50 @@ mod nix_impl {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as StdError;
+    use std::io;
+
+    use super::Error;
+
+    fn parse(value: &str) -> Result<u32, Error> {
+        Ok(value.parse::<u32>()?)
+    }
+
+    fn consumer(value: &str) -> Result<u32, Box<dyn StdError>> {
+        Ok(parse(value)?)
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse("42").unwrap(), 42);
+
+        let e = parse("forty two").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(e.to_string(), "invalid digit found in string");
+
+        let e: Error = "4.2.".parse::<f32>().unwrap_err().into();
+        assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let e = consumer("").unwrap_err();
+        let io_error = e.source().unwrap().downcast_ref::<io::Error>().unwrap();
+        assert_eq!(io_error.kind(), io::ErrorKind::InvalidData);
+        assert!(io_error.get_ref().is_some());
+    }
+
+    #[test]
+    fn test_constructors() {
+        let e = Error::io(io::Error::from(io::ErrorKind::PermissionDenied));
+        assert_eq!(e.kind(), io::ErrorKind::PermissionDenied);
+        assert!(e.source().is_some());
+
+        let e = Error::not_supported("Charge control is not supported");
+        assert_eq!(e.kind(), io::ErrorKind::Other);
+        assert_eq!(e.to_string(), "Charge control is not supported");
+    }
+}
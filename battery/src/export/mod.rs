@@ -0,0 +1,4 @@
+//! Writers of the batteries information into the formats used by the external tools.
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
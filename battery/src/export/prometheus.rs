@@ -0,0 +1,197 @@
+//! [Prometheus text exposition format](https://prometheus.io/docs/instrumenting/exposition_formats/) writer.
+//!
+//! Available only with the `prometheus` feature enabled.
+//!
+//! Each battery is labeled with its [name](../../struct.Battery.html#method.name)
+//! (or index, if it is unknown) as a `battery` label, and with `vendor` and `model` labels,
+//! if these are known. Metric names listed below are considered to be a part of the public API
+//! and will not change in the minor versions:
+//!
+//!  * `battery_state_of_charge_ratio`
+//!  * `battery_state_of_health_ratio`
+//!  * `battery_energy_watt_hours`
+//!  * `battery_energy_full_watt_hours`
+//!  * `battery_energy_full_design_watt_hours`
+//!  * `battery_power_watts`
+//!  * `battery_voltage_volts`
+//!  * `battery_temperature_celsius`
+//!  * `battery_cycle_count`
+//!  * `battery_time_to_full_seconds`
+//!  * `battery_time_to_empty_seconds`
+//!  * `battery_state`, with `1` value for the current battery state and `0` for the others, distinguished by the
+//!    `state` label
+//!
+//! Optional values are skipped if battery does not provide them.
+//!
+//! ## Example
+//!
+//! ```edition2018
+//! # fn main() -> battery::Result<()> {
+//! let manager = battery::Manager::new()?;
+//! let batteries = manager.batteries()?.collect::<battery::Result<Vec<_>>>()?;
+//!
+//! let mut output = String::new();
+//! battery::export::prometheus::write_metrics(&mut output, &batteries).unwrap();
+//! print!("{}", output);
+//! # Ok(())
+//! # }
+//! ```
+
+use std::fmt;
+
+use crate::units::electric_potential::volt;
+use crate::units::energy::watt_hour;
+use crate::units::power::watt;
+use crate::units::ratio::ratio;
+use crate::units::thermodynamic_temperature::degree_celsius;
+use crate::units::time::second;
+use crate::{Battery, State};
+
+struct Gauge {
+    name: &'static str,
+    help: &'static str,
+    value: fn(&Battery) -> Option<f32>,
+}
+
+const GAUGES: &[Gauge] = &[
+    Gauge {
+        name: "battery_state_of_charge_ratio",
+        help: "Battery state of charge",
+        value: |battery| Some(battery.state_of_charge().get::<ratio>()),
+    },
+    Gauge {
+        name: "battery_state_of_health_ratio",
+        help: "Battery state of health",
+        value: |battery| Some(battery.state_of_health().get::<ratio>()),
+    },
+    Gauge {
+        name: "battery_energy_watt_hours",
+        help: "Energy left in the battery",
+        value: |battery| Some(battery.energy().get::<watt_hour>()),
+    },
+    Gauge {
+        name: "battery_energy_full_watt_hours",
+        help: "Energy in the fully charged battery",
+        value: |battery| Some(battery.energy_full().get::<watt_hour>()),
+    },
+    Gauge {
+        name: "battery_energy_full_design_watt_hours",
+        help: "Energy in the fully charged battery by design",
+        value: |battery| Some(battery.energy_full_design().get::<watt_hour>()),
+    },
+    Gauge {
+        name: "battery_power_watts",
+        help: "Battery charging or discharging rate",
+        value: |battery| Some(battery.energy_rate().get::<watt>()),
+    },
+    Gauge {
+        name: "battery_voltage_volts",
+        help: "Battery voltage",
+        value: |battery| Some(battery.voltage().get::<volt>()),
+    },
+    Gauge {
+        name: "battery_temperature_celsius",
+        help: "Battery temperature",
+        value: |battery| battery.temperature().map(|value| value.get::<degree_celsius>()),
+    },
+    Gauge {
+        name: "battery_cycle_count",
+        help: "Number of battery charge/discharge cycles",
+        value: |battery| battery.cycle_count().map(|value| value as f32),
+    },
+    Gauge {
+        name: "battery_time_to_full_seconds",
+        help: "Time left until the battery is fully charged",
+        value: |battery| battery.time_to_full().map(|value| value.get::<second>()),
+    },
+    Gauge {
+        name: "battery_time_to_empty_seconds",
+        help: "Time left until the battery is fully discharged",
+        value: |battery| battery.time_to_empty().map(|value| value.get::<second>()),
+    },
+];
+
+const STATES: [State; 5] = [
+    State::Unknown,
+    State::Charging,
+    State::Discharging,
+    State::Empty,
+    State::Full,
+];
+
+/// Writes metrics for the `batteries` in the Prometheus text format.
+///
+/// See the [module documentation](index.html) for the list of metrics.
+pub fn write_metrics(w: &mut impl fmt::Write, batteries: &[Battery]) -> fmt::Result {
+    for gauge in GAUGES {
+        let values = batteries
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, battery)| (gauge.value)(battery).map(|value| (idx, battery, value)))
+            .collect::<Vec<_>>();
+        if values.is_empty() {
+            continue;
+        }
+
+        write_header(w, gauge.name, gauge.help)?;
+        for (idx, battery, value) in values {
+            w.write_str(gauge.name)?;
+            write_labels(w, idx, battery, None)?;
+            writeln!(w, " {}", value)?;
+        }
+    }
+
+    if !batteries.is_empty() {
+        write_header(w, "battery_state", "Battery state")?;
+        for (idx, battery) in batteries.iter().enumerate() {
+            for state in STATES.iter() {
+                let state_label = state.to_string();
+                w.write_str("battery_state")?;
+                write_labels(w, idx, battery, Some(("state", &state_label)))?;
+                writeln!(w, " {}", if battery.state() == *state { 1 } else { 0 })?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_header(w: &mut impl fmt::Write, name: &str, help: &str) -> fmt::Result {
+    writeln!(w, "# HELP {} {}", name, help)?;
+    writeln!(w, "# TYPE {} gauge", name)
+}
+
+fn write_labels(w: &mut impl fmt::Write, idx: usize, battery: &Battery, extra: Option<(&str, &str)>) -> fmt::Result {
+    let idx = idx.to_string();
+    let labels = [
+        Some(("battery", battery.name().unwrap_or(&idx))),
+        battery.vendor().map(|value| ("vendor", value)),
+        battery.model().map(|value| ("model", value)),
+        extra,
+    ];
+
+    w.write_char('{')?;
+    for (i, (name, value)) in labels.iter().filter_map(|label| *label).enumerate() {
+        if i > 0 {
+            w.write_char(',')?;
+        }
+        write!(w, "{}=\"", name)?;
+        write_escaped(w, value)?;
+        w.write_char('"')?;
+    }
+    w.write_char('}')
+}
+
+/// Escapes label value as required by the text format.
+fn write_escaped(w: &mut impl fmt::Write, value: &str) -> fmt::Result {
+    for chr in value.chars() {
+        match chr {
+            '\\' => w.write_str("\\\\")?,
+            '"' => w.write_str("\\\"")?,
+            '\n' => w.write_str("\\n")?,
+            _ => w.write_char(chr)?,
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,88 @@
+//! Internal wrappers for the `tracing` crate macros.
+//!
+//! With the `tracing` feature disabled they are expanding into nothing,
+//! so neither the dependency nor any runtime overhead is introduced.
+//! Macros are not exported and are used by the platform implementations only.
+
+/// Creates a `DEBUG` level span and enters it, returning a guard
+/// which exits the span on drop.
+#[cfg(feature = "tracing")]
+macro_rules! trace_span {
+    ($($arg:tt)+) => {
+        tracing::debug_span!($($arg)+).entered()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_span {
+    ($($arg:tt)+) => {
+        ()
+    };
+}
+
+/// Span which outlives a single call and is entered with the `trace_enter!` macro,
+/// ex. the one covering the whole enumeration.
+#[cfg(feature = "tracing")]
+pub(crate) type Span = tracing::Span;
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) struct Span;
+
+/// Creates a `DEBUG` level span without entering it.
+#[cfg(feature = "tracing")]
+macro_rules! trace_new_span {
+    ($($arg:tt)+) => {
+        tracing::debug_span!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_new_span {
+    ($($arg:tt)+) => {
+        $crate::instrument::Span
+    };
+}
+
+/// Enters the span created with the `trace_new_span!` macro, returning a guard
+/// which exits the span on drop.
+#[cfg(feature = "tracing")]
+macro_rules! trace_enter {
+    ($span:expr) => {
+        $span.enter()
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_enter {
+    ($span:expr) => {
+        &$span
+    };
+}
+
+/// Emits a `DEBUG` level event, used for the raw values read from the OS.
+#[cfg(feature = "tracing")]
+#[allow(unused_macros)] // Not every platform reports the raw values
+macro_rules! trace_debug {
+    ($($arg:tt)+) => {
+        tracing::debug!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[allow(unused_macros)]
+macro_rules! trace_debug {
+    ($($arg:tt)+) => {};
+}
+
+/// Emits a `WARN` level event, used when a quirk workaround is applied to the OS-provided value.
+#[cfg(feature = "tracing")]
+macro_rules! trace_warn {
+    ($($arg:tt)+) => {
+        tracing::warn!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! trace_warn {
+    ($($arg:tt)+) => {};
+}
@@ -16,9 +16,23 @@
 //! For a quick example see the [Manager](struct.Manager.html) type documentation
 //! or [`simple.rs`](https://github.com/svartalf/rust-battery/blob/master/battery/examples/simple.rs)
 //! file in the `examples/` folder.
+//! Simple one-shot programs might use the [batteries](fn.batteries.html) function instead
+//! of creating the `Manager` explicitly.
 //!
 //! [battop](https://crates.io/crates/battop) crate is using this library as a knowledge source,
 //! so check it out too for a real-life example.
+//!
+//! ## Features
+//!
+//! * `async` — enables the [r#async](async/index.html) module with runtime-agnostic asynchronous wrappers around the
+//!   [Manager](struct.Manager.html) API (requires Rust 1.39+).
+//! * `serde` — implements `Serialize` and `Deserialize` for the [State](enum.State.html) and
+//!   [Technology](enum.Technology.html) enums.
+//! * `schemars` — implements `JsonSchema` for the same enums, matching their `serde` representation.
+//! * `prometheus` — enables the [export::prometheus](export/prometheus/index.html) module, which writes batteries
+//!   information in the Prometheus text exposition format.
+//! * `tracing` — instruments the batteries enumeration and refresh with the [tracing](https://crates.io/crates/tracing)
+//!   spans, platform values reads with `DEBUG` events and the workarounds for the invalid values with `WARN` events.
 
 #![deny(unused)]
 #![deny(unstable_features)]
@@ -36,11 +50,25 @@ extern crate winapi;
 #[macro_use]
 extern crate nix;
 
+#[macro_use]
+mod instrument;
 mod types;
 #[macro_use]
 pub mod units;
 pub mod errors;
 mod platform;
 
-pub use self::errors::{Error, Result};
-pub use self::types::{Batteries, Battery, Manager, State, Technology};
+#[cfg(feature = "async")]
+pub mod r#async;
+#[cfg(feature = "prometheus")]
+pub mod export;
+
+pub use self::errors::{Error, FieldError, Result};
+pub use self::platform::supported;
+pub use self::types::{
+    active_battery, batteries, status, Anomaly, Batteries, Battery, BatteryInfo, Capacity, CapacitySource, ChargePhase,
+    Condition, Config, DisplayConfig, EnumerationDelta, Estimate, EstimateQuality, EstimateSource, EtaComparison,
+    Event, Kind, KindFilter, LearnedEstimator, LinePower, Manager, PromptSegment, RateKind, RiskLevel, RoundingMode,
+    Severity, State, SystemBatteryReport, Technology, TemperatureUnit, ThresholdEvent, ThresholdWatcher, TimeFormat,
+    WatcherHandle,
+};
@@ -101,9 +101,16 @@ impl AcpiBif {
     }
 
     /// Either mWh or mAh, depends on `self.units`
+    ///
+    /// Falls back to the last full capacity if design capacity is unknown.
     #[inline]
     pub fn design_capacity(&self) -> u32 {
-        self.dcap
+        if self.dcap == ACPI_BATT_UNKNOWN {
+            trace_warn!("design capacity is unknown, falling back to the last full capacity");
+            self.lfcap
+        } else {
+            self.dcap
+        }
     }
 
     /// Either mWh or mAh, depends on `self.units`
@@ -157,9 +164,16 @@ impl AcpiBst {
         }
     }
 
+    /// Unknown rate is reported as a zero value,
+    /// as it happens for some gauges during the firmware update.
     #[inline]
     pub fn rate(&self) -> u32 {
-        self.rate
+        if self.rate == ACPI_BATT_UNKNOWN {
+            trace_warn!("rate unknown value marker is reported as zero");
+            0
+        } else {
+            self.rate
+        }
     }
 
     #[inline]
@@ -3,8 +3,8 @@ use std::fmt;
 
 use super::acpi;
 use crate::platform::traits::BatteryDevice;
-use crate::units::{ElectricPotential, Energy, Power, ThermodynamicTemperature};
-use crate::{Result, State, Technology};
+use crate::units::{ElectricCharge, ElectricPotential, Energy, Power, ThermodynamicTemperature};
+use crate::{Kind, Result, State, Technology};
 
 #[derive(Default)]
 pub struct IoCtlDevice {
@@ -18,6 +18,10 @@ pub struct IoCtlDevice {
     design_capacity: Energy,
     max_capacity: Energy,
     current_capacity: Energy,
+    // Natively reported values, if the battery uses mAh units
+    charge: Option<ElectricCharge>,
+    charge_full: Option<ElectricCharge>,
+    design_voltage: ElectricPotential,
 
     manufacturer: Option<String>,
     model: Option<String>,
@@ -68,6 +72,17 @@ impl IoCtlDevice {
             acpi::Units::MilliWatts => milliwatt_hour!(bif.last_full_capacity()),
             acpi::Units::MilliAmperes => milliampere_hour!(bif.last_full_capacity()) * design_voltage,
         };
+        match bif.units() {
+            acpi::Units::MilliWatts => {
+                self.charge = None;
+                self.charge_full = None;
+            }
+            acpi::Units::MilliAmperes => {
+                self.charge = Some(milliampere_hour!(bst.capacity()));
+                self.charge_full = Some(milliampere_hour!(bif.last_full_capacity()));
+            }
+        }
+        self.design_voltage = design_voltage;
         self.state = bst.state();
         self.voltage = voltage;
 
@@ -92,6 +107,22 @@ impl BatteryDevice for IoCtlDevice {
         self.energy_rate
     }
 
+    fn charge(&self) -> Option<ElectricCharge> {
+        self.charge
+    }
+
+    fn charge_full(&self) -> Option<ElectricCharge> {
+        self.charge_full
+    }
+
+    fn reports_energy(&self) -> bool {
+        self.charge.is_none()
+    }
+
+    fn conversion_voltage(&self) -> ElectricPotential {
+        self.design_voltage
+    }
+
     fn state(&self) -> State {
         self.state
     }
@@ -120,6 +151,11 @@ impl BatteryDevice for IoCtlDevice {
         self.technology
     }
 
+    fn kind(&self) -> Kind {
+        // ACPI batteries are system batteries only
+        Kind::System
+    }
+
     fn cycle_count(&self) -> Option<u32> {
         None
     }
@@ -4,7 +4,7 @@ use std::rc::Rc;
 
 use super::{IoCtlDevice, IoCtlManager};
 use crate::platform::traits::BatteryIterator;
-use crate::Result;
+use crate::{KindFilter, Result};
 
 pub struct IoCtlIterator {
     manager: Rc<IoCtlManager>,
@@ -46,8 +46,13 @@ impl BatteryIterator for IoCtlIterator {
     type Manager = IoCtlManager;
     type Device = IoCtlDevice;
 
-    fn new(manager: Rc<Self::Manager>) -> Result<Self> {
-        let batteries = manager.count()?;
+    fn with_filter(manager: Rc<Self::Manager>, filter: KindFilter) -> Result<Self> {
+        // ACPI batteries are system batteries only
+        let batteries = if filter.contains(KindFilter::SYSTEM) {
+            manager.count()?
+        } else {
+            0
+        };
 
         Ok(Self {
             manager,
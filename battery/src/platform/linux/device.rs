@@ -1,12 +1,17 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt;
 use std::io;
 use std::path::{Path, PathBuf};
 
 use crate::platform::traits::*;
-use crate::units::{ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature};
-use crate::{Error, Result, State, Technology};
+use crate::units::{ElectricCharge, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature};
+use crate::{Error, FieldError, Kind, KindFilter, RateKind, Result, State, Technology};
 
-use super::sysfs::{fs, DataBuilder, InstantData, Scope, Type};
+use super::sysfs::fs::Attr;
+#[cfg(feature = "fast-refresh")]
+use super::sysfs::FileCache;
+use super::sysfs::{fs, skip_restricted, Adapter, DataBuilder, Dir, Ideapad, InstantData, Scope, Type};
 
 pub struct SysFsDevice {
     root: PathBuf,
@@ -14,56 +19,166 @@ pub struct SysFsDevice {
 
     // These fields are "cached" outside from DataBuilder/InstantData,
     // since they're can't change with refresh
+    name: Option<String>,
     vendor: Option<String>,
     model: Option<String>,
     serial_number: Option<String>,
+    serial_number_raw: Option<Vec<u8>>,
     technology: Technology,
+    kind: Kind,
+    rechargeable: Option<bool>,
+    // External power supplies and vendor driver attributes are looked up once per enumeration,
+    // instead of listing the power supplies on each refresh
+    adapters: Vec<Adapter>,
+    ideapad: Option<Ideapad>,
+    // Static values, which were not read because of the insufficient permissions
+    restricted_fields: Vec<&'static str>,
+
+    // Attribute files opened on the first refresh
+    #[cfg(feature = "fast-refresh")]
+    cache: FileCache,
 }
 
 impl SysFsDevice {
-    pub fn is_system_battery<T: AsRef<Path>>(path: T) -> Result<bool> {
+    /// Classifies the power supply by its `type` and `scope` attributes only.
+    ///
+    /// Returns `None` for supplies which are not batteries (ex. AC adapters)
+    /// and for batteries with an unknown scope.
+    pub fn kind<T: AsRef<Path>>(path: T) -> Result<Option<KindFilter>> {
         let path = path.as_ref();
-        if fs::type_(path.join("type"))? == Type::Battery && fs::scope(path.join("scope"))? == Scope::System {
-            return Ok(true);
-        }
+        let kind = match fs::type_(path.join("type"))? {
+            // Scope is not read for the supplies, which are not batteries at all
+            Type::Battery => classify(Type::Battery, fs::scope(path.join("scope"))?),
+            type_ => classify(type_, Scope::Unknown),
+        };
+
+        Ok(kind.filter())
+    }
 
-        Ok(false)
+    /// Removable batteries might keep their sysfs directory even when they are physically removed,
+    /// reporting it via the `present` attribute instead.
+    /// Missing attribute means that battery is present.
+    pub fn is_present<T: AsRef<Path>>(path: T) -> Result<bool> {
+        fs::present(path.as_ref().join("present"))
     }
 
     pub fn try_from(root: PathBuf) -> Result<SysFsDevice> {
-        let builder = DataBuilder::new(&root);
-        let vendor = builder.manufacturer()?;
-        let model = builder.model()?;
-        let serial_number = builder.serial_number()?;
+        Self::try_from_dir(root, None)
+    }
+
+    /// Same as `try_from`, but reads the attributes relatively to the already opened `root` directory.
+    pub fn try_from_dir(root: PathBuf, dir: Option<&Dir>) -> Result<SysFsDevice> {
+        let _span = trace_span!("device", path = %root.display());
+        let name = root.file_name().map(|name| name.to_string_lossy().into_owned());
+        let builder = DataBuilder::with_dir(&root, dir);
+        let mut restricted_fields = Vec::new();
+        let vendor = skip_restricted(&mut restricted_fields, "vendor", builder.manufacturer())?;
+        let model = skip_restricted(&mut restricted_fields, "model", builder.model())?;
+        let serial_number_raw = skip_restricted(&mut restricted_fields, "serial_number", builder.serial_number())?;
+        let serial_number = serial_number_raw
+            .as_ref()
+            .map(|raw| String::from_utf8_lossy(raw).into_owned());
         let technology = builder.technology()?;
+        let scope = builder.scope()?;
+        let kind = classify(builder.type_()?, scope);
+        let rechargeable = is_rechargeable(technology, scope, reports_level_only(&root));
+        // Vendor charge modes are for the laptop batteries only
+        let ideapad = match scope {
+            Scope::Device => None,
+            _ => Ideapad::find(&root),
+        };
 
-        let source = builder.collect()?;
+        let adapters = Adapter::find_all(&root);
+
+        let source = builder
+            .with_adapters(&adapters)
+            .with_ideapad(ideapad.as_ref())
+            .collect()?;
 
         Ok(SysFsDevice {
+            #[cfg(feature = "fast-refresh")]
+            cache: FileCache::new(root.clone()),
             root,
             source,
+            name,
             vendor,
             model,
             serial_number,
+            serial_number_raw,
             technology,
+            kind,
+            rechargeable,
+            adapters,
+            ideapad,
+            restricted_fields,
         })
     }
 
     pub fn refresh(&mut self) -> Result<()> {
+        let _span = trace_span!("device", path = %self.root.display());
+        let dir = self.open()?;
+        self.source = self.builder(dir.as_ref()).collect()?;
+
+        Ok(())
+    }
+
+    /// Same as `refresh`, but the failed value reads are returned instead of failing the refresh.
+    pub fn refresh_partial(&mut self) -> Result<Vec<FieldError>> {
+        let _span = trace_span!("device", path = %self.root.display());
+        let dir = self.open()?;
+        let mut errors = Vec::new();
+        self.source = self
+            .builder(dir.as_ref())
+            .with_previous(&self.source)
+            .collect_partial(&mut errors);
+
+        Ok(errors)
+    }
+
+    /// Opens the device directory, so the attributes are read relatively to it
+    /// instead of allocating the full path for each one of them.
+    ///
+    /// Attribute files are opened already with the `fast-refresh` feature, so `None` is returned for it.
+    fn open(&self) -> Result<Option<Dir>> {
         // It is necessary to ensure that `self.root`
         // still exists and accessible.
         // See https://github.com/svartalf/rust-battery/issues/29
-        if self.root.is_dir() {
-            let builder = DataBuilder::new(&self.root);
-            self.source = builder.collect()?;
+        if !self.root.is_dir() {
+            let inner = io::Error::from(io::ErrorKind::NotFound);
+            let e = Error::new(inner, format!("Device directory `{:?}` is missing", self.root));
 
-            Ok(())
+            return Err(e);
+        }
+
+        let dir = if cfg!(feature = "fast-refresh") {
+            None
         } else {
+            Some(Dir::open(&self.root)?)
+        };
+        let present = match dir {
+            Some(ref dir) => fs::present(Attr::At(dir, "present"))?,
+            None => Self::is_present(&self.root)?,
+        };
+        if !present {
             let inner = io::Error::from(io::ErrorKind::NotFound);
-            let e = Error::new(inner, format!("Device directory `{:?}` is missing", self.root));
+            let e = Error::new(inner, format!("Device `{:?}` is not present", self.root));
 
-            Err(e)
+            return Err(e);
         }
+
+        Ok(dir)
+    }
+
+    fn builder<'a>(&'a self, dir: Option<&'a Dir>) -> DataBuilder<'a> {
+        #[cfg(feature = "fast-refresh")]
+        let builder = DataBuilder::with_cache(&self.root, dir, &self.cache);
+        #[cfg(not(feature = "fast-refresh"))]
+        let builder = DataBuilder::with_dir(&self.root, dir);
+
+        builder
+            .with_adapters(&self.adapters)
+            .with_ideapad(self.ideapad.as_ref())
+            .with_temperature_scale(self.source.temperature_scale)
     }
 }
 
@@ -88,22 +203,70 @@ impl BatteryDevice for SysFsDevice {
         self.source.energy_full_design
     }
 
+    fn energy_full_is_estimated(&self) -> bool {
+        self.source.energy_full_estimated
+    }
+
     fn energy_rate(&self) -> Power {
         self.source.energy_rate
     }
 
+    fn rate_kind(&self) -> Option<RateKind> {
+        self.source.rate_kind
+    }
+
     fn state(&self) -> State {
         self.source.state
     }
 
+    fn state_raw(&self) -> Option<Cow<'_, str>> {
+        self.source.state_raw.as_ref().map(|raw| Cow::Borrowed(raw.as_ref()))
+    }
+
     fn voltage(&self) -> ElectricPotential {
         self.source.voltage
     }
 
+    fn voltage_min_design(&self) -> Option<ElectricPotential> {
+        self.source.voltage_min_design
+    }
+
+    fn voltage_max_design(&self) -> Option<ElectricPotential> {
+        self.source.voltage_max_design
+    }
+
     fn temperature(&self) -> Option<ThermodynamicTemperature> {
         self.source.temperature
     }
 
+    fn ambient_temperature(&self) -> Option<ThermodynamicTemperature> {
+        self.source.ambient_temperature
+    }
+
+    fn temperature_min(&self) -> Option<ThermodynamicTemperature> {
+        self.source.temperature_min
+    }
+
+    fn temperature_max(&self) -> Option<ThermodynamicTemperature> {
+        self.source.temperature_max
+    }
+
+    fn temperature_alert_max(&self) -> Option<ThermodynamicTemperature> {
+        self.source.temperature_alert_max
+    }
+
+    fn raw_attributes(&self) -> Result<BTreeMap<String, String>> {
+        fs::attributes(&self.root)
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(AsRef::as_ref)
+    }
+
+    fn device_path(&self) -> Option<&str> {
+        self.root.to_str()
+    }
+
     fn vendor(&self) -> Option<&str> {
         self.vendor.as_ref().map(AsRef::as_ref)
     }
@@ -116,13 +279,132 @@ impl BatteryDevice for SysFsDevice {
         self.serial_number.as_ref().map(AsRef::as_ref)
     }
 
+    fn serial_number_raw(&self) -> Option<&[u8]> {
+        self.serial_number_raw.as_ref().map(AsRef::as_ref)
+    }
+
+    fn restricted_fields(&self) -> Vec<&'static str> {
+        let mut fields = self.restricted_fields.clone();
+        fields.extend_from_slice(&self.source.restricted_fields);
+
+        fields
+    }
+
     fn technology(&self) -> Technology {
         self.technology
     }
 
+    fn kind(&self) -> Kind {
+        self.kind
+    }
+
     fn cycle_count(&self) -> Option<u32> {
         self.source.cycle_count
     }
+
+    fn is_calibrating(&self) -> Option<bool> {
+        self.source.calibrating
+    }
+
+    fn capacity_alarm(&self) -> Option<Energy> {
+        self.source.capacity_alarm
+    }
+
+    #[cfg(feature = "control")]
+    fn set_capacity_alarm(&mut self, energy: Energy) -> Result<()> {
+        let value = DataBuilder::with_dir(&self.root, None).alarm_value(energy)?;
+        let path = self.root.join("alarm");
+        fs::set(&path, value)?;
+        self.refresh()?;
+
+        // Firmware stores the alarm with its own granularity (ACPI is using mWh or mAh),
+        // so the read back value might be slightly different from the written one
+        match fs::measurement(&path)? {
+            Some(actual) if (actual - value as f32).abs() < 1_000.0 => Ok(()),
+            _ => Err(Error::invalid_data(format!(
+                "Device has not accepted the `{}` alarm value",
+                value
+            ))),
+        }
+    }
+
+    fn charge_stop_threshold(&self) -> Option<Ratio> {
+        self.source.charge_stop_threshold
+    }
+
+    fn charge(&self) -> Option<ElectricCharge> {
+        self.source.charge
+    }
+
+    fn charge_full(&self) -> Option<ElectricCharge> {
+        self.source.charge_full
+    }
+
+    fn reports_energy(&self) -> bool {
+        self.source.reports_energy
+    }
+
+    /// Same designed voltage as the one charge values are converted into the energy ones with,
+    /// except for the rarely reported `voltage_present`.
+    fn conversion_voltage(&self) -> ElectricPotential {
+        self.source
+            .voltage_max_design
+            .or(self.source.voltage_min_design)
+            .unwrap_or(self.source.voltage)
+    }
+
+    fn max_charge_power(&self) -> Option<Power> {
+        self.source.max_charge_power
+    }
+
+    fn adapter_power(&self) -> Option<Power> {
+        self.source.adapter_power
+    }
+
+    fn external_power(&self) -> Option<bool> {
+        self.source.external_power
+    }
+
+    fn fast_charge_active(&self) -> Option<bool> {
+        self.source.fast_charge
+    }
+
+    fn is_rechargeable(&self) -> Option<bool> {
+        self.rechargeable
+    }
+}
+
+/// Guesses if the power supply is rechargeable:
+///
+///  1. Any known `technology` is rechargeable, since kernel has no values for the primary cells
+///  2. Batteries with `System` scope are rechargeable
+///  3. Batteries with `Device` scope, which are reporting `capacity_level` only, without any charge or energy values,
+///     are primary cells in peripherals (ex. mice running on AA cells)
+///  4. Otherwise it is unknown
+pub(super) fn is_rechargeable(technology: Technology, scope: Scope, level_only: bool) -> Option<bool> {
+    match (technology, scope) {
+        (Technology::Unknown, Scope::System) => Some(true),
+        (Technology::Unknown, Scope::Device) if level_only => Some(false),
+        (Technology::Unknown, _) => None,
+        _ => Some(true),
+    }
+}
+
+/// Classifies the power supply by its `type` and `scope`,
+/// supplies which are not batteries are of unknown kind.
+fn classify(type_: Type, scope: Scope) -> Kind {
+    match (type_, scope) {
+        (Type::Battery, Scope::System) => Kind::System,
+        (Type::Battery, Scope::Device) => Kind::Peripheral,
+        (Type::Ups, _) => Kind::Ups,
+        _ => Kind::Unknown,
+    }
+}
+
+fn reports_level_only(root: &Path) -> bool {
+    let values = ["energy_now", "energy_full", "charge_now", "charge_full", "capacity"];
+
+    root.join("capacity_level").exists() && values.iter().all(|name| !root.join(name).exists())
 }
 
 impl fmt::Debug for SysFsDevice {
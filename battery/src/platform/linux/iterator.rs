@@ -1,27 +1,33 @@
 use std::fmt;
-use std::fs::{self, ReadDir};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::vec;
 
 use super::{SysFsDevice, SysFsManager};
+use crate::platform::ordering::sort_natural;
 use crate::platform::traits::*;
-use crate::Result;
+use crate::{KindFilter, Result};
 
 pub struct SysFsIterator {
     #[allow(dead_code)]
     manager: Rc<SysFsManager>,
-    entries: ReadDir,
+    entries: vec::IntoIter<PathBuf>,
+    filter: KindFilter,
 }
 
 impl BatteryIterator for SysFsIterator {
     type Manager = SysFsManager;
     type Device = SysFsDevice;
 
-    fn new(manager: Rc<Self::Manager>) -> Result<Self> {
-        let entries = fs::read_dir(manager.path())?;
+    fn with_filter(manager: Rc<Self::Manager>, filter: KindFilter) -> Result<Self> {
+        let entries = sorted_entries(manager.path())?;
 
         Ok(SysFsIterator {
             manager,
-            entries,
+            entries: entries.into_iter(),
+            filter,
         })
     }
 }
@@ -31,24 +37,15 @@ impl Iterator for SysFsIterator {
 
     fn next(&mut self) -> Option<Self::Item> {
         loop {
-            return match self.entries.next() {
-                None => None,
-                // Unable to access sysfs for some reasons
-                Some(Err(e)) => Some(Err(e.into())),
-                Some(Ok(entry)) => {
-                    let path = entry.path();
-                    match SysFsDevice::is_system_battery(&path) {
-                        Ok(true) => Some(SysFsDevice::try_from(path)),
-                        Ok(false) => continue,
-                        Err(e) => Some(Err(e)),
-                    }
-                }
-            };
+            let path = self.entries.next()?;
+            if let Some(device) = load_device(path, self.filter) {
+                return Some(device);
+            }
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.entries.size_hint()
+        (0, Some(self.entries.len()))
     }
 }
 
@@ -61,3 +58,34 @@ impl fmt::Debug for SysFsIterator {
             .finish()
     }
 }
+
+/// Loads the power supply device from the `path` directory,
+/// returns `None` if it is not of the `filter` kinds or if the battery slot is empty at the moment.
+pub(super) fn load_device(path: PathBuf, filter: KindFilter) -> Option<Result<SysFsDevice>> {
+    match SysFsDevice::kind(&path) {
+        Ok(Some(kind)) if filter.contains(kind) => match SysFsDevice::is_present(&path) {
+            Ok(true) => Some(SysFsDevice::try_from(path)),
+            Ok(false) => None,
+            Err(e) => Some(Err(e)),
+        },
+        Ok(_) => None,
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// Lists the `root` directory entries.
+///
+/// `readdir` order is not guaranteed to be stable across boots,
+/// so entries are sorted by their names.
+pub(super) fn sorted_entries(root: &Path) -> Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(root)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    sort_natural(&mut entries, |path| file_name(path));
+
+    Ok(entries)
+}
+
+pub(super) fn file_name(path: &Path) -> &str {
+    path.file_name().and_then(|name| name.to_str()).unwrap_or_default()
+}
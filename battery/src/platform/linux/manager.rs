@@ -1,19 +1,47 @@
+use std::io;
+use std::panic;
 use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::thread;
 
 use super::device::SysFsDevice;
-use super::iterator::SysFsIterator;
+use super::iterator::{file_name, load_device, sorted_entries, SysFsIterator};
+use super::sysfs::fs::{self, Attr};
+use super::sysfs::{Dir, Scope, Type};
 use crate::platform::traits::*;
-use crate::Result;
+use crate::{FieldError, LinePower, Result};
 
 #[allow(clippy::redundant_static_lifetimes)]
 static SYSFS_ROOT: &'static str = "/sys/class/power_supply";
+/// ACPI platform profile, see the `Documentation/ABI/testing/sysfs-platform_profile` in the kernel sources.
+static PLATFORM_PROFILE: &str = "/sys/firmware/acpi/platform_profile";
+/// Persisted `power-profiles-daemon` state, which is the profile GNOME and KDE are displaying.
+static PPD_STATE: &str = "/var/lib/power-profiles-daemon/state.ini";
+
+/// `platform_profile` value for the low power mode.
+static LOW_POWER_PROFILE: &str = "low-power";
+/// `power-profiles-daemon` profile for the low power mode.
+static POWER_SAVER_PROFILE: &str = "power-saver";
 
 #[derive(Debug)]
 pub struct SysFsManager {
     root: PathBuf,
+    platform_profile: PathBuf,
+    ppd_state: PathBuf,
 }
 
 impl SysFsManager {
+    pub fn with_root<T: Into<PathBuf>>(root: T) -> SysFsManager {
+        let root = root.into();
+
+        SysFsManager {
+            // Profile files are missing, unless tests are creating them
+            platform_profile: root.join("platform_profile"),
+            ppd_state: root.join("state.ini"),
+            root,
+        }
+    }
+
     pub fn path(&self) -> &Path {
         self.root.as_path()
     }
@@ -25,10 +53,161 @@ impl BatteryManager for SysFsManager {
     fn new() -> Result<Self> {
         Ok(Self {
             root: PathBuf::from(SYSFS_ROOT),
+            platform_profile: PathBuf::from(PLATFORM_PROFILE),
+            ppd_state: PathBuf::from(PPD_STATE),
+        })
+    }
+
+    /// Nothing is kept open between the calls, but the paths are preserved for the tests.
+    fn reloaded(&self) -> Result<Self> {
+        Ok(Self {
+            root: self.root.clone(),
+            platform_profile: self.platform_profile.clone(),
+            ppd_state: self.ppd_state.clone(),
         })
     }
 
     fn refresh(&self, device: &mut SysFsDevice) -> Result<()> {
         device.refresh()
     }
+
+    fn refresh_partial(&self, device: &mut SysFsDevice) -> Result<Vec<FieldError>> {
+        device.refresh_partial()
+    }
+
+    /// `power-profiles-daemon` profile is checked first, since it is the one users are switching,
+    /// and it works even if the platform has no profiles support. Kernel platform profile is checked next.
+    fn battery_saver_active(&self) -> Result<Option<bool>> {
+        if let Some(state) = readable(fs::get_string(&self.ppd_state))? {
+            // `[State]` section of the INI file, ex. `Profile=power-saver`
+            let profile = state
+                .lines()
+                .map(str::trim)
+                .find(|line| line.starts_with("Profile="))
+                .map(|line| &line["Profile=".len()..]);
+            if let Some(profile) = profile {
+                return Ok(Some(profile.trim() == POWER_SAVER_PROFILE));
+            }
+        }
+
+        match readable(fs::get_string(&self.platform_profile))? {
+            Some(profile) => Ok(Some(profile.trim() == LOW_POWER_PROFILE)),
+            None => Ok(None),
+        }
+    }
+
+    /// `Mains` power supplies only, USB ones are usually the USB Type-C ports.
+    fn line_power(&self) -> Result<Vec<LinePower>> {
+        let root = Dir::open(self.path())?;
+        let mut supplies = vec![];
+
+        for path in sorted_entries(self.path())? {
+            let name = file_name(&path);
+            let entry = match root.open_dir(name) {
+                Ok(entry) => entry,
+                // Supply was removed after the listing, same as the enumeration skips it
+                Err(ref e) if e.kind() == io::ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if fs::type_(Attr::At(&entry, "type"))? != Type::Mains {
+                continue;
+            }
+
+            let online = fs::online(Attr::At(&entry, "online"))?;
+            let charge_type =
+                fs::get_string(Attr::At(&entry, "charge_type"))?.filter(|value| value != "Unknown" && value != "N/A");
+            supplies.push(LinePower::new(name.to_string(), online, charge_type));
+        }
+
+        Ok(supplies)
+    }
+
+    /// Power supply directory is loaded directly, without listing the other ones.
+    fn find_by_name(manager: &Rc<Self>, name: &str) -> Result<Option<SysFsDevice>> {
+        // Names are single path components, anything else would escape the sysfs root
+        if name.is_empty() || name == "." || name == ".." || name.contains('/') {
+            return Ok(None);
+        }
+        let path = manager.path().join(name);
+        if !path.is_dir() {
+            return Ok(None);
+        }
+
+        load_device(path, SysFsIterator::DEFAULT_FILTER).transpose()
+    }
+
+    /// Unlike the `SysFsIterator`, which resolves full attribute paths for each read,
+    /// power supply directory and each of its entries are opened only once here,
+    /// and attributes are read relatively to the opened entry directory.
+    fn scan(manager: &Rc<Self>) -> Result<(Vec<SysFsDevice>, Option<bool>)> {
+        let root = Dir::open(manager.path())?;
+        let mut devices = vec![];
+        let mut external_power = None;
+
+        for path in sorted_entries(manager.path())? {
+            let entry = root.open_dir(file_name(&path))?;
+            match fs::type_(Attr::At(&entry, "type"))? {
+                Type::Battery
+                    if fs::scope(Attr::At(&entry, "scope"))? == Scope::System
+                        && fs::present(Attr::At(&entry, "present"))? =>
+                {
+                    devices.push(SysFsDevice::try_from_dir(path, Some(&entry))?);
+                }
+                Type::Mains | Type::Usb => {
+                    if let Some(online) = fs::online(Attr::At(&entry, "online"))? {
+                        external_power = Some(online || external_power.unwrap_or(false));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok((devices, external_power))
+    }
+
+    /// Power supply entries are split into contiguous chunks, each loaded by its own thread,
+    /// and the chunks are joined in order, so devices are yielded in the same order as with `SysFsIterator`.
+    ///
+    /// Devices are not sharing any state, each thread opens its own attribute files.
+    fn scan_parallel(manager: &Rc<Self>, threads: usize) -> Result<Vec<SysFsDevice>> {
+        let entries = sorted_entries(manager.path())?;
+        let filter = SysFsIterator::DEFAULT_FILTER;
+        let threads = threads.min(entries.len());
+        if threads <= 1 {
+            return entries
+                .into_iter()
+                .filter_map(|path| load_device(path, filter))
+                .collect();
+        }
+
+        #[allow(clippy::manual_div_ceil)] // `usize::div_ceil` is not available for the supported Rust versions
+        let chunk_size = (entries.len() + threads - 1) / threads;
+        let workers = entries
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let chunk = chunk.to_vec();
+                thread::Builder::new()
+                    .name("battery-scan".to_string())
+                    .spawn(move || -> Result<Vec<SysFsDevice>> {
+                        chunk.into_iter().filter_map(|path| load_device(path, filter)).collect()
+                    })
+            })
+            .collect::<io::Result<Vec<_>>>()?;
+
+        let mut devices = Vec::with_capacity(entries.len());
+        for worker in workers {
+            let chunk = worker.join().unwrap_or_else(|e| panic::resume_unwind(e))?;
+            devices.extend(chunk);
+        }
+
+        Ok(devices)
+    }
+}
+
+/// Treats the files, which can't be read by the current user, as missing ones.
+fn readable(result: Result<Option<String>>) -> Result<Option<String>> {
+    match result {
+        Err(ref e) if e.kind() == io::ErrorKind::PermissionDenied => Ok(None),
+        result => result,
+    }
 }
@@ -0,0 +1,179 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// From the `errno.h`.
+const ENODEV: i32 = 19;
+
+/// Buffer size for a single `pread(2)` call.
+///
+/// sysfs attributes are mostly short numeric values, longer ones are read in a few calls.
+const CHUNK_SIZE: usize = 64;
+
+/// How long the missing attribute is not looked up again.
+///
+/// Some attributes are appearing later, ex. when the driver finishes the battery probing
+/// or when the charger is plugged in, but looking them up on each refresh would defeat the cache.
+const MISSING_TTL: Duration = Duration::from_secs(10);
+
+/// Opened attribute file, which can be read at any offset.
+pub trait AttributeFile: Sized {
+    fn open(path: &Path) -> io::Result<Self>;
+
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize>;
+}
+
+impl AttributeFile for File {
+    fn open(path: &Path) -> io::Result<File> {
+        File::open(path)
+    }
+
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        FileExt::read_at(self, buf, offset)
+    }
+}
+
+#[derive(Debug)]
+enum Entry<F> {
+    Opened(F),
+    // Attribute was missing at that moment
+    Missing(Instant),
+}
+
+/// Attribute files of a single device, which are opened once and re-read with `pread(2)` on each refresh,
+/// so the refresh costs one syscall per attribute instead of the `open(2)`, `read(2)` and `close(2)` triple.
+///
+/// Missing attributes are remembered too, so they are looked up again only after the `MISSING_TTL`.
+///
+/// Files are `F` instead of the `File` for the tests only.
+#[derive(Debug)]
+pub struct FileCache<F = File> {
+    root: PathBuf,
+    chunk_size: usize,
+    missing_ttl: Duration,
+    files: Mutex<HashMap<String, Entry<F>>>,
+}
+
+impl FileCache {
+    /// `chunk_size` can't be larger than the default one.
+    #[cfg(test)]
+    pub fn with_chunk_size(root: PathBuf, chunk_size: usize) -> FileCache {
+        assert!(chunk_size <= CHUNK_SIZE);
+        FileCache {
+            root,
+            chunk_size,
+            missing_ttl: MISSING_TTL,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl<F: AttributeFile> FileCache<F> {
+    pub fn new(root: PathBuf) -> FileCache<F> {
+        FileCache {
+            root,
+            chunk_size: CHUNK_SIZE,
+            missing_ttl: MISSING_TTL,
+            files: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_missing_ttl(self, missing_ttl: Duration) -> FileCache<F> {
+        FileCache {
+            missing_ttl,
+            ..self
+        }
+    }
+
+    /// Reads the whole `name` attribute file content as a string.
+    #[cfg(test)]
+    pub fn read_to_string(&self, name: &str) -> io::Result<String> {
+        let mut content = Vec::new();
+        self.read_into(name, &mut content)?;
+
+        String::from_utf8(content).map_err(|_| io::Error::from(io::ErrorKind::InvalidData))
+    }
+
+    /// Appends the whole `name` attribute file content to the `buffer`.
+    ///
+    /// Descriptors are becoming stale when the device disappears and the kernel starts to fail reads
+    /// from them with `ENODEV`, even if the device with the same name appears again.
+    /// In that case the file is reopened once, and the reopen result is returned.
+    pub fn read_into(&self, name: &str, buffer: &mut Vec<u8>) -> io::Result<()> {
+        // Lock is never contended, but keeps the `Battery` to be `Sync` with this feature enabled
+        let mut files = match self.files.lock() {
+            Ok(files) => files,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let is_known = match files.get(name) {
+            Some(Entry::Opened(_)) => true,
+            Some(Entry::Missing(since)) => since.elapsed() < self.missing_ttl,
+            None => false,
+        };
+        if !is_known {
+            let entry = self.open(name)?;
+            files.insert(name.to_string(), entry);
+        }
+
+        let start = buffer.len();
+        let result = match files.get(name) {
+            Some(Entry::Opened(file)) => self.read_file(file, buffer),
+            _ => return Err(io::Error::from(io::ErrorKind::NotFound)),
+        };
+
+        match result {
+            Err(ref e) if e.raw_os_error() == Some(ENODEV) => {
+                trace_debug!(attribute = name, "stale file descriptor, reopening");
+                files.remove(name);
+                buffer.truncate(start);
+                let entry = self.open(name)?;
+                let result = match entry {
+                    Entry::Opened(ref file) => self.read_file(file, buffer),
+                    Entry::Missing(_) => Err(io::Error::from(io::ErrorKind::NotFound)),
+                };
+                files.insert(name.to_string(), entry);
+                result
+            }
+            Err(e) => {
+                // Other errors might be temporary, so the file is opened again on the next read
+                files.remove(name);
+                Err(e)
+            }
+            result => result,
+        }
+    }
+
+    /// Returns the `Entry::Missing` if the attribute does not exist.
+    fn open(&self, name: &str) -> io::Result<Entry<F>> {
+        match F::open(&self.root.join(name)) {
+            Ok(file) => Ok(Entry::Opened(file)),
+            Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(Entry::Missing(Instant::now())),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Reads the file from the start with as many `pread(2)` calls as needed.
+    ///
+    /// sysfs is returning the whole value in one call, so the short read means that the value is over,
+    /// and another call is made only if the value does not fit into the buffer.
+    fn read_file(&self, file: &F, buffer: &mut Vec<u8>) -> io::Result<()> {
+        let mut chunk = [0; CHUNK_SIZE];
+        let chunk = &mut chunk[..self.chunk_size];
+        let mut offset = 0;
+        loop {
+            let read = file.read_at(chunk, offset)?;
+            buffer.extend_from_slice(&chunk[..read]);
+            offset += read as u64;
+            if read < chunk.len() {
+                break;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,56 @@
+use std::ffi::CString;
+use std::fs::File;
+use std::io;
+use std::os::unix::io::{AsRawFd, FromRawFd};
+use std::path::Path;
+
+/// Names shorter than that are passed to `openat(2)` from the stack instead of the allocated `CString`;
+/// sysfs attribute names are way shorter.
+const NAME_BUFFER_SIZE: usize = 64;
+
+/// Opened sysfs directory.
+///
+/// Files inside of it are opened with `openat(2)` relatively to the directory descriptor,
+/// so the kernel does not need to resolve the whole `/sys/class/power_supply/{name}/` path
+/// for each attribute read.
+#[derive(Debug)]
+pub struct Dir(File);
+
+impl Dir {
+    pub fn open<T: AsRef<Path>>(path: T) -> io::Result<Dir> {
+        File::open(path).map(Dir)
+    }
+
+    /// Opens the `name` subdirectory, following the symlinks,
+    /// as `/sys/class/power_supply` entries are symlinks to the device directories.
+    pub fn open_dir(&self, name: &str) -> io::Result<Dir> {
+        self.open_at(name, libc::O_DIRECTORY).map(Dir)
+    }
+
+    pub fn open_file(&self, name: &str) -> io::Result<File> {
+        self.open_at(name, 0)
+    }
+
+    fn open_at(&self, name: &str, flags: libc::c_int) -> io::Result<File> {
+        let bytes = name.as_bytes();
+        if bytes.len() < NAME_BUFFER_SIZE && !bytes.contains(&0) {
+            let mut buffer = [0; NAME_BUFFER_SIZE];
+            buffer[..bytes.len()].copy_from_slice(bytes);
+            self.open_at_c(buffer.as_ptr() as *const libc::c_char, flags)
+        } else {
+            let name = CString::new(name).map_err(|_| io::Error::from(io::ErrorKind::InvalidInput))?;
+            self.open_at_c(name.as_ptr(), flags)
+        }
+    }
+
+    /// `name` should be a valid pointer to the NUL-terminated string.
+    fn open_at_c(&self, name: *const libc::c_char, flags: libc::c_int) -> io::Result<File> {
+        let fd = unsafe { libc::openat(self.0.as_raw_fd(), name, libc::O_RDONLY | libc::O_CLOEXEC | flags) };
+
+        if fd < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(unsafe { File::from_raw_fd(fd) })
+        }
+    }
+}
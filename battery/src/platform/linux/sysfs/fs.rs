@@ -1,23 +1,99 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::error;
-use std::fs::read_to_string;
-use std::io;
-use std::path::Path;
-use std::str::FromStr;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::str::{self, FromStr};
 
-use super::{Scope, Type};
-use crate::units::{ElectricCharge, ElectricPotential, Energy, Power};
+#[cfg(feature = "fast-refresh")]
+use super::FileCache;
+use super::{Dir, Scope, Type};
+use crate::units::{ElectricCharge, ElectricPotential, Energy, Power, ThermodynamicTemperature};
+#[cfg(feature = "control")]
+use crate::Error;
 use crate::Result;
 
 // From the `errno.h`.
 // Easier than building whole `libc` dep.
 const ENODEV: i32 = 19;
 
+/// 200 °C in tenths of a degree, which no battery is going to survive.
+const MILLIDEGREES_THRESHOLD: f32 = 2000.0;
+
+/// "Unknown value" markers used by the Smart Battery (`0xFFFF`) and ACPI (`0xFFFFFFFF`) specifications.
+///
+/// Gauges might return them during the firmware update or recalibration, and some drivers
+/// are passing them to sysfs as is, or multiplied by `1000` while converting the mWh/mA values into µWh/µA.
+const SENTINELS: [f32; 4] = [
+    0xFFFF as f32,
+    0xFFFF as f32 * 1_000.0,
+    0xFFFF_FFFFu32 as f32,
+    0xFFFF_FFFFu32 as f32 * 1_000.0,
+];
+
+/// Initial capacity of the per-thread buffer the attributes are read into before parsing.
+///
+/// `File::read_to_end` reserves the reported file size, which is a page for the sysfs attributes,
+/// so the buffer is allocated once and reused by all the reads after that.
+const SCRATCH_CAPACITY: usize = 4096;
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<u8>> = RefCell::new(Vec::with_capacity(SCRATCH_CAPACITY));
+}
+
+/// sysfs attribute file, which can be read by its full path
+/// or by its name relative to the already opened device directory.
+pub trait Attribute {
+    fn file_name(&self) -> Cow<'_, str>;
+
+    /// Appends the file content to the `buffer`.
+    fn read_into(&self, buffer: &mut Vec<u8>) -> io::Result<()>;
+}
+
+impl<T: AsRef<Path>> Attribute for T {
+    fn file_name(&self) -> Cow<'_, str> {
+        self.as_ref().file_name().unwrap().to_string_lossy()
+    }
+
+    fn read_into(&self, buffer: &mut Vec<u8>) -> io::Result<()> {
+        File::open(self)?.read_to_end(buffer).map(|_| ())
+    }
+}
+
+pub enum Attr<'d> {
+    Path(PathBuf),
+    At(&'d Dir, &'d str),
+    #[cfg(feature = "fast-refresh")]
+    Cached(&'d FileCache, &'d str),
+}
+
+impl<'d> Attribute for Attr<'d> {
+    fn file_name(&self) -> Cow<'_, str> {
+        match self {
+            Attr::Path(path) => Attribute::file_name(path),
+            Attr::At(_, name) => Cow::Borrowed(name),
+            #[cfg(feature = "fast-refresh")]
+            Attr::Cached(_, name) => Cow::Borrowed(name),
+        }
+    }
+
+    fn read_into(&self, buffer: &mut Vec<u8>) -> io::Result<()> {
+        match self {
+            Attr::Path(path) => path.read_into(buffer),
+            Attr::At(dir, name) => dir.open_file(name)?.read_to_end(buffer).map(|_| ()),
+            #[cfg(feature = "fast-refresh")]
+            Attr::Cached(cache, name) => cache.read_into(name, buffer),
+        }
+    }
+}
+
 /// Read µWh value from the `energy_` file and convert into `Energy` type.
-pub fn energy<T: AsRef<Path>>(path: T) -> Result<Option<Energy>> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy().starts_with("energy_"));
+pub fn energy<T: Attribute>(attr: T) -> Result<Option<Energy>> {
+    debug_assert!(attr.file_name().starts_with("energy_"));
 
-    match get::<f32, _>(path) {
+    match measurement(attr) {
         Ok(Some(value_uwh)) => Ok(Some(microwatt_hour!(value_uwh))),
         Ok(None) => Ok(None),
         Err(e) => Err(e),
@@ -25,11 +101,10 @@ pub fn energy<T: AsRef<Path>>(path: T) -> Result<Option<Energy>> {
 }
 
 /// Read µAh value from the `charge_` file and convert into `ElectricCharge` type.
-pub fn charge<T: AsRef<Path>>(path: T) -> Result<Option<ElectricCharge>> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy().starts_with("charge_"));
+pub fn charge<T: Attribute>(attr: T) -> Result<Option<ElectricCharge>> {
+    debug_assert!(attr.file_name().starts_with("charge_"));
 
-    match get::<f32, _>(path) {
+    match measurement(attr) {
         Ok(Some(value_uah)) if value_uah > 1.0 => Ok(Some(microampere_hour!(value_uah))),
         Ok(Some(_)) => Ok(None),
         Ok(None) => Ok(None),
@@ -38,11 +113,10 @@ pub fn charge<T: AsRef<Path>>(path: T) -> Result<Option<ElectricCharge>> {
 }
 
 /// Read µV value from the `voltage_` file and convert into `ElectricPotential` type.
-pub fn voltage<T: AsRef<Path>>(path: T) -> Result<Option<ElectricPotential>> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy().starts_with("voltage_"));
+pub fn voltage<T: Attribute>(attr: T) -> Result<Option<ElectricPotential>> {
+    debug_assert!(attr.file_name().starts_with("voltage_"));
 
-    match get::<f32, _>(path) {
+    match measurement(attr) {
         Ok(Some(value_uv)) if value_uv > 1.0 => Ok(Some(microvolt!(value_uv))),
         Ok(Some(_)) => Ok(None),
         Ok(None) => Ok(None),
@@ -51,11 +125,10 @@ pub fn voltage<T: AsRef<Path>>(path: T) -> Result<Option<ElectricPotential>> {
 }
 
 /// Read µW value from the `power_` file and convert into `Power` type.
-pub fn power<T: AsRef<Path>>(path: T) -> Result<Option<Power>> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy().starts_with("power_"));
+pub fn power<T: Attribute>(attr: T) -> Result<Option<Power>> {
+    debug_assert!(attr.file_name().starts_with("power_"));
 
-    match get::<f32, _>(path) {
+    match measurement(attr) {
         Ok(Some(value_uw)) if value_uw > 10_000.0 => Ok(Some(microwatt!(value_uw))),
         Ok(Some(_)) => Ok(None),
         Ok(None) => Ok(None),
@@ -63,12 +136,67 @@ pub fn power<T: AsRef<Path>>(path: T) -> Result<Option<Power>> {
     }
 }
 
+/// Read raw value from the `temp` file (or one of the `temp_` files),
+/// which is converted into `ThermodynamicTemperature` type by the device `TemperatureScale`.
+pub fn temperature<T: Attribute>(attr: T) -> Result<Option<f32>> {
+    debug_assert!(attr.file_name().starts_with("temp"));
+
+    measurement(attr)
+}
+
+/// Unit the device driver reports all of its `temp` and `temp_` attributes in.
+///
+/// Values are defined to be in tenths of a degree Celsius, yet some drivers are reporting millidegrees
+/// as the thermal zones do. Unit can't be told by a single value (`1500` is either 150 °C or 1.5 °C),
+/// so it is detected for the whole device instead: once any of its temperatures is way above
+/// the battery operating range, device is known to report millidegrees.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum TemperatureScale {
+    Decidegrees,
+    Millidegrees,
+}
+
+impl TemperatureScale {
+    /// Returns the scale for the raw `values` read from the device,
+    /// which was detected to be using `self` scale before.
+    pub fn detect(self, values: &[Option<f32>]) -> TemperatureScale {
+        let millidegrees = values
+            .iter()
+            .filter_map(|value| *value)
+            .any(|value| value.abs() >= MILLIDEGREES_THRESHOLD);
+        if millidegrees {
+            TemperatureScale::Millidegrees
+        } else {
+            self
+        }
+    }
+
+    /// Converts the raw attribute value into `ThermodynamicTemperature` type.
+    pub fn convert(self, value: f32) -> ThermodynamicTemperature {
+        match self {
+            TemperatureScale::Decidegrees => celsius!(value / 10.0),
+            TemperatureScale::Millidegrees => celsius!(value / 1000.0),
+        }
+    }
+}
+
+/// Read numeric value from the measurement file, treating the "unknown value" markers as a missing value.
+pub fn measurement<T: Attribute>(attr: T) -> Result<Option<f32>> {
+    match parse::<f32, _>(&attr) {
+        Ok(Some(value)) if SENTINELS.contains(&value) => {
+            trace_warn!(attribute = %attr.file_name(), value, "unknown value marker is ignored");
+            Ok(None)
+        }
+        Ok(value) => Ok(value),
+        Err(e) => Err(e),
+    }
+}
+
 /// Read device `type` file and convert into `Type` enum.
-pub fn type_<T: AsRef<Path>>(path: T) -> Result<Type> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy() == "type");
+pub fn type_<T: Attribute>(attr: T) -> Result<Type> {
+    debug_assert!(attr.file_name() == "type");
 
-    match get::<Type, _>(path) {
+    match get::<Type, _>(attr) {
         Ok(Some(value)) => Ok(value),
         Ok(None) => Ok(Type::Unknown),
         Err(e) => Err(e),
@@ -76,30 +204,98 @@ pub fn type_<T: AsRef<Path>>(path: T) -> Result<Type> {
 }
 
 /// Read device `scope` file and convert into `Scope` enum.
-pub fn scope<T: AsRef<Path>>(path: T) -> Result<Scope> {
-    let path = path.as_ref();
-    debug_assert!(path.file_name().unwrap().to_string_lossy() == "scope");
+pub fn scope<T: Attribute>(attr: T) -> Result<Scope> {
+    debug_assert!(attr.file_name() == "scope");
 
-    match get::<Scope, _>(path) {
+    match get::<Scope, _>(attr) {
         Ok(Some(value)) => Ok(value),
         Ok(None) => Ok(Scope::System),
         Err(e) => Err(e),
     }
 }
 
+/// Read device `present` file and convert into `bool`.
+pub fn present<T: Attribute>(attr: T) -> Result<bool> {
+    debug_assert!(attr.file_name() == "present");
+
+    match get::<u8, _>(attr) {
+        Ok(Some(value)) => Ok(value != 0),
+        Ok(None) => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read `online` file of the external power supply and convert into `bool`.
+pub fn online<T: Attribute>(attr: T) -> Result<Option<bool>> {
+    debug_assert!(attr.file_name() == "online");
+
+    match get::<u8, _>(attr) {
+        Ok(Some(value)) => Ok(Some(value != 0)),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes `value` into the existing attribute file.
+///
+/// Attributes are writable by root only, so the permission error is described explicitly.
+#[cfg(feature = "control")]
+pub fn set<T: AsRef<Path>, V: std::fmt::Display>(path: T, value: V) -> Result<()> {
+    use std::io::Write;
+
+    let path = path.as_ref();
+    let result = std::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .and_then(|mut file| writeln!(file, "{}", value));
+    trace_debug!(attribute = %path.display(), %value, ?result, "write");
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => Err(Error::not_found(format!(
+            "Attribute `{}` does not exist",
+            path.display()
+        ))),
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            let description = format!(
+                "Permission denied to write `{}`, root privileges are required",
+                path.display()
+            );
+            Err(Error::new(e, description))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
 /// ## Returns
 ///
 /// Ok(Some(value)) - file was read properly
 /// Ok(None) - file is missing
 /// Err(_) - unable to access file for some reasons (except `NotFound` and `ENODEV`)
-pub fn get_string<T: AsRef<Path>>(path: T) -> Result<Option<String>> {
-    match read_to_string(path) {
-        Ok(mut content) => {
-            if content.starts_with('\0') {
+pub fn get_string<T: Attribute>(attr: T) -> Result<Option<String>> {
+    get_with(attr, str::to_string)
+}
+
+/// Same as `get_string`, but the file content is passed to `f` instead of being copied into the `String`,
+/// so the value known in advance can be returned without any allocations.
+pub fn get_with<T, F, R>(attr: T, f: F) -> Result<Option<R>>
+where
+    T: Attribute,
+    F: FnOnce(&str) -> R,
+{
+    with_str(&attr, f)
+}
+
+/// Same as `get_string`, but returns the file content as is, even if it is not a valid UTF-8.
+pub fn get_bytes<T: Attribute>(attr: T) -> Result<Option<Vec<u8>>> {
+    let mut content = Vec::new();
+    let result = match attr.read_into(&mut content) {
+        Ok(()) => {
+            if content.starts_with(b"\0") {
                 Err(io::Error::from(io::ErrorKind::InvalidData).into())
             } else {
-                // In-place trim
-                if content.ends_with('\n') {
+                if content.ends_with(b"\n") {
                     content.truncate(content.len() - 1);
                 }
 
@@ -107,25 +303,89 @@ pub fn get_string<T: AsRef<Path>>(path: T) -> Result<Option<String>> {
             }
         }
         Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(ref e) if e.raw_os_error() == Some(ENODEV) => Ok(None),
+        Err(e) => Err(e.into()),
+    };
+    trace_debug!(attribute = %attr.file_name(), value = ?result);
+
+    result
+}
+
+/// Reads all the attribute files of the device directory at `path`, with the values as they are reported
+/// by the driver, lossy converted into UTF-8 and trimmed.
+///
+/// Subdirectories and links (ex. `device` or `power`) and the `uevent` file, which duplicates the attributes,
+/// are skipped; so are the attributes which can't be read (ex. write-only ones
+/// or the ones not supported by the firmware), since they have no value to show.
+pub fn attributes<T: AsRef<Path>>(path: T) -> Result<BTreeMap<String, String>> {
+    let mut attributes = BTreeMap::new();
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() || entry.file_name() == "uevent" {
+            continue;
+        }
+
+        if let Ok(Some(value)) = get_bytes(entry.path()) {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            attributes.insert(name, String::from_utf8_lossy(&value).trim().to_string());
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// Passes the attribute content to `f`, reading it into the per-thread scratch buffer.
+fn with_str<T, F, R>(attr: &T, f: F) -> Result<Option<R>>
+where
+    T: Attribute,
+    F: FnOnce(&str) -> R,
+{
+    SCRATCH.with(|scratch| {
+        let mut scratch = scratch.borrow_mut();
+        Ok(read_str(attr, &mut scratch)?.map(f))
+    })
+}
+
+/// Reads the attribute into the cleared `buffer`, returning its content without the trailing newline.
+fn read_str<'b, T: Attribute>(attr: &T, buffer: &'b mut Vec<u8>) -> Result<Option<&'b str>> {
+    buffer.clear();
+    let result = match attr.read_into(buffer) {
+        Ok(()) => match str::from_utf8(buffer) {
+            Ok(content) if content.starts_with('\0') => Err(io::Error::from(io::ErrorKind::InvalidData).into()),
+            Ok(content) if content.ends_with('\n') => Ok(Some(&content[..content.len() - 1])),
+            Ok(content) => Ok(Some(content)),
+            Err(_) => Err(io::Error::from(io::ErrorKind::InvalidData).into()),
+        },
+        Err(ref e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
         // Some drivers are creating the files, but attempt to read them
         // fails with a `ENODEV` error.
         // See https://github.com/svartalf/rust-battery/issues/28
         Err(ref e) if e.raw_os_error() == Some(ENODEV) => Ok(None),
         Err(e) => Err(e.into()),
-    }
+    };
+    trace_debug!(attribute = %attr.file_name(), value = ?result);
+
+    result
 }
 
-pub fn get<V, T>(path: T) -> Result<Option<V>>
+pub fn get<V, T>(attr: T) -> Result<Option<V>>
 where
-    T: AsRef<Path>,
+    T: Attribute,
     V: FromStr,
     <V as FromStr>::Err: error::Error + Sync + Send,
 {
-    match get_string(path) {
-        Ok(Some(ref value)) => match V::from_str(value) {
-            Ok(result) => Ok(Some(result)),
-            Err(_) => Ok(None),
-        },
+    parse(&attr)
+}
+
+fn parse<V, T>(attr: &T) -> Result<Option<V>>
+where
+    T: Attribute,
+    V: FromStr,
+    <V as FromStr>::Err: error::Error + Sync + Send,
+{
+    match with_str(attr, V::from_str) {
+        Ok(Some(Ok(result))) => Ok(Some(result)),
+        Ok(Some(Err(_))) => Ok(None),
         Ok(None) => Ok(None),
         Err(e) => Err(e),
     }
@@ -1,10 +1,18 @@
 use std::io;
 use std::str::FromStr;
 
+#[cfg(feature = "fast-refresh")]
+mod cache;
+mod dir;
 pub mod fs;
 mod source;
 
-pub use self::source::{DataBuilder, InstantData};
+#[cfg(all(test, feature = "fast-refresh"))]
+pub use self::cache::AttributeFile;
+#[cfg(feature = "fast-refresh")]
+pub use self::cache::FileCache;
+pub use self::dir::Dir;
+pub use self::source::{skip_restricted, Adapter, DataBuilder, Ideapad, InstantData};
 
 #[allow(dead_code)]
 #[derive(Debug, Eq, PartialEq)]
@@ -34,7 +42,7 @@ impl FromStr for Type {
 
 /// A power supply which doesn't have a "scope" attribute should be assumed to
 /// have "System" scope.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub enum Scope {
     /// Powers a specific device, or tree of devices
     Device,
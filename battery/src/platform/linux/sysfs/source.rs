@@ -1,14 +1,23 @@
+use std::borrow::Cow;
+use std::cell::Cell;
 use std::f32;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use lazycell::LazyCell;
 use num_traits::identities::Zero;
 
-use super::fs;
+use super::fs::{self, Attr, TemperatureScale};
+#[cfg(feature = "fast-refresh")]
+use super::FileCache;
+use super::{Dir, Scope, Type};
+#[cfg(feature = "control")]
+use crate::units::electric_charge::microampere_hour;
+#[cfg(feature = "control")]
+use crate::units::energy::microwatt_hour;
 use crate::units::power::{microwatt, watt};
 use crate::units::{Bound, ElectricCharge, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature};
-use crate::{Error, Result, State, Technology};
+use crate::{Error, FieldError, RateKind, Result, State, Technology};
 
 #[derive(Debug)]
 pub struct InstantData {
@@ -18,57 +27,351 @@ pub struct InstantData {
     pub energy: Energy,
     pub energy_full: Energy,
     pub energy_full_design: Energy,
+    // `energy_full` is substituted with the design value
+    pub energy_full_estimated: bool,
     pub energy_rate: Power,
+    // `None` if there is no rate attribute at all
+    pub rate_kind: Option<RateKind>,
+    // Natively reported values, `None` for the energy-based drivers
+    pub charge: Option<ElectricCharge>,
+    pub charge_full: Option<ElectricCharge>,
+    // `false` if the energy values are converted from the charge ones
+    pub reports_energy: bool,
     pub voltage: ElectricPotential,
+    pub voltage_min_design: Option<ElectricPotential>,
+    pub voltage_max_design: Option<ElectricPotential>,
     pub state: State,
+    pub state_raw: Option<Cow<'static, str>>,
     pub temperature: Option<ThermodynamicTemperature>,
+    pub ambient_temperature: Option<ThermodynamicTemperature>,
+    pub temperature_min: Option<ThermodynamicTemperature>,
+    pub temperature_max: Option<ThermodynamicTemperature>,
+    pub temperature_alert_max: Option<ThermodynamicTemperature>,
+    // Unit the temperatures above were reported in
+    pub temperature_scale: TemperatureScale,
     pub cycle_count: Option<u32>,
+    pub calibrating: Option<bool>,
+    pub capacity_alarm: Option<Energy>,
+    pub fast_charge: Option<bool>,
+    pub adapter_power: Option<Power>,
+    // Whether any of the external power supplies is online, `None` if there are none
+    pub external_power: Option<bool>,
+    pub max_charge_power: Option<Power>,
+    pub charge_stop_threshold: Option<Ratio>,
+    // Optional values, which were not read because of the insufficient permissions
+    pub restricted_fields: Vec<&'static str>,
+}
+
+/// Lenovo IdeaPad ACPI driver devices, relative to the `/sys/class/power_supply` directory.
+static IDEAPAD_ACPI: &str = "../../bus/platform/drivers/ideapad_acpi";
+
+/// Lenovo `ideapad_acpi` driver device attributes.
+///
+/// Driver does not map its charge modes into the `charge_type` attribute
+/// and exposes them as its own device attributes instead.
+#[derive(Debug)]
+pub struct Ideapad {
+    rapid_charge: PathBuf,
+    conservation_mode: PathBuf,
+}
+
+impl Ideapad {
+    /// Finds the driver device next to the battery `root` directory.
+    ///
+    /// Driver device is looked up once per battery, and IO errors are ignored
+    /// in order not to fail the battery because of the vendor attributes.
+    pub fn find(root: &Path) -> Option<Ideapad> {
+        let drivers = root.parent()?.join(IDEAPAD_ACPI);
+        std::fs::read_dir(drivers)
+            .ok()?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .find(|path| path.join("rapid_charge").is_file() || path.join("conservation_mode").is_file())
+            .map(|path| Ideapad {
+                rapid_charge: path.join("rapid_charge"),
+                conservation_mode: path.join("conservation_mode"),
+            })
+    }
+
+    fn fast_charge(&self) -> Option<bool> {
+        let rapid_charge = fs::get::<u8, _>(&self.rapid_charge).ok()?;
+        let conservation_mode = fs::get::<u8, _>(&self.conservation_mode).ok()?;
+        match (rapid_charge, conservation_mode) {
+            (Some(rapid_charge), _) => Some(rapid_charge != 0),
+            // Conservation mode keeps the battery partially charged, which rules out the rapid charge
+            (None, Some(conservation_mode)) if conservation_mode != 0 => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// External power supply (ex. AC adapter or USB-C port) attributes.
+#[derive(Debug)]
+pub struct Adapter {
+    online: PathBuf,
+    voltage_max: PathBuf,
+    current_max: PathBuf,
+}
+
+impl Adapter {
+    /// Finds the external power supplies, which are the siblings of the battery `root` directory.
+    ///
+    /// Supplies are looked up once per battery, and IO errors are ignored
+    /// in order not to fail the battery because of them.
+    pub fn find_all(root: &Path) -> Vec<Adapter> {
+        let entries = match root.parent().map(std::fs::read_dir) {
+            Some(Ok(entries)) => entries,
+            _ => return Vec::new(),
+        };
+
+        entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.as_path() != root)
+            .filter(|path| match fs::type_(path.join("type")) {
+                Ok(type_) => type_ == Type::Mains || type_ == Type::Usb,
+                Err(_) => false,
+            })
+            .map(|path| Adapter {
+                online: path.join("online"),
+                voltage_max: path.join("voltage_max"),
+                current_max: path.join("current_max"),
+            })
+            .collect()
+    }
+
+    fn is_online(&self) -> Option<bool> {
+        fs::online(&self.online).ok()?
+    }
+
+    fn power(&self) -> Option<Power> {
+        if self.is_online() != Some(true) {
+            return None;
+        }
+
+        // USB Power Delivery sources are reporting the negotiated contract this way
+        let voltage = fs::voltage(&self.voltage_max).ok()??;
+        let current = fs::measurement(&self.current_max).ok()??;
+        if current > 0.0 {
+            Some(microampere!(current) * voltage)
+        } else {
+            None
+        }
+    }
 }
 
 pub struct DataBuilder<'p> {
     root: &'p Path,
+    // Opened `root` directory, if available
+    dir: Option<&'p Dir>,
+    // Already opened attribute files, if available
+    #[cfg(feature = "fast-refresh")]
+    cache: Option<&'p FileCache>,
+    // External power supplies
+    adapters: &'p [Adapter],
+    // Vendor driver attributes, if found
+    ideapad: Option<&'p Ideapad>,
+    // Temperatures unit detected by the previous reads
+    temperature_scale: TemperatureScale,
+    // Values of the previous read, kept for the values which failed to be read
+    previous: Option<&'p InstantData>,
 
     design_voltage: LazyCell<ElectricPotential>,
+    energy_now: LazyCell<Option<Energy>>,
+    charge_now: LazyCell<Option<ElectricCharge>>,
+    charge_full: LazyCell<Option<ElectricCharge>>,
+    // `charge_full` or `charge_full_design`, whichever is available
+    charge_capacity: LazyCell<ElectricCharge>,
     energy: LazyCell<Energy>,
     energy_full: LazyCell<Energy>,
     energy_full_design: LazyCell<Energy>,
+    // Set once `energy_full` falls back to the design value
+    energy_full_estimated: Cell<bool>,
     energy_rate: LazyCell<Power>,
+    // Set once `energy_rate` is read from any attribute
+    rate_kind: Cell<Option<RateKind>>,
 
     state_of_health: LazyCell<Ratio>,
     state_of_charge: LazyCell<Ratio>,
 
+    status: LazyCell<Option<Cow<'static, str>>>,
     state: LazyCell<State>,
 }
 
 impl<'p> DataBuilder<'p> {
-    pub fn new(path: &'p Path) -> DataBuilder<'p> {
+    pub fn with_dir(path: &'p Path, dir: Option<&'p Dir>) -> DataBuilder<'p> {
         DataBuilder {
             root: path,
+            dir,
+            #[cfg(feature = "fast-refresh")]
+            cache: None,
+            adapters: &[],
+            ideapad: None,
+            temperature_scale: TemperatureScale::Decidegrees,
+            previous: None,
 
             design_voltage: LazyCell::new(),
+            energy_now: LazyCell::new(),
+            charge_now: LazyCell::new(),
+            charge_full: LazyCell::new(),
+            charge_capacity: LazyCell::new(),
             energy: LazyCell::new(),
             energy_full: LazyCell::new(),
             energy_full_design: LazyCell::new(),
+            energy_full_estimated: Cell::new(false),
             energy_rate: LazyCell::new(),
+            rate_kind: Cell::new(None),
             state_of_health: LazyCell::new(),
             state_of_charge: LazyCell::new(),
+            status: LazyCell::new(),
             state: LazyCell::new(),
         }
     }
 
+    /// Same as `with_dir`, but reads the attributes from the already opened files.
+    #[cfg(feature = "fast-refresh")]
+    pub fn with_cache(path: &'p Path, dir: Option<&'p Dir>, cache: &'p FileCache) -> DataBuilder<'p> {
+        DataBuilder {
+            cache: Some(cache),
+            ..Self::with_dir(path, dir)
+        }
+    }
+
+    /// Reads the adapter power from the `adapters` found.
+    pub fn with_adapters(self, adapters: &'p [Adapter]) -> DataBuilder<'p> {
+        DataBuilder {
+            adapters,
+            ..self
+        }
+    }
+
+    /// Reads the charge modes from the Lenovo driver attributes as well.
+    pub fn with_ideapad(self, ideapad: Option<&'p Ideapad>) -> DataBuilder<'p> {
+        DataBuilder {
+            ideapad,
+            ..self
+        }
+    }
+
+    /// Keeps reading the temperatures in the `scale` detected by the previous reads.
+    pub fn with_temperature_scale(self, temperature_scale: TemperatureScale) -> DataBuilder<'p> {
+        DataBuilder {
+            temperature_scale,
+            ..self
+        }
+    }
+
+    /// Keeps the `previous` values for the ones which failed to be read by the `collect_partial`.
+    pub fn with_previous(self, previous: &'p InstantData) -> DataBuilder<'p> {
+        DataBuilder {
+            previous: Some(previous),
+            ..self
+        }
+    }
+
     pub fn collect(self) -> Result<InstantData> {
-        Ok(InstantData {
-            state_of_charge: *self.state_of_charge()?,
-            state_of_health: *self.state_of_health()?,
-            energy: *self.energy()?,
-            energy_full: *self.energy_full()?,
-            energy_full_design: *self.energy_full_design()?,
-            energy_rate: *self.energy_rate()?,
-            voltage: self.voltage()?,
-            state: *self.state()?,
-            temperature: self.temperature()?,
-            cycle_count: self.cycle_count()?,
-        })
+        let mut errors = Vec::new();
+        // Best effort values are `None` if they can't be read, without failing the whole collection
+        let data = self.collect_fields(&mut errors, &mut Vec::new());
+
+        match errors.into_iter().next() {
+            Some(error) => Err(error.into_error()),
+            None => Ok(data),
+        }
+    }
+
+    /// Same as `collect`, but the failed values are stored into `errors` and replaced with the fallback ones:
+    /// the `previous` values if there are any, or zero and unknown ones otherwise.
+    pub fn collect_partial(self, errors: &mut Vec<FieldError>) -> InstantData {
+        let mut best_effort = Vec::new();
+        let data = self.collect_fields(errors, &mut best_effort);
+        errors.append(&mut best_effort);
+
+        data
+    }
+
+    /// Collects the values, storing the failed reads of the required and optional values into `errors`,
+    /// and of the best effort ones, which do not fail the `collect`, into `best_effort`.
+    fn collect_fields(self, errors: &mut Vec<FieldError>, best_effort: &mut Vec<FieldError>) -> InstantData {
+        let previous = self.previous;
+        macro_rules! fallback {
+            ($field:ident, $default:expr) => {
+                previous.map_or($default, |data| data.$field.clone())
+            };
+        }
+
+        let mut field = FieldCollector {
+            errors,
+            best_effort,
+            restricted: Vec::new(),
+        };
+
+        // Driver reports all the temperatures in the same unit, so it is detected by all of them together
+        let temperatures = [
+            field.optional("temperature", fs::temperature(self.attr("temp"))),
+            field.best_effort("ambient_temperature", fs::temperature(self.attr("temp_ambient"))),
+            field.best_effort("temperature_min", fs::temperature(self.attr("temp_min"))),
+            field.best_effort("temperature_max", fs::temperature(self.attr("temp_max"))),
+            field.best_effort("temperature_alert_max", fs::temperature(self.attr("temp_alert_max"))),
+        ];
+        let temperature_scale = self.temperature_scale.detect(&temperatures);
+        let [temperature, ambient_temperature, temperature_min, temperature_max, temperature_alert_max] = temperatures;
+        let celsius = |value: Option<f32>| value.map(|value| temperature_scale.convert(value));
+
+        let mut data = InstantData {
+            state_of_charge: field.get_ref(
+                "state_of_charge",
+                self.state_of_charge(),
+                fallback!(state_of_charge, Ratio::zero()),
+            ),
+            state_of_health: field.get_ref(
+                "state_of_health",
+                self.state_of_health(),
+                fallback!(state_of_health, Ratio::zero()),
+            ),
+            energy: field.get_ref("energy", self.energy(), fallback!(energy, Energy::zero())),
+            energy_full: field.get_ref(
+                "energy_full",
+                self.energy_full(),
+                fallback!(energy_full, Energy::zero()),
+            ),
+            energy_full_design: field.get_ref(
+                "energy_full_design",
+                self.energy_full_design(),
+                fallback!(energy_full_design, Energy::zero()),
+            ),
+            // Evaluated after the `energy_full` above, which sets it
+            energy_full_estimated: self.energy_full_estimated.get(),
+            energy_rate: field.get_ref("energy_rate", self.energy_rate(), fallback!(energy_rate, Power::zero())),
+            // Evaluated after the `energy_rate` above, which sets it
+            rate_kind: self.rate_kind.get(),
+            charge: self.charge_now(),
+            charge_full: field.best_effort("charge_full", self.charge_full()),
+            reports_energy: self.energy_now().is_some(),
+            voltage: field.get("voltage", self.voltage(), fallback!(voltage, ElectricPotential::zero())),
+            voltage_min_design: field.best_effort("voltage_min_design", fs::voltage(self.attr("voltage_min_design"))),
+            voltage_max_design: field.best_effort("voltage_max_design", fs::voltage(self.attr("voltage_max_design"))),
+            state: field.get_ref("state", self.state(), fallback!(state, State::Unknown)),
+            state_raw: field.get_ref("state_raw", self.status(), fallback!(state_raw, None)),
+            temperature: celsius(temperature),
+            ambient_temperature: celsius(ambient_temperature),
+            temperature_min: celsius(temperature_min),
+            temperature_max: celsius(temperature_max),
+            temperature_alert_max: celsius(temperature_alert_max),
+            temperature_scale,
+            cycle_count: field.optional("cycle_count", self.cycle_count()),
+            calibrating: field.best_effort("is_calibrating", self.calibrating()),
+            capacity_alarm: field.best_effort("capacity_alarm", self.capacity_alarm()),
+            fast_charge: field.best_effort("fast_charge_active", self.fast_charge()),
+            adapter_power: self.adapter_power(),
+            external_power: self.external_power(),
+            max_charge_power: field.best_effort("max_charge_power", self.max_charge_power()),
+            charge_stop_threshold: field.best_effort("charge_stop_threshold", self.charge_stop_threshold()),
+            restricted_fields: Vec::new(),
+        };
+        data.restricted_fields = field.restricted;
+
+        data
     }
 
     fn design_voltage(&self) -> Result<&ElectricPotential> {
@@ -80,7 +383,7 @@ impl<'p> DataBuilder<'p> {
                 "voltage_now",
             ]
             .iter()
-            .filter_map(|filename| match fs::voltage(self.root.join(filename)) {
+            .filter_map(|filename| match fs::voltage(self.attr(filename)) {
                 Ok(Some(value)) => Some(value),
                 _ => None,
             })
@@ -92,40 +395,64 @@ impl<'p> DataBuilder<'p> {
         })
     }
 
-    // Not cached because used only once
     // IO errors are ignored, since later calculations will handle `None` result
     fn energy_now(&self) -> Option<Energy> {
-        ["energy_now", "energy_avg"]
-            .iter()
-            .filter_map(|filename| match fs::energy(self.root.join(filename)) {
-                Ok(Some(value)) => Some(value),
-                _ => None,
-            })
-            .next()
+        *self.energy_now.borrow_with(|| {
+            ["energy_now", "energy_avg"]
+                .iter()
+                .filter_map(|filename| match fs::energy(self.attr(filename)) {
+                    Ok(Some(value)) => Some(value),
+                    _ => None,
+                })
+                .next()
+        })
     }
 
-    // Not cached because used only once.
     // IO errors are ignored, since later calculations will handle `None` result
     fn charge_now(&self) -> Option<ElectricCharge> {
-        ["charge_now", "charge_avg"]
-            .iter()
-            .filter_map(|filename| match fs::charge(self.root.join(filename)) {
-                Ok(Some(value)) => Some(value),
-                _ => None,
-            })
-            .next()
+        *self.charge_now.borrow_with(|| {
+            ["charge_now", "charge_avg"]
+                .iter()
+                .filter_map(|filename| match fs::charge(self.attr(filename)) {
+                    Ok(Some(value)) => Some(value),
+                    _ => None,
+                })
+                .next()
+        })
     }
 
-    // Not cached because used only once
-    fn charge_full(&self) -> ElectricCharge {
-        ["charge_full", "charge_full_design"]
-            .iter()
-            .filter_map(|filename| match fs::charge(self.root.join(filename)) {
-                Ok(Some(value)) => Some(value),
-                _ => None,
-            })
-            .next()
-            .unwrap_or_else(|| microampere_hour!(0.0))
+    fn charge_full(&self) -> Result<Option<ElectricCharge>> {
+        // Failed read is not cached, so it is tried again by the next caller
+        match self
+            .charge_full
+            .try_borrow_with(|| fs::charge(self.attr("charge_full")))
+        {
+            Ok(value) => Ok(*value),
+            Err(e) => Err(e),
+        }
+    }
+
+    // Used to tell the charge-based drivers apart, so IO errors are treated as a missing value
+    fn charge_capacity(&self) -> ElectricCharge {
+        *self.charge_capacity.borrow_with(|| match self.charge_full() {
+            Ok(Some(value)) => value,
+            _ => match fs::charge(self.attr("charge_full_design")) {
+                Ok(Some(value)) => value,
+                _ => microampere_hour!(0.0),
+            },
+        })
+    }
+
+    // Not cached because used only once.
+    // `charge_full_design` is not used as a fallback here, since it would overestimate
+    // the state of charge for the worn out batteries.
+    fn charge_ratio(&self) -> Option<Ratio> {
+        let charge_full = match self.charge_full() {
+            Ok(Some(value)) if value.is_sign_positive() && !value.is_zero() => value,
+            _ => return None,
+        };
+
+        self.charge_now().map(|charge| (charge / charge_full).into_bounded())
     }
 
     pub fn state_of_health(&self) -> Result<&Ratio> {
@@ -145,7 +472,7 @@ impl<'p> DataBuilder<'p> {
             Some(energy) => Ok(energy),
             None => match self.charge_now() {
                 Some(charge) => Ok(charge * *self.design_voltage()?),
-                None => match fs::get::<f32, _>(self.root.join("capacity")) {
+                None => match fs::measurement(self.attr("capacity")) {
                     Ok(Some(capacity)) => Ok(*self.energy_full()? * percent!(capacity).into_bounded()),
                     _ => Err(Error::not_found("Unable to calculate device energy value")),
                 },
@@ -155,11 +482,14 @@ impl<'p> DataBuilder<'p> {
 
     fn energy_full(&self) -> Result<&Energy> {
         self.energy_full
-            .try_borrow_with(|| match fs::energy(self.root.join("energy_full")) {
+            .try_borrow_with(|| match fs::energy(self.attr("energy_full")) {
                 Ok(Some(value)) => Ok(value),
-                Ok(None) => match fs::charge(self.root.join("charge_full")) {
+                Ok(None) => match self.charge_full() {
                     Ok(Some(value)) => Ok(value * *self.design_voltage()?),
-                    Ok(None) => Ok(*self.energy_full_design()?),
+                    Ok(None) => {
+                        self.energy_full_estimated.set(true);
+                        Ok(*self.energy_full_design()?)
+                    }
                     Err(e) => Err(e),
                 },
                 Err(e) => Err(e),
@@ -168,9 +498,9 @@ impl<'p> DataBuilder<'p> {
 
     fn energy_full_design(&self) -> Result<&Energy> {
         self.energy_full_design.try_borrow_with(|| {
-            match fs::energy(self.root.join("energy_full_design")) {
+            match fs::energy(self.attr("energy_full_design")) {
                 Ok(Some(value)) => Ok(value),
-                Ok(None) => match fs::charge(self.root.join("charge_full_design")) {
+                Ok(None) => match fs::charge(self.attr("charge_full_design")) {
                     Ok(Some(value)) => Ok(value * *self.design_voltage()?),
                     // It is possible that both `energy_full_design` and `charge_full_design`
                     // files might be missing, see #40.
@@ -178,7 +508,10 @@ impl<'p> DataBuilder<'p> {
                     // It will affect other parameters calculation,
                     // and in a future versions this function probably should return
                     // `Result<Option<Energy>>` instead to mark missing value.
-                    Ok(None) => Ok(microwatt_hour!(0.0)),
+                    Ok(None) => {
+                        trace_warn!("design capacity is missing, falling back to zero");
+                        Ok(microwatt_hour!(0.0))
+                    }
                     Err(e) => Err(e),
                 },
                 Err(e) => Err(e),
@@ -188,34 +521,41 @@ impl<'p> DataBuilder<'p> {
 
     fn energy_rate(&self) -> Result<&Power> {
         self.energy_rate.try_borrow_with(|| {
-            let value = match fs::power(self.root.join("power_now"))? {
-                Some(power) => Some(power),
-                None => {
-                    match fs::get::<f32, _>(self.root.join("current_now"))? {
-                        Some(current_now) => {
-                            // If charge_full exists, then current_now is always reported in µA.
-                            // In the legacy case, where energy only units exist, and power_now isn't present
-                            // current_now is power in µW.
-                            // Source: upower
-                            if !self.charge_full().is_zero() {
-                                // µA then
-                                Some(microampere!(current_now) * *self.design_voltage()?)
-                            } else {
-                                // µW :|
-                                Some(microwatt!(current_now))
-                            }
-                        }
-                        None => None,
-                    }
+            // `*_now` attributes are instant readings, while `*_avg` ones are averaged by the driver
+            let value = match self.rate("power_now", "current_now")? {
+                Some(power) => {
+                    self.rate_kind.set(Some(RateKind::Instantaneous));
+                    Some(power)
                 }
+                None => match self.rate("power_avg", "current_avg")? {
+                    Some(power) => {
+                        self.rate_kind.set(Some(RateKind::Averaged));
+                        Some(power)
+                    }
+                    None => None,
+                },
             };
 
             let value = value
                 // Sanity check if power is greater than 100W (upower)
-                .map(|power| if power.get::<watt>() > 100.0 { watt!(0.0) } else { power })
+                .map(|power| {
+                    if power.get::<watt>() > 100.0 {
+                        trace_warn!(
+                            watts = power.get::<watt>(),
+                            "energy rate is too high, resetting to zero"
+                        );
+                        watt!(0.0)
+                    } else {
+                        power
+                    }
+                })
                 // Some batteries give out massive rate values when nearly empty (upower)
                 .map(|power| {
                     if power.get::<microwatt>() < 10.0 {
+                        trace_warn!(
+                            microwatts = power.get::<microwatt>(),
+                            "energy rate is too low, resetting to zero"
+                        );
                         watt!(0.0)
                     } else {
                         power
@@ -232,6 +572,7 @@ impl<'p> DataBuilder<'p> {
                     // TODO: There might be a chance that we had lost a precision during the conversion
                     // from the microwatts into default watts, so this should be fixed
                     if (power.get::<watt>() - 65535.0).abs() < f32::EPSILON {
+                        trace_warn!("energy rate is an ACPI `Ones` value, resetting to zero");
                         watt!(0.0)
                     } else {
                         power
@@ -248,9 +589,49 @@ impl<'p> DataBuilder<'p> {
         })
     }
 
+    // Reads the rate from the `power` attribute, falling back to the `current` one
+    fn rate(&self, power: &'p str, current: &'p str) -> Result<Option<Power>> {
+        if let Some(power) = fs::power(self.attr(power))? {
+            return Ok(Some(power));
+        }
+
+        match fs::measurement(self.attr(current))? {
+            Some(current_now) => {
+                // If charge_full exists, then current_now is always reported in µA.
+                // In the legacy case, where energy only units exist, and power_now isn't present
+                // current_now is power in µW.
+                // Source: upower
+                //
+                // Some charge-based drivers are not providing `charge_full*` attributes at all,
+                // so `charge_now` presence is checked too.
+                // Some drivers are reporting negative `current_now` while discharging.
+                //
+                // Design voltage is used instead of the `voltage_now`, since it is used for `energy`
+                // calculation too, and time estimations should be the same as with charge and current.
+                if current_now.is_sign_negative() {
+                    trace_warn!(current_now, "negative current value is used as an absolute one");
+                }
+                if !self.charge_capacity().is_zero() || self.charge_now().is_some() {
+                    // µA then
+                    Ok(Some(microampere!(current_now.abs()) * *self.design_voltage()?))
+                } else {
+                    // µW :|
+                    Ok(Some(microwatt!(current_now)))
+                }
+            }
+            None => Ok(None),
+        }
+    }
+
     fn state_of_charge(&self) -> Result<&Ratio> {
         self.state_of_charge.try_borrow_with(|| {
-            match fs::get::<f32, _>(self.root.join("capacity")) {
+            // Charge ratio goes first, since it does not depend on the voltage at all,
+            // while `energy` for charge-based drivers is calculated with it
+            if let Some(ratio) = self.charge_ratio() {
+                return Ok(ratio);
+            }
+
+            match fs::measurement(self.attr("capacity")) {
                 Ok(Some(capacity)) => Ok(percent!(capacity).into_bounded()),
                 Ok(None) if self.energy_full()?.is_sign_positive() => Ok(*self.energy()? / *self.energy_full()?),
                 // Same as upower, falling back to 0.0%
@@ -260,20 +641,23 @@ impl<'p> DataBuilder<'p> {
         })
     }
 
+    fn status(&self) -> Result<&Option<Cow<'static, str>>> {
+        self.status
+            .try_borrow_with(|| fs::get_with(self.attr("status"), known_status))
+    }
+
     fn state(&self) -> Result<&State> {
-        self.state
-            .try_borrow_with(|| match fs::get::<State, _>(self.root.join("status")) {
-                Ok(Some(state)) => Ok(state),
-                Ok(None) => Ok(State::Unknown),
-                Err(e) => Err(e),
-            })
+        self.state.try_borrow_with(|| match self.status()? {
+            Some(status) => Ok(status.parse().unwrap_or(State::Unknown)),
+            None => Ok(State::Unknown),
+        })
     }
 
     fn voltage(&self) -> Result<ElectricPotential> {
         let mut value =
             ["voltage_now", "voltage_avg"]
                 .iter()
-                .filter_map(|filename| match fs::voltage(self.root.join(filename)) {
+                .filter_map(|filename| match fs::voltage(self.attr(filename)) {
                     Ok(Some(value)) => Some(value),
                     _ => None,
                 });
@@ -284,16 +668,8 @@ impl<'p> DataBuilder<'p> {
         }
     }
 
-    fn temperature(&self) -> Result<Option<ThermodynamicTemperature>> {
-        match fs::get::<f32, _>(self.root.join("temp")) {
-            Ok(Some(value)) => Ok(Some(celsius!(value / 10.0))),
-            Ok(None) => Ok(None),
-            Err(e) => Err(e),
-        }
-    }
-
     fn cycle_count(&self) -> Result<Option<u32>> {
-        fs::get::<u32, _>(self.root.join("cycle_count")).map(|value| {
+        fs::get::<u32, _>(self.attr("cycle_count")).map(|value| {
             // Handling zero cycles count as a non-existing value.
             // Reason: some drivers are creating `cycle_count` with zero value
             // even for old batteries.
@@ -301,32 +677,237 @@ impl<'p> DataBuilder<'p> {
             // (real one this time), it is better just to ignore this value.
             // See: https://github.com/svartalf/rust-battery/issues/23
             match value {
-                Some(cycles) if cycles == 0 => None,
+                Some(cycles) if cycles == 0 => {
+                    trace_debug!("zero cycle count is ignored");
+                    None
+                }
+                // Smart Battery "unknown value" marker
+                Some(0xFFFF) => {
+                    trace_warn!("cycle count unknown value marker is ignored");
+                    None
+                }
                 Some(cycles) => Some(cycles),
                 None => None,
             }
         })
     }
 
+    fn calibrating(&self) -> Result<Option<bool>> {
+        Ok(fs::get::<u8, _>(self.attr("calibrate"))?.map(|value| value != 0))
+    }
+
+    fn capacity_alarm(&self) -> Result<Option<Energy>> {
+        match fs::measurement(self.attr("alarm"))? {
+            // Zero value means that alarm is not set
+            Some(value) if value > 0.0 => {
+                // `alarm` is reported in the same units as the rest of the capacity attributes
+                if !self.charge_capacity().is_zero() || self.charge_now().is_some() {
+                    Ok(Some(microampere_hour!(value) * *self.design_voltage()?))
+                } else {
+                    Ok(Some(microwatt_hour!(value)))
+                }
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Converts `energy` into the `alarm` attribute value,
+    /// which is reported in the same units as the rest of the capacity attributes.
+    #[cfg(feature = "control")]
+    pub fn alarm_value(&self, energy: Energy) -> Result<u64> {
+        let value = if !self.charge_capacity().is_zero() || self.charge_now().is_some() {
+            (energy / *self.design_voltage()?).get::<microampere_hour>()
+        } else {
+            energy.get::<microwatt_hour>()
+        };
+
+        if value.is_finite() && value >= 0.0 {
+            Ok(value.round() as u64)
+        } else {
+            Err(Error::new(
+                io::Error::from(io::ErrorKind::InvalidInput),
+                "Alarm energy should be a non-negative value",
+            ))
+        }
+    }
+
+    fn charge_stop_threshold(&self) -> Result<Option<Ratio>> {
+        match fs::measurement(self.attr("charge_control_end_threshold"))? {
+            Some(value) if value > 0.0 => Ok(Some(percent!(value).into_bounded())),
+            _ => Ok(None),
+        }
+    }
+
+    fn max_charge_power(&self) -> Result<Option<Power>> {
+        let current = match fs::measurement(self.attr("constant_charge_current_max"))? {
+            Some(value) if value > 0.0 => microampere!(value),
+            _ => return Ok(None),
+        };
+        let voltage = match fs::measurement(self.attr("constant_charge_voltage_max"))? {
+            Some(value) if value > 1.0 => microvolt!(value),
+            _ => *self.design_voltage()?,
+        };
+
+        Ok(Some(current * voltage))
+    }
+
+    fn fast_charge(&self) -> Result<Option<bool>> {
+        // `charge_type` values are defined by the kernel, see `POWER_SUPPLY_CHARGE_TYPE_*`;
+        // vendor drivers are mapping their own modes into them, ex. Dell "Express" charge is reported as "Fast"
+        let fast = fs::get_with(self.attr("charge_type"), |value| match value {
+            "Fast" => Some(true),
+            "Unknown" | "N/A" => None,
+            _ => Some(false),
+        })?;
+
+        match fast.and_then(|fast| fast) {
+            Some(fast) => Ok(Some(fast)),
+            None => Ok(self.ideapad.and_then(Ideapad::fast_charge)),
+        }
+    }
+
+    fn adapter_power(&self) -> Option<Power> {
+        self.adapters
+            .iter()
+            .filter_map(Adapter::power)
+            .fold(None, |max: Option<Power>, power| match max {
+                Some(max) if max >= power => Some(max),
+                _ => Some(power),
+            })
+    }
+
+    fn external_power(&self) -> Option<bool> {
+        self.adapters
+            .iter()
+            .filter_map(Adapter::is_online)
+            .fold(None, |connected, online| Some(online || connected.unwrap_or(false)))
+    }
+
+    fn attr(&self, name: &'p str) -> Attr<'p> {
+        #[cfg(feature = "fast-refresh")]
+        {
+            if let Some(cache) = self.cache {
+                return Attr::Cached(cache, name);
+            }
+        }
+
+        match self.dir {
+            Some(dir) => Attr::At(dir, name),
+            None => Attr::Path(self.root.join(name)),
+        }
+    }
+
     // Following methods are not cached in the struct
 
+    pub fn type_(&self) -> Result<Type> {
+        fs::type_(self.attr("type"))
+    }
+
+    pub fn scope(&self) -> Result<Scope> {
+        fs::scope(self.attr("scope"))
+    }
+
     pub fn manufacturer(&self) -> Result<Option<String>> {
-        fs::get_string(self.root.join("manufacturer"))
+        fs::get_string(self.attr("manufacturer"))
     }
 
     pub fn model(&self) -> Result<Option<String>> {
-        fs::get_string(self.root.join("model_name"))
+        fs::get_string(self.attr("model_name"))
     }
 
-    pub fn serial_number(&self) -> Result<Option<String>> {
-        fs::get_string(self.root.join("serial_number"))
+    /// Serial number is read as is, since some batteries are reporting the binary ones.
+    pub fn serial_number(&self) -> Result<Option<Vec<u8>>> {
+        fs::get_bytes(self.attr("serial_number"))
     }
 
     pub fn technology(&self) -> Result<Technology> {
-        match fs::get::<Technology, _>(self.root.join("technology")) {
+        match fs::get::<Technology, _>(self.attr("technology")) {
             Ok(Some(tech)) => Ok(tech),
             Ok(None) => Ok(Technology::Unknown),
             Err(e) => Err(e),
         }
     }
 }
+
+/// `status` attribute values defined by the kernel,
+/// see `POWER_SUPPLY_STATUS_*` in the `drivers/power/supply/power_supply_sysfs.c`.
+const KNOWN_STATUSES: &[&str] = &["Unknown", "Charging", "Discharging", "Not charging", "Full"];
+
+/// Replaces `value` with a static string if it is one of the well-known values,
+/// so the allocated string is not kept for the most of the devices.
+fn known_status(value: &str) -> Cow<'static, str> {
+    match KNOWN_STATUSES.iter().find(|status| **status == value) {
+        Some(status) => Cow::Borrowed(status),
+        None => Cow::Owned(value.to_string()),
+    }
+}
+
+/// Replaces the permission error for the optional value with `None`, recording the value `name` into `restricted`.
+///
+/// Some drivers are making the attributes (ex. `serial_number`) readable by root only,
+/// which should not fail the whole battery for the unprivileged users.
+pub fn skip_restricted<T>(
+    restricted: &mut Vec<&'static str>,
+    name: &'static str,
+    result: Result<Option<T>>,
+) -> Result<Option<T>> {
+    match result {
+        Err(ref e) if e.kind() == io::ErrorKind::PermissionDenied => {
+            trace_debug!(field = name, "permission denied, value is skipped");
+            restricted.push(name);
+            Ok(None)
+        }
+        result => result,
+    }
+}
+
+/// Records the failed values reads.
+struct FieldCollector<'e> {
+    errors: &'e mut Vec<FieldError>,
+    best_effort: &'e mut Vec<FieldError>,
+    restricted: Vec<&'static str>,
+}
+
+impl<'e> FieldCollector<'e> {
+    fn get<T>(&mut self, name: &'static str, result: Result<T>, fallback: T) -> T {
+        match result {
+            Ok(value) => value,
+            Err(e) => self.record(name, e, fallback),
+        }
+    }
+
+    /// Same as `get`, but for the optional values, which are skipped if they can't be read
+    /// because of the insufficient permissions.
+    fn optional<T>(&mut self, name: &'static str, result: Result<Option<T>>) -> Option<T> {
+        let result = skip_restricted(&mut self.restricted, name, result);
+        self.get(name, result, None)
+    }
+
+    /// Same as `optional`, but the failed reads are stored apart, as they should not fail the `collect`.
+    ///
+    /// Used for the diagnostic values, which are not essential for the battery to be loaded.
+    fn best_effort<T>(&mut self, name: &'static str, result: Result<Option<T>>) -> Option<T> {
+        match skip_restricted(&mut self.restricted, name, result) {
+            Ok(value) => value,
+            Err(e) => {
+                trace_warn!(field = name, error = %e, "unable to read value");
+                self.best_effort.push(FieldError::new(name, e));
+                None
+            }
+        }
+    }
+
+    /// Same as `get`, but for the values cached by the `DataBuilder`.
+    fn get_ref<T: Clone>(&mut self, name: &'static str, result: Result<&T>, fallback: T) -> T {
+        match result {
+            Ok(value) => value.clone(),
+            Err(e) => self.record(name, e, fallback),
+        }
+    }
+
+    fn record<T>(&mut self, name: &'static str, e: Error, fallback: T) -> T {
+        trace_warn!(field = name, error = %e, "unable to read value");
+        self.errors.push(FieldError::new(name, e));
+        fallback
+    }
+}
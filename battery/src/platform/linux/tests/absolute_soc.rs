@@ -0,0 +1,55 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::units::ratio::percent;
+use crate::Battery;
+
+#[test]
+fn test_worn_out_battery() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Full",
+        "energy_now" => 40000000,
+        "energy_full" => 40000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.relative_soc(), battery.state_of_charge());
+    assert_abs_diff_eq!(battery.relative_soc().get::<percent>(), 100.0, epsilon = 0.001);
+    assert_abs_diff_eq!(battery.absolute_soc().unwrap().get::<percent>(), 80.0, epsilon = 0.001);
+}
+
+#[test]
+fn test_charge_based_driver() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "charge_full" => 4000000,
+        "charge_full_design" => 5000000,
+        "voltage_min_design" => 11100000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.relative_soc().get::<percent>(), 50.0, epsilon = 0.001);
+    assert_abs_diff_eq!(battery.absolute_soc().unwrap().get::<percent>(), 40.0, epsilon = 0.001);
+}
+
+// Design capacity is missing, same as in the #40 issue
+#[test]
+fn test_missing_design_capacity() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.relative_soc().get::<percent>(), 50.0, epsilon = 0.001);
+    assert_eq!(battery.absolute_soc(), None);
+}
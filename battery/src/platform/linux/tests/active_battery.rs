@@ -0,0 +1,37 @@
+use super::battery_with;
+use crate::{active_battery, Battery};
+
+fn battery(status: &str, power_now: u32) -> Battery {
+    battery_with(&[("status", status), ("power_now", &power_now.to_string())])
+}
+
+// Two packs are discharged sequentially, as in ThinkPads
+#[test]
+fn test_sequential_discharge() {
+    // First pack is in use
+    let batteries = vec![battery("Discharging", 9000000), battery("Unknown", 0)];
+    assert_eq!(active_battery(&batteries), Some(0));
+
+    // Handoff moment, both of them are reporting discharging state
+    let batteries = vec![battery("Discharging", 1000000), battery("Discharging", 9000000)];
+    assert_eq!(active_battery(&batteries), Some(1));
+
+    // Second pack is in use, first one is drained
+    let batteries = vec![battery("Discharging", 0), battery("Discharging", 9000000)];
+    assert_eq!(active_battery(&batteries), Some(1));
+
+    // Equal rates, lower index wins
+    let batteries = vec![battery("Discharging", 9000000), battery("Discharging", 9000000)];
+    assert_eq!(active_battery(&batteries), Some(0));
+}
+
+#[test]
+fn test_fallback_to_charging() {
+    let batteries = vec![battery("Full", 0), battery("Charging", 20000000)];
+    assert_eq!(active_battery(&batteries), Some(1));
+
+    let batteries = vec![battery("Full", 0), battery("Unknown", 0)];
+    assert_eq!(active_battery(&batteries), None);
+
+    assert_eq!(active_battery(&[]), None);
+}
@@ -0,0 +1,77 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::units::power::watt;
+use crate::Battery;
+
+fn create_adapter(root: &std::path::Path, name: &str, online: bool, volts: u32, amperes: f32) {
+    let path = create_supply(root, name, "USB");
+    fs::write(path.join("online"), format!("{}\n", online as u8)).unwrap();
+    fs::write(path.join("voltage_max"), format!("{}\n", volts * 1_000_000)).unwrap();
+    fs::write(
+        path.join("current_max"),
+        format!("{}\n", (amperes * 1_000_000.0) as u32),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_charge_efficiency() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("status"), "Charging\n").unwrap();
+    fs::write(path.join("power_now"), "30000000\n").unwrap();
+    create_adapter(root.path(), "ucsi-source-psy-0", true, 20, 3.25);
+    create_adapter(root.path(), "ucsi-source-psy-1", true, 5, 3.0);
+    create_adapter(root.path(), "ucsi-source-psy-2", false, 20, 5.0);
+
+    let battery: Battery = SysFsDevice::try_from(path).unwrap().into();
+
+    assert_abs_diff_eq!(battery.adapter_power().unwrap().get::<watt>(), 65.0, epsilon = 0.01);
+    assert_abs_diff_eq!(
+        battery.charge_efficiency_estimate().unwrap(),
+        30.0 / 65.0,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_not_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    create_adapter(root.path(), "ucsi-source-psy-0", true, 20, 3.25);
+
+    let battery: Battery = SysFsDevice::try_from(path).unwrap().into();
+
+    assert!(battery.adapter_power().is_some());
+    assert!(battery.charge_efficiency_estimate().is_none());
+}
+
+#[test]
+fn test_unknown_adapter() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("status"), "Charging\n").unwrap();
+    fs::write(create_supply(root.path(), "AC", "Mains").join("online"), "1\n").unwrap();
+
+    let battery: Battery = SysFsDevice::try_from(path).unwrap().into();
+
+    assert!(battery.adapter_power().is_none());
+    assert!(battery.charge_efficiency_estimate().is_none());
+}
+
+#[test]
+fn test_refresh() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    create_adapter(root.path(), "ucsi-source-psy-0", true, 20, 3.25);
+    let mut device = SysFsDevice::try_from(path).unwrap();
+
+    // Adapters found during the enumeration are re-read on refresh
+    fs::write(root.path().join("ucsi-source-psy-0/online"), "0\n").unwrap();
+    device.refresh().unwrap();
+    assert!(Battery::from(device).adapter_power().is_none());
+}
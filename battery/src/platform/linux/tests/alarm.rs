@@ -0,0 +1,63 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::units::energy::watt_hour;
+
+#[test]
+fn test_energy_alarm() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "alarm" => 2500000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(
+        device.capacity_alarm().unwrap().get::<watt_hour>(),
+        2.5,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_charge_alarm() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 725000,
+        "charge_full" => 3424000,
+        "alarm" => 340000,
+        "voltage_min_design" => 11400000,
+        "voltage_now" => 10663000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(
+        device.capacity_alarm().unwrap().get::<watt_hour>(),
+        0.34 * 11.4,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_alarm_not_set() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "alarm" => 0,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert!(device.capacity_alarm().is_none());
+}
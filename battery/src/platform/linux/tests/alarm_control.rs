@@ -0,0 +1,121 @@
+use std::error::Error as StdError;
+use std::fs;
+use std::io;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::units::energy::watt_hour;
+use crate::units::Energy;
+use crate::{Battery, Error};
+
+fn kind(err: &Error) -> io::ErrorKind {
+    err.source().and_then(|e| e.downcast_ref::<io::Error>()).unwrap().kind()
+}
+
+#[test]
+fn test_set_energy_alarm() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "alarm" => 2500000,
+        "voltage_now" => 12000000
+    );
+    let mut battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    battery.set_alarm_energy(Energy::new::<watt_hour>(5.0)).unwrap();
+
+    let raw = fs::read_to_string(root.path().join("alarm")).unwrap();
+    assert_eq!(raw, "5000000\n");
+    assert_abs_diff_eq!(
+        battery.capacity_alarm().unwrap().get::<watt_hour>(),
+        5.0,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_set_charge_alarm() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 725000,
+        "charge_full" => 3424000,
+        "alarm" => 340000,
+        "voltage_min_design" => 11400000,
+        "voltage_now" => 10663000
+    );
+    let mut battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    // 0.5 Ah at the 11.4 V design voltage
+    battery.set_alarm_energy(Energy::new::<watt_hour>(5.7)).unwrap();
+
+    let raw = fs::read_to_string(root.path().join("alarm")).unwrap();
+    assert_abs_diff_eq!(raw.trim().parse::<f32>().unwrap(), 500_000.0, epsilon = 1.0);
+    assert_abs_diff_eq!(
+        battery.capacity_alarm().unwrap().get::<watt_hour>(),
+        5.7,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_alarm_is_not_supported() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let mut battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    let err = battery.set_alarm_energy(Energy::new::<watt_hour>(5.0)).unwrap_err();
+    assert_eq!(kind(&err), io::ErrorKind::NotFound);
+    assert!(!root.path().join("alarm").exists());
+}
+
+#[test]
+fn test_negative_alarm_is_rejected() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "alarm" => 2500000,
+        "voltage_now" => 12000000
+    );
+    let mut battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    let err = battery.set_alarm_energy(Energy::new::<watt_hour>(-1.0)).unwrap_err();
+    assert_eq!(kind(&err), io::ErrorKind::InvalidInput);
+    assert_eq!(fs::read_to_string(root.path().join("alarm")).unwrap(), "2500000\n");
+}
+
+#[test]
+fn test_permission_denied() {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Permissions are not checked for root
+    if unsafe { libc::geteuid() } == 0 {
+        return;
+    }
+
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "alarm" => 2500000,
+        "voltage_now" => 12000000
+    );
+    fs::set_permissions(root.path().join("alarm"), fs::Permissions::from_mode(0o444)).unwrap();
+    let mut battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    let err = battery.set_alarm_energy(Energy::new::<watt_hour>(5.0)).unwrap_err();
+    assert!(err.to_string().contains("Permission denied"));
+    assert_eq!(kind(&err), io::ErrorKind::PermissionDenied);
+}
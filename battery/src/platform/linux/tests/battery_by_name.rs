@@ -0,0 +1,33 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_battery_by_name() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    create_supply(root.path(), "BAT1", "Battery");
+    create_supply(root.path(), "AC", "Mains");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let battery = manager.battery_by_name("BAT1").unwrap().unwrap();
+    assert_eq!(battery.name(), Some("BAT1"));
+
+    assert!(manager.battery_by_name("AC").unwrap().is_none());
+    assert!(manager.battery_by_name("BAT2").unwrap().is_none());
+}
+
+#[test]
+fn test_battery_by_name_outside_of_root() {
+    let root = tempfile::tempdir().unwrap();
+    let nested = root.path().join("power_supply");
+    fs::create_dir(&nested).unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(&nested));
+
+    assert!(manager.battery_by_name("../BAT0").unwrap().is_none());
+    assert!(manager.battery_by_name("..").unwrap().is_none());
+    assert!(manager.battery_by_name("").unwrap().is_none());
+}
@@ -0,0 +1,52 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use crate::Manager;
+
+#[test]
+fn test_undeterminable() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(manager.battery_saver_active().unwrap(), None);
+}
+
+#[test]
+fn test_platform_profile() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    fs::write(root.path().join("platform_profile"), "low-power\n").unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(true));
+
+    fs::write(root.path().join("platform_profile"), "balanced\n").unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(false));
+}
+
+#[test]
+fn test_power_profiles_daemon() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    // Daemon works with the placeholder driver, if the platform does not support profiles
+    fs::write(root.path().join("platform_profile"), "balanced\n").unwrap();
+
+    fs::write(
+        root.path().join("state.ini"),
+        "[State]\nDriver=placeholder\nProfile=power-saver\n",
+    )
+    .unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(true));
+
+    fs::write(
+        root.path().join("state.ini"),
+        "[State]\nDriver=placeholder\nProfile=balanced\n",
+    )
+    .unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(false));
+
+    // Profile is not persisted yet
+    fs::write(root.path().join("state.ini"), "[State]\n").unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(false));
+    fs::write(root.path().join("platform_profile"), "low-power\n").unwrap();
+    assert_eq!(manager.battery_saver_active().unwrap(), Some(true));
+}
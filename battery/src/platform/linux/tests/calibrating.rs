@@ -0,0 +1,21 @@
+use std::fs;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::Battery;
+
+#[test]
+fn test_calibrating() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let mut battery = Battery::from(SysFsDevice::try_from(path.clone()).unwrap());
+    assert_eq!(battery.is_calibrating(), None);
+
+    fs::write(path.join("calibrate"), "1\n").unwrap();
+    battery.refresh().unwrap();
+    assert_eq!(battery.is_calibrating(), Some(true));
+
+    fs::write(path.join("calibrate"), "0\n").unwrap();
+    battery.refresh().unwrap();
+    assert_eq!(battery.is_calibrating(), Some(false));
+}
@@ -0,0 +1,79 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::units::electric_charge::ampere_hour;
+use crate::units::electric_potential::volt;
+use crate::units::energy::watt_hour;
+use crate::{Battery, CapacitySource};
+
+#[test]
+fn test_energy_based_capacity() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 60000000,
+        "voltage_min_design" => 15000000,
+        "voltage_now" => 16000000
+    );
+
+    let battery = Battery::from(SysFsDevice::try_from(root.path().to_path_buf()).unwrap());
+    let capacity = battery.capacity();
+    assert_eq!(capacity.energy_source(), CapacitySource::Native);
+    assert_eq!(capacity.charge_source(), CapacitySource::Computed);
+    assert_abs_diff_eq!(capacity.voltage().get::<volt>(), 15.0, epsilon = 0.001);
+    assert_abs_diff_eq!(capacity.energy().get::<watt_hour>(), 30.0, epsilon = 0.001);
+    assert_abs_diff_eq!(capacity.charge().unwrap().get::<ampere_hour>(), 2.0, epsilon = 0.001);
+    assert_abs_diff_eq!(
+        capacity.charge_full().unwrap().get::<ampere_hour>(),
+        4.0,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_charge_based_capacity() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2500000,
+        "charge_full" => 5000000,
+        "voltage_max_design" => 12000000,
+        "voltage_now" => 11000000
+    );
+
+    let battery = Battery::from(SysFsDevice::try_from(root.path().to_path_buf()).unwrap());
+    let capacity = battery.capacity();
+    assert_eq!(capacity.energy_source(), CapacitySource::Computed);
+    assert_eq!(capacity.charge_source(), CapacitySource::Native);
+    assert_abs_diff_eq!(capacity.voltage().get::<volt>(), 12.0, epsilon = 0.001);
+    assert_abs_diff_eq!(capacity.charge().unwrap().get::<ampere_hour>(), 2.5, epsilon = 0.001);
+    assert_abs_diff_eq!(
+        capacity.charge_full().unwrap().get::<ampere_hour>(),
+        5.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(capacity.energy().get::<watt_hour>(), 30.0, epsilon = 0.001);
+    assert_abs_diff_eq!(capacity.energy_full().get::<watt_hour>(), 60.0, epsilon = 0.001);
+}
+
+// Some drivers are reporting both of them
+#[test]
+fn test_both_capacities() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 31000000,
+        "energy_full" => 62000000,
+        "charge_now" => 2500000,
+        "charge_full" => 5000000,
+        "voltage_now" => 12000000
+    );
+
+    let battery = Battery::from(SysFsDevice::try_from(root.path().to_path_buf()).unwrap());
+    let capacity = battery.capacity();
+    assert_eq!(capacity.energy_source(), CapacitySource::Native);
+    assert_eq!(capacity.charge_source(), CapacitySource::Native);
+    assert_abs_diff_eq!(capacity.energy().get::<watt_hour>(), 31.0, epsilon = 0.001);
+    assert_abs_diff_eq!(capacity.charge().unwrap().get::<ampere_hour>(), 2.5, epsilon = 0.001);
+}
@@ -0,0 +1,42 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::units::ratio::percent;
+use crate::Manager;
+
+#[test]
+fn test_capacity_resolution() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    // Gauge reports the integer percents only
+    fs::write(path.join("capacity"), "50\n").unwrap();
+    fs::remove_file(path.join("energy_now")).unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+
+    for capacity in [50, 48, 48, 46].iter() {
+        fs::write(path.join("capacity"), format!("{}\n", capacity)).unwrap();
+        manager.refresh(&mut battery).unwrap();
+        assert!(battery.capacity_resolution().is_none());
+    }
+
+    fs::write(path.join("capacity"), "45\n").unwrap();
+    manager.refresh(&mut battery).unwrap();
+    assert_abs_diff_eq!(
+        battery.capacity_resolution().unwrap().get::<percent>(),
+        1.0,
+        epsilon = 0.001
+    );
+
+    // Larger steps are not changing it
+    fs::write(path.join("capacity"), "50\n").unwrap();
+    manager.refresh(&mut battery).unwrap();
+    assert_abs_diff_eq!(
+        battery.capacity_resolution().unwrap().get::<percent>(),
+        1.0,
+        epsilon = 0.001
+    );
+}
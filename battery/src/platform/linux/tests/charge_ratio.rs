@@ -0,0 +1,49 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::units::ratio::percent;
+
+// Charge-based driver with a noisy `voltage_now` and also reporting
+// the `energy_full` value, so the energy ratio would jitter along with the voltage
+#[test]
+fn test_noisy_voltage_stable_charge() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2500000,
+        "charge_full" => 5000000,
+        "energy_full" => 60000000,
+        "capacity" => 49,
+        "voltage_now" => 12100000
+    );
+
+    let mut device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+    assert_abs_diff_eq!(device.state_of_charge().get::<percent>(), 50.0, epsilon = 0.001);
+
+    for voltage in &["11300000", "12600000", "11900000"] {
+        fs::write(root.path().join("voltage_now"), voltage).unwrap();
+        device.refresh().unwrap();
+
+        assert_abs_diff_eq!(device.state_of_charge().get::<percent>(), 50.0, epsilon = 0.001);
+    }
+}
+
+// `charge_full_design` alone is not enough for the charge ratio
+#[test]
+fn test_charge_full_design_is_ignored() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2500000,
+        "charge_full_design" => 5000000,
+        "capacity" => 60,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(device.state_of_charge().get::<percent>(), 60.0, epsilon = 0.001);
+}
@@ -0,0 +1,67 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::units::energy::watt_hour;
+use crate::units::ratio::percent;
+use crate::units::time::hour;
+use crate::Battery;
+
+#[test]
+fn test_time_to_threshold() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "charge_control_end_threshold" => 80,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.charge_stop_threshold().unwrap().get::<percent>(), 80.0);
+    // 15 Wh till the 40 Wh threshold at 10 W
+    assert_abs_diff_eq!(
+        battery.energy_until_full().unwrap().get::<watt_hour>(),
+        15.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(battery.time_to_full().unwrap().get::<hour>(), 1.5, epsilon = 0.001);
+}
+
+#[test]
+fn test_threshold_is_reached() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "energy_now" => 41000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "charge_control_end_threshold" => 80,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.energy_until_full().unwrap().get::<watt_hour>(), 0.0);
+    assert!(battery.time_to_full().is_none());
+}
+
+#[test]
+fn test_no_threshold() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "charge_control_end_threshold" => 100,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.charge_stop_threshold().unwrap().get::<percent>(), 100.0);
+    assert_abs_diff_eq!(battery.time_to_full().unwrap().get::<hour>(), 2.5, epsilon = 0.001);
+}
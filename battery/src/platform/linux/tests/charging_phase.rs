@@ -0,0 +1,67 @@
+use std::fs;
+use std::path::Path;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery, set_attribute, set_energy};
+use crate::{Battery, ChargePhase, Manager};
+
+fn set(path: &Path, status: &str, energy_wh: u32, voltage_mv: u32) {
+    set_energy(path, status, energy_wh * 1_000);
+    set_attribute(path, "voltage_now", voltage_mv * 1_000);
+    set_attribute(path, "power_now", 20000000);
+}
+
+fn battery(root: &Path) -> Battery {
+    first_battery(&Manager::from(SysFsManager::with_root(root)))
+}
+
+#[test]
+fn test_charging_phase_by_voltage() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("voltage_max_design"), "12600000\n").unwrap();
+
+    set(&path, "Charging", 30, 12000);
+    assert_eq!(
+        battery(root.path()).charging_phase(),
+        Some(ChargePhase::ConstantCurrent)
+    );
+
+    // Voltage is held at the maximum already, even if the state of charge is below the taper threshold
+    set(&path, "Charging", 35, 12580);
+    assert_eq!(
+        battery(root.path()).charging_phase(),
+        Some(ChargePhase::ConstantVoltage)
+    );
+}
+
+#[test]
+fn test_charging_phase_by_state_of_charge() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    set(&path, "Charging", 30, 12000);
+    assert_eq!(
+        battery(root.path()).charging_phase(),
+        Some(ChargePhase::ConstantCurrent)
+    );
+
+    set(&path, "Charging", 45, 12000);
+    assert_eq!(
+        battery(root.path()).charging_phase(),
+        Some(ChargePhase::ConstantVoltage)
+    );
+}
+
+#[test]
+fn test_charging_phase_not_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    set(&path, "Discharging", 30, 12000);
+    assert_eq!(battery(root.path()).charging_phase(), None);
+
+    set(&path, "Charging", 30, 12000);
+    fs::write(path.join("power_now"), "0\n").unwrap();
+    assert_eq!(battery(root.path()).charging_phase(), None);
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::super::{SysFsDevice, SysFsManager};
+use super::{create_supply, first_battery};
+use crate::types::clock::{FakeClock, SharedClock};
+use crate::units::ratio::percent;
+use crate::units::time::minute;
+use crate::{Battery, Config, LearnedEstimator, Manager, State};
+
+#[test]
+fn test_fake_clock() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+    let created = battery.last_updated().unwrap();
+
+    clock.advance(Duration::from_secs(90));
+    assert_eq!(battery.time_in_state(), Some(Duration::from_secs(90)));
+
+    fs::write(path.join("status"), "Charging\n").unwrap();
+    manager.refresh(&mut battery).unwrap();
+    assert_eq!(battery.last_updated(), Some(created + Duration::from_secs(90)));
+    assert_eq!(battery.time_in_state(), Some(Duration::from_secs(0)));
+
+    clock.advance(Duration::from_secs(30));
+    assert_eq!(battery.time_in_state(), Some(Duration::from_secs(30)));
+}
+
+#[test]
+fn test_fake_clock_staleness() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let mut config = Config::new().max_data_age(Duration::from_secs(60));
+    config.clock = SharedClock::new(clock.clone());
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap()).with_config(&config);
+
+    clock.advance(Duration::from_secs(60));
+    assert!(!battery.is_stale());
+    clock.advance(Duration::from_secs(1));
+    assert!(battery.is_stale());
+}
+
+#[test]
+fn test_fake_clock_estimator() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+    assert_eq!(battery.state(), State::Discharging);
+
+    // Percentage-only battery
+    fs::remove_file(path.join("energy_now")).unwrap();
+    let mut estimator = LearnedEstimator::new();
+    for idx in 0..=10 {
+        fs::write(path.join("capacity"), format!("{}\n", 60 - idx)).unwrap();
+        manager.refresh(&mut battery).unwrap();
+        assert_eq!(battery.state_of_charge().get::<percent>().round() as i32, 60 - idx);
+        estimator.push(&battery);
+        clock.advance(Duration::from_secs(60));
+    }
+
+    // 50% left at 1% per minute
+    let estimate = estimator.time_to_empty().unwrap();
+    assert!((estimate.time().get::<minute>() - 50.0).abs() < 0.1);
+}
@@ -0,0 +1,64 @@
+use std::fs;
+
+use super::super::SysFsDevice;
+use crate::{Battery, Condition};
+
+fn condition(energy_full: u32, cycle_count: Option<u32>) -> Condition {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => energy_full,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    if let Some(count) = cycle_count {
+        fs::write(root.path().join("cycle_count"), count.to_string()).unwrap();
+    }
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    battery.condition()
+}
+
+#[test]
+fn test_new() {
+    assert_eq!(condition(49500000, Some(12)), Condition::New);
+}
+
+#[test]
+fn test_used() {
+    // Healthy, but already cycled
+    assert_eq!(condition(49500000, Some(Condition::NEW_MAX_CYCLES)), Condition::Used);
+    assert_eq!(condition(45000000, Some(120)), Condition::Used);
+    // Unknown cycle count is never considered as a new one
+    assert_eq!(condition(49500000, None), Condition::Used);
+}
+
+#[test]
+fn test_aged() {
+    assert_eq!(condition(35000000, Some(200)), Condition::Aged);
+    assert_eq!(condition(49500000, Some(Condition::AGED_MIN_CYCLES)), Condition::Aged);
+    assert_eq!(condition(35000000, None), Condition::Aged);
+}
+
+#[test]
+fn test_worn() {
+    assert_eq!(condition(25000000, Some(200)), Condition::Worn);
+    assert_eq!(condition(45000000, Some(Condition::WORN_MIN_CYCLES)), Condition::Worn);
+}
+
+// Same as in the #40 issue
+#[test]
+fn test_missing_design_capacity() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "cycle_count" => 10,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.condition(), Condition::Unknown);
+}
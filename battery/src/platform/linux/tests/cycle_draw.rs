@@ -0,0 +1,54 @@
+use std::sync::Arc;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery, set_energy, step};
+use crate::types::clock::FakeClock;
+use crate::units::power::watt;
+use crate::Manager;
+
+const HOUR: u64 = 60 * 60;
+
+#[test]
+fn test_last_cycle_average_draw() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_energy(&path, "Full", 50_000);
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+    assert!(battery.last_cycle_average_draw().is_none());
+
+    set_energy(&path, "Discharging", 48_000);
+    step(&manager, &clock, &mut battery, HOUR);
+    set_energy(&path, "Discharging", 20_000);
+    step(&manager, &clock, &mut battery, 2 * HOUR);
+    assert!(battery.last_cycle_average_draw().is_none());
+
+    // 46 Wh drawn in 4 hours
+    set_energy(&path, "Discharging", 2_000);
+    step(&manager, &clock, &mut battery, 2 * HOUR);
+    assert_eq!(battery.last_cycle_average_draw().unwrap().get::<watt>(), 11.5);
+
+    // Interrupted discharge keeps the previous value
+    set_energy(&path, "Discharging", 49_000);
+    step(&manager, &clock, &mut battery, HOUR);
+    set_energy(&path, "Charging", 30_000);
+    step(&manager, &clock, &mut battery, HOUR);
+    set_energy(&path, "Discharging", 1_000);
+    step(&manager, &clock, &mut battery, HOUR);
+    assert_eq!(battery.last_cycle_average_draw().unwrap().get::<watt>(), 11.5);
+}
+
+#[test]
+fn test_last_cycle_average_draw_partial_discharge() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    // Discharge started at 50%
+    set_energy(&path, "Discharging", 1_000);
+    step(&manager, &clock, &mut battery, 3 * HOUR);
+    assert!(battery.last_cycle_average_draw().is_none());
+}
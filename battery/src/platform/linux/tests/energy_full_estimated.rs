@@ -0,0 +1,55 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::units::energy::watt_hour;
+use crate::units::ratio::percent;
+
+// Driver reports the `capacity` percentage and the design values only
+#[test]
+fn test_energy_full_from_design() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "capacity" => 40,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+    assert!(device.energy_full_is_estimated());
+    assert_abs_diff_eq!(device.energy_full().get::<watt_hour>(), 50.0, epsilon = 0.001);
+    assert_abs_diff_eq!(device.energy().get::<watt_hour>(), 20.0, epsilon = 0.001);
+    assert_abs_diff_eq!(device.state_of_charge().get::<percent>(), 40.0, epsilon = 0.001);
+    assert_abs_diff_eq!(device.state_of_health().get::<percent>(), 100.0, epsilon = 0.001);
+}
+
+#[test]
+fn test_energy_full_reported() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 20000000,
+        "energy_full" => 45000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+    assert!(!device.energy_full_is_estimated());
+}
+
+#[test]
+fn test_charge_full_reported() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "charge_full" => 4000000,
+        "charge_full_design" => 5000000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+    assert!(!device.energy_full_is_estimated());
+}
@@ -0,0 +1,85 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::units::energy::watt_hour;
+use crate::units::power::watt;
+use crate::units::time::hour;
+
+// Energy-based driver, reporting the rate in µW via `power_now`
+#[test]
+fn test_power_reporting_driver() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 15000000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(device.energy_rate().get::<watt>(), 15.0, epsilon = 0.001);
+    assert_abs_diff_eq!(device.time_to_empty().unwrap().get::<hour>(), 2.0, epsilon = 0.001);
+}
+
+// Charge-based driver, reporting the rate in µA via `current_now`
+#[test]
+fn test_current_reporting_driver() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "charge_full" => 4000000,
+        "charge_full_design" => 4000000,
+        "current_now" => 1250000,
+        "voltage_min_design" => 11100000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(device.energy_rate().get::<watt>(), 1.25 * 11.1, epsilon = 0.001);
+    assert_abs_diff_eq!(device.energy().get::<watt_hour>(), 2.0 * 11.1, epsilon = 0.001);
+    // 2 Ah left, drained at 1.25 A
+    assert_abs_diff_eq!(device.time_to_empty().unwrap().get::<hour>(), 1.6, epsilon = 0.001);
+}
+
+// Charge-based driver without `charge_full*` attributes
+#[test]
+fn test_current_reporting_driver_without_full_charge() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "capacity" => 50,
+        "current_now" => 1250000,
+        "voltage_min_design" => 11100000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(device.energy_rate().get::<watt>(), 1.25 * 11.1, epsilon = 0.001);
+    assert_abs_diff_eq!(device.time_to_empty().unwrap().get::<hour>(), 1.6, epsilon = 0.001);
+}
+
+// Some drivers are reporting discharging current as a negative value
+#[test]
+fn test_negative_current() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "charge_full" => 4000000,
+        "current_now" => -1250000,
+        "voltage_min_design" => 11100000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_abs_diff_eq!(device.energy_rate().get::<watt>(), 1.25 * 11.1, epsilon = 0.001);
+}
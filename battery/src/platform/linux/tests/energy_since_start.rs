@@ -0,0 +1,75 @@
+use super::super::SysFsManager;
+use super::{create_supply, set_energy};
+use crate::units::energy::watt_hour;
+use crate::{Battery, Manager};
+
+fn refresh(manager: &Manager, batteries: &mut [Battery]) {
+    for battery in batteries.iter_mut() {
+        manager.refresh(battery).unwrap();
+    }
+}
+
+#[test]
+fn test_energy_since_start() {
+    let root = tempfile::tempdir().unwrap();
+    let bat0 = create_supply(root.path(), "BAT0", "Battery");
+    let bat1 = create_supply(root.path(), "BAT1", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut batteries = manager
+        .batteries()
+        .unwrap()
+        .map(|battery| battery.unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 0.0);
+
+    set_energy(&bat0, "Discharging", 20_000);
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 5.0);
+
+    // Charging is not subtracted, only the following discharge is counted
+    set_energy(&bat0, "Charging", 30_000);
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 5.0);
+    set_energy(&bat0, "Discharging", 28_000);
+    set_energy(&bat1, "Discharging", 24_000);
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 8.0);
+}
+
+#[test]
+fn test_energy_since_start_new_manager() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut batteries = manager
+        .batteries()
+        .unwrap()
+        .map(|battery| battery.unwrap())
+        .collect::<Vec<_>>();
+    set_energy(&path, "Discharging", 15_000);
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 10.0);
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 0.0);
+}
+
+#[test]
+fn test_energy_since_start_before_load() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    // Energy drained before the battery is loaded is not counted
+    set_energy(&path, "Discharging", 15_000);
+    let mut batteries = manager
+        .batteries()
+        .unwrap()
+        .map(|battery| battery.unwrap())
+        .collect::<Vec<_>>();
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 0.0);
+
+    set_energy(&path, "Discharging", 12_000);
+    refresh(&manager, &mut batteries);
+    assert_eq!(manager.energy_since_start().unwrap().get::<watt_hour>(), 3.0);
+}
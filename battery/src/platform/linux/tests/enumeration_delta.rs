@@ -0,0 +1,27 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_diff_enumeration() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let second = create_supply(root.path(), "BAT1", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let delta = manager.diff_enumeration(&[]).unwrap();
+    assert_eq!(delta.added(), &["BAT0".to_string(), "BAT1".to_string()]);
+    assert!(delta.removed().is_empty());
+
+    let delta = manager.diff_enumeration(delta.current()).unwrap();
+    assert!(delta.is_empty());
+
+    fs::remove_dir_all(second).unwrap();
+    create_supply(root.path(), "BAT2", "Battery");
+    let delta = manager.diff_enumeration(delta.current()).unwrap();
+    assert_eq!(delta.added(), &["BAT2".to_string()]);
+    assert_eq!(delta.removed(), &["BAT1".to_string()]);
+    assert_eq!(delta.into_current(), vec!["BAT0".to_string(), "BAT2".to_string()]);
+}
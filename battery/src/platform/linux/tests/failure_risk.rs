@@ -0,0 +1,82 @@
+use std::fs;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::super::{SysFsDevice, SysFsManager};
+use super::{create_supply, first_battery};
+use crate::types::clock::FakeClock;
+use crate::{Battery, Manager, RiskLevel};
+
+fn risk(energy_full: u32, energy_full_design: u32, temp: Option<i32>) -> RiskLevel {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => energy_full,
+        "energy_full_design" => energy_full_design,
+        "voltage_now" => 12000000
+    );
+    if let Some(temp) = temp {
+        // Tenths of a degree Celsius
+        fs::write(root.path().join("temp"), temp.to_string()).unwrap();
+    }
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    battery.failure_risk()
+}
+
+#[test]
+fn test_health() {
+    assert_eq!(risk(48000000, 50000000, Some(300)), RiskLevel::Low);
+    assert_eq!(risk(30000000, 50000000, None), RiskLevel::Elevated);
+    assert_eq!(risk(20000000, 50000000, None), RiskLevel::High);
+    assert_eq!(risk(48000000, 0, None), RiskLevel::Unknown);
+    // Zero full energy is a read failure, not a worn out battery
+    assert_eq!(risk(0, 50000000, None), RiskLevel::Unknown);
+}
+
+#[test]
+fn test_health_estimated() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert!(battery.energy_full_is_estimated());
+    assert_eq!(battery.failure_risk(), RiskLevel::Unknown);
+}
+
+#[test]
+fn test_full_drift() {
+    assert_eq!(risk(52000000, 50000000, None), RiskLevel::Low);
+    assert_eq!(risk(60000000, 50000000, None), RiskLevel::Elevated);
+}
+
+#[test]
+fn test_temperature() {
+    assert_eq!(risk(48000000, 50000000, Some(500)), RiskLevel::Elevated);
+    assert_eq!(risk(48000000, 50000000, Some(650)), RiskLevel::High);
+    // The worst signal wins, even if the capacity is unknown
+    assert_eq!(risk(48000000, 0, Some(500)), RiskLevel::Elevated);
+}
+
+#[test]
+fn test_self_discharge() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("status"), "Full\n").unwrap();
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+    assert_eq!(battery.failure_risk(), RiskLevel::Low);
+
+    // 500 mWh lost in 2 hours is 12% of the full energy per day
+    fs::write(path.join("energy_now"), "24500000\n").unwrap();
+    clock.advance(Duration::from_secs(2 * 60 * 60));
+    manager.refresh(&mut battery).unwrap();
+    assert_eq!(battery.failure_risk(), RiskLevel::High);
+}
@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::Path;
+
+use super::super::SysFsDevice;
+use super::{battery_with, create_supply};
+use crate::Battery;
+
+fn battery(charge_type: Option<&str>) -> Battery {
+    match charge_type {
+        Some(value) => battery_with(&[("status", "Charging"), ("charge_type", value)]),
+        None => battery_with(&[("status", "Charging")]),
+    }
+}
+
+#[test]
+fn test_fast_charge() {
+    assert_eq!(battery(Some("Fast")).fast_charge_active(), Some(true));
+}
+
+#[test]
+fn test_standard_charge() {
+    assert_eq!(battery(Some("Standard")).fast_charge_active(), Some(false));
+    assert_eq!(battery(Some("Long Life")).fast_charge_active(), Some(false));
+}
+
+#[test]
+fn test_unknown_charge_type() {
+    assert_eq!(battery(Some("Unknown")).fast_charge_active(), None);
+    assert_eq!(battery(Some("N/A")).fast_charge_active(), None);
+    assert_eq!(battery(None).fast_charge_active(), None);
+}
+
+/// Creates the `BAT0` battery in the sysfs-like tree under the `root`,
+/// along with the Lenovo IdeaPad ACPI device with the `attributes` given.
+fn ideapad(root: &Path, attributes: &[(&str, &str)]) -> Battery {
+    let class = root.join("class/power_supply");
+    fs::create_dir_all(&class).unwrap();
+    let path = create_supply(&class, "BAT0", "Battery");
+
+    let drivers = root.join("bus/platform/drivers/ideapad_acpi");
+    fs::create_dir_all(drivers.join("VPC2004:00")).unwrap();
+    fs::write(drivers.join("uevent"), "").unwrap();
+    for (name, value) in attributes {
+        fs::write(drivers.join("VPC2004:00").join(name), format!("{}\n", value)).unwrap();
+    }
+
+    SysFsDevice::try_from(path).unwrap().into()
+}
+
+#[test]
+fn test_ideapad_rapid_charge() {
+    let root = tempfile::tempdir().unwrap();
+    let attributes = [("rapid_charge", "1"), ("conservation_mode", "0")];
+    assert_eq!(ideapad(root.path(), &attributes).fast_charge_active(), Some(true));
+
+    let root = tempfile::tempdir().unwrap();
+    let attributes = [("rapid_charge", "0"), ("conservation_mode", "0")];
+    assert_eq!(ideapad(root.path(), &attributes).fast_charge_active(), Some(false));
+}
+
+#[test]
+fn test_ideapad_conservation_mode() {
+    let root = tempfile::tempdir().unwrap();
+    assert_eq!(
+        ideapad(root.path(), &[("conservation_mode", "1")]).fast_charge_active(),
+        Some(false)
+    );
+
+    // Conservation mode alone does not tell if the rapid charge is on
+    let root = tempfile::tempdir().unwrap();
+    assert_eq!(
+        ideapad(root.path(), &[("conservation_mode", "0")]).fast_charge_active(),
+        None
+    );
+}
+
+#[test]
+fn test_charge_type_precedence() {
+    let root = tempfile::tempdir().unwrap();
+    ideapad(root.path(), &[("rapid_charge", "0")]);
+    fs::write(root.path().join("class/power_supply/BAT0/charge_type"), "Fast\n").unwrap();
+
+    let battery = SysFsDevice::try_from(root.path().join("class/power_supply/BAT0")).unwrap();
+    assert_eq!(Battery::from(battery).fast_charge_active(), Some(true));
+}
@@ -0,0 +1,179 @@
+use std::cell::Cell;
+use std::fs::{self, File};
+use std::io;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use approx::assert_abs_diff_eq;
+
+use super::super::sysfs::{AttributeFile, DataBuilder, Dir, FileCache};
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+use crate::units::energy::watt_hour;
+
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)] // `const` initializers require Rust 1.59
+    static GENERATION: Cell<usize> = Cell::new(0);
+}
+
+/// Attribute file which descriptor goes stale once the device is re-plugged,
+/// failing the reads with `ENODEV`, as the sysfs ones do.
+struct DeviceFile {
+    file: File,
+    generation: usize,
+}
+
+impl AttributeFile for DeviceFile {
+    fn open(path: &Path) -> io::Result<DeviceFile> {
+        Ok(DeviceFile {
+            file: File::open(path)?,
+            generation: GENERATION.with(Cell::get),
+        })
+    }
+
+    fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+        if self.generation != GENERATION.with(Cell::get) {
+            return Err(io::Error::from_raw_os_error(19));
+        }
+        FileExt::read_at(&self.file, buf, offset)
+    }
+}
+
+fn replug() {
+    GENERATION.with(|generation| generation.set(generation.get() + 1));
+}
+
+#[test]
+fn test_partial_reads() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("short"), "abc\n").unwrap();
+    fs::write(root.path().join("exact"), "1234567\n").unwrap();
+    fs::write(root.path().join("long"), "Some Very Long Model Name\n").unwrap();
+    fs::write(root.path().join("empty"), "").unwrap();
+
+    let cache = FileCache::with_chunk_size(root.path().to_path_buf(), 8);
+
+    assert_eq!(cache.read_to_string("short").unwrap(), "abc\n");
+    assert_eq!(cache.read_to_string("exact").unwrap(), "1234567\n");
+    assert_eq!(cache.read_to_string("long").unwrap(), "Some Very Long Model Name\n");
+    assert_eq!(cache.read_to_string("empty").unwrap(), "");
+}
+
+#[test]
+fn test_rereads_changed_values() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("energy_now"), "25000000\n").unwrap();
+    let cache = FileCache::with_chunk_size(root.path().to_path_buf(), 8);
+    assert_eq!(cache.read_to_string("energy_now").unwrap(), "25000000\n");
+
+    // Shorter value is not mixed with the previous one
+    fs::write(root.path().join("energy_now"), "900\n").unwrap();
+    assert_eq!(cache.read_to_string("energy_now").unwrap(), "900\n");
+}
+
+#[test]
+fn test_missing_attribute() {
+    let root = tempfile::tempdir().unwrap();
+    let cache: FileCache = FileCache::new(root.path().to_path_buf());
+
+    let err = cache.read_to_string("power_now").unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_missing_attribute_appears() {
+    let root = tempfile::tempdir().unwrap();
+    let cache: FileCache = FileCache::new(root.path().to_path_buf());
+    assert!(cache.read_to_string("temp").is_err());
+
+    // Missing attribute is not looked up again for a while
+    fs::write(root.path().join("temp"), "305\n").unwrap();
+    assert_eq!(
+        cache.read_to_string("temp").unwrap_err().kind(),
+        io::ErrorKind::NotFound
+    );
+
+    let cache: FileCache = FileCache::new(root.path().join("late")).with_missing_ttl(Duration::from_millis(20));
+    fs::create_dir(root.path().join("late")).unwrap();
+    assert!(cache.read_to_string("temp").is_err());
+    fs::write(root.path().join("late/temp"), "305\n").unwrap();
+    thread::sleep(Duration::from_millis(30));
+    assert_eq!(cache.read_to_string("temp").unwrap(), "305\n");
+}
+
+#[test]
+fn test_stale_descriptor_reopen() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(root.path().join("energy_now"), "25000000\n").unwrap();
+    let cache: FileCache<DeviceFile> = FileCache::new(root.path().to_path_buf());
+    assert_eq!(cache.read_to_string("energy_now").unwrap(), "25000000\n");
+
+    // Device is back with the new attribute files
+    fs::remove_file(root.path().join("energy_now")).unwrap();
+    fs::write(root.path().join("energy_now"), "20000000\n").unwrap();
+    replug();
+    assert_eq!(cache.read_to_string("energy_now").unwrap(), "20000000\n");
+    assert_eq!(cache.read_to_string("energy_now").unwrap(), "20000000\n");
+
+    // Device is gone for good
+    fs::remove_file(root.path().join("energy_now")).unwrap();
+    replug();
+    let err = cache.read_to_string("energy_now").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+}
+
+#[test]
+fn test_device_refresh() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let mut device = SysFsDevice::try_from(path.clone()).unwrap();
+
+    fs::write(path.join("energy_now"), "20000000\n").unwrap();
+    device.refresh().unwrap();
+    assert_abs_diff_eq!(device.energy().get::<watt_hour>(), 20.0, epsilon = 0.001);
+
+    fs::write(path.join("energy_now"), "19000000\n").unwrap();
+    device.refresh().unwrap();
+    assert_abs_diff_eq!(device.energy().get::<watt_hour>(), 19.0, epsilon = 0.001);
+
+    // Opened files are not keeping the disappeared device alive
+    fs::remove_dir_all(&path).unwrap();
+    assert!(device.refresh().is_err());
+}
+
+/// Compares the refresh from the opened files against the portable one, run with
+///
+/// ```text
+/// cargo test --release -p battery --features fast-refresh bench_file_cache -- --ignored --nocapture
+/// ```
+///
+/// Syscall counts can be compared by running the same command under the `strace -c -f`:
+/// portable refresh makes `openat(2)`, `read(2)` and `close(2)` calls for each attribute,
+/// while the cached one makes a single `pread64(2)` call.
+#[test]
+#[ignore]
+fn bench_file_cache() {
+    const ROUNDS: u32 = 10_000;
+
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let cache: FileCache = FileCache::new(path.clone());
+
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        let dir = Dir::open(&path).unwrap();
+        DataBuilder::with_dir(&path, Some(&dir)).collect().unwrap();
+    }
+    let portable = started.elapsed() / ROUNDS;
+
+    let started = Instant::now();
+    for _ in 0..ROUNDS {
+        DataBuilder::with_cache(&path, None, &cache).collect().unwrap();
+    }
+    let cached = started.elapsed() / ROUNDS;
+
+    println!("portable refresh: {:?}, cached refresh: {:?}", portable, cached);
+}
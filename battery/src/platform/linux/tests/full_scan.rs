@@ -0,0 +1,119 @@
+use std::fs;
+use std::os::unix::fs::symlink;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::Manager;
+
+#[test]
+fn test_full_scan() {
+    let root = tempfile::tempdir().unwrap();
+    for name in ["BAT10", "BAT2", "BAT1", "hid-mouse"].iter() {
+        create_supply(root.path(), name, "Battery");
+    }
+    fs::write(root.path().join("hid-mouse").join("scope"), "Device\n").unwrap();
+    fs::write(create_supply(root.path(), "BAT3", "Battery").join("present"), "0\n").unwrap();
+    fs::write(create_supply(root.path(), "AC", "Mains").join("online"), "0\n").unwrap();
+    fs::write(create_supply(root.path(), "USB0", "USB").join("online"), "1\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let report = manager.full_scan().unwrap();
+
+    let names = report
+        .batteries()
+        .iter()
+        .map(|battery| battery.name())
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![Some("BAT1"), Some("BAT2"), Some("BAT10")]);
+    assert_eq!(report.is_external_power_connected(), Some(true));
+
+    // Same batteries as the regular enumeration yields
+    let batteries = manager.batteries().unwrap().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(batteries.len(), report.batteries().len());
+    for (expected, actual) in batteries.iter().zip(report.batteries()) {
+        assert_eq!(expected.name(), actual.name());
+        assert_eq!(expected.state(), actual.state());
+        assert_eq!(expected.energy(), actual.energy());
+        assert_eq!(expected.energy_full(), actual.energy_full());
+        assert_eq!(expected.voltage(), actual.voltage());
+    }
+}
+
+#[test]
+fn test_external_power() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(manager.full_scan().unwrap().is_external_power_connected(), None);
+
+    let ac = create_supply(root.path(), "AC", "Mains");
+    fs::write(ac.join("online"), "0\n").unwrap();
+    assert_eq!(manager.full_scan().unwrap().is_external_power_connected(), Some(false));
+
+    fs::write(ac.join("online"), "1\n").unwrap();
+    assert_eq!(manager.full_scan().unwrap().is_external_power_connected(), Some(true));
+}
+
+// `/sys/class/power_supply/` entries are symlinks to the actual device directories
+#[test]
+fn test_symlinked_entries() {
+    let devices = tempfile::tempdir().unwrap();
+    create_supply(devices.path(), "BAT0", "Battery");
+    let root = tempfile::tempdir().unwrap();
+    symlink(devices.path().join("BAT0"), root.path().join("BAT0")).unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let report = manager.full_scan().unwrap();
+
+    assert_eq!(report.batteries().len(), 1);
+    assert_eq!(report.batteries()[0].serial_number(), Some("BAT0"));
+}
+
+#[test]
+fn test_primary_battery() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    // Nothing is discharging at a non-zero rate, first one is picked
+    let report = manager.full_scan().unwrap();
+    assert_eq!(
+        report.primary_battery().and_then(|battery| battery.name()),
+        Some("BAT0")
+    );
+
+    fs::write(
+        create_supply(root.path(), "BAT1", "Battery").join("power_now"),
+        "5000000\n",
+    )
+    .unwrap();
+    let report = manager.full_scan().unwrap();
+    assert_eq!(
+        report.primary_battery().and_then(|battery| battery.name()),
+        Some("BAT1")
+    );
+}
+
+#[test]
+fn test_combined_state_of_charge() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let report = manager.full_scan().unwrap();
+    assert!(report.primary_battery().is_none());
+    assert_eq!(report.state_of_charge(), Ratio::new::<percent>(0.0));
+
+    // 25 of 50 Wh plus 60 of 100 Wh
+    create_supply(root.path(), "BAT0", "Battery");
+    let bat1 = create_supply(root.path(), "BAT1", "Battery");
+    fs::write(bat1.join("energy_now"), "60000000\n").unwrap();
+    fs::write(bat1.join("energy_full"), "100000000\n").unwrap();
+
+    let report = manager.full_scan().unwrap();
+    assert_abs_diff_eq!(report.state_of_charge().get::<percent>(), 56.666, epsilon = 0.01);
+}
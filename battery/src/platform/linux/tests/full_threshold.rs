@@ -0,0 +1,100 @@
+use std::path::Path;
+
+use super::{create_supply, load_battery, set_attribute};
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, Config, State};
+
+fn battery(path: &Path, threshold: Option<f32>) -> Battery {
+    let config = match threshold {
+        Some(value) => Config::new().full_threshold(Ratio::new::<percent>(value)),
+        None => Config::default(),
+    };
+    load_battery(path, &config)
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_attribute(&path, "status", "Charging");
+    set_attribute(&path, "capacity", "99");
+    set_attribute(&path, "power_now", "10000000");
+
+    let battery = battery(&path, None);
+    assert_eq!(battery.state(), State::Charging);
+    assert!(battery.time_to_full().is_some());
+    assert!(battery.energy_until_full().is_some());
+}
+
+#[test]
+fn test_threshold_boundary() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_attribute(&path, "status", "Charging");
+    set_attribute(&path, "power_now", "10000000");
+
+    set_attribute(&path, "capacity", "96");
+    let below = battery(&path, Some(97.0));
+    assert_eq!(below.state(), State::Charging);
+    assert!(below.time_to_full().is_some());
+    assert!(below.energy_until_full().is_some());
+
+    set_attribute(&path, "capacity", "97");
+    let exact = battery(&path, Some(97.0));
+    assert_eq!(exact.state(), State::Full);
+    assert_eq!(exact.time_to_full(), None);
+    assert_eq!(exact.time_to_empty(), None);
+    assert_eq!(exact.energy_until_full(), None);
+}
+
+#[test]
+fn test_not_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_attribute(&path, "status", "Not charging");
+    set_attribute(&path, "capacity", "80");
+    let ac = create_supply(root.path(), "AC", "Mains");
+    set_attribute(&ac, "online", "1");
+
+    assert_eq!(battery(&path, Some(80.0)).state(), State::Full);
+    assert_eq!(battery(&path, Some(81.0)).state(), State::Unknown);
+}
+
+// Battery in the unknown state might be discharging, so the external power is checked
+#[test]
+fn test_unknown_state_without_external_power() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_attribute(&path, "status", "Unknown");
+    set_attribute(&path, "capacity", "98");
+
+    // External power status is not known
+    assert_eq!(battery(&path, Some(97.0)).state(), State::Unknown);
+
+    let ac = create_supply(root.path(), "AC", "Mains");
+    set_attribute(&ac, "online", "0");
+    assert_eq!(battery(&path, Some(97.0)).state(), State::Unknown);
+
+    set_attribute(&ac, "online", "1");
+    assert_eq!(battery(&path, Some(97.0)).state(), State::Full);
+}
+
+#[test]
+fn test_unplugged() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_attribute(&path, "status", "Charging");
+    set_attribute(&path, "capacity", "98");
+    set_attribute(&path, "power_now", "10000000");
+
+    let mut battery = battery(&path, Some(97.0));
+    assert_eq!(battery.state(), State::Full);
+
+    set_attribute(&path, "status", "Discharging");
+    battery.refresh().unwrap();
+    assert_eq!(battery.state(), State::Discharging);
+    assert_eq!(battery.time_to_full(), None);
+    assert!(battery.time_to_empty().is_some());
+    assert!(battery.energy_until_empty().is_some());
+}
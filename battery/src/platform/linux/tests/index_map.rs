@@ -0,0 +1,68 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::units::energy::watt_hour;
+use crate::Manager;
+
+#[test]
+fn test_index_map_detects_swap() {
+    let root = tempfile::tempdir().unwrap();
+    let first = create_supply(root.path(), "BAT0", "Battery");
+    let second = create_supply(root.path(), "BAT1", "Battery");
+    for (path, serial) in [(&first, "1111"), (&second, "2222")].iter() {
+        fs::write(path.join("manufacturer"), "SMP\n").unwrap();
+        fs::write(path.join("model_name"), "5B10W13930\n").unwrap();
+        fs::write(path.join("serial_number"), format!("{}\n", serial)).unwrap();
+    }
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let before = manager.index_map().unwrap();
+    assert_eq!(
+        before,
+        vec![
+            (0, "SMP/5B10W13930/1111".to_string()),
+            (1, "SMP/5B10W13930/2222".to_string())
+        ]
+    );
+
+    // Packs were re-plugged and kernel assigned them names in the reverse order
+    fs::write(first.join("serial_number"), "2222\n").unwrap();
+    fs::write(second.join("serial_number"), "1111\n").unwrap();
+
+    let after = manager.index_map().unwrap();
+    assert_eq!(after[0].1, before[1].1);
+    assert_eq!(after[1].1, before[0].1);
+}
+
+#[test]
+fn test_stable_id_fallback() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("serial_number"), " \n").unwrap();
+    fs::write(path.join("model_name"), "5B10W13930\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(manager.index_map().unwrap(), vec![(0, "BAT0".to_string())]);
+}
+
+#[test]
+fn test_get_by_id() {
+    let root = tempfile::tempdir().unwrap();
+    let first = create_supply(root.path(), "BAT0", "Battery");
+    let second = create_supply(root.path(), "BAT1", "Battery");
+    fs::write(first.join("manufacturer"), "SMP\n").unwrap();
+    fs::write(first.join("energy_now"), "10000000\n").unwrap();
+    fs::write(second.join("serial_number"), "\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let battery = manager.get_by_id("SMP/BAT0").unwrap().unwrap();
+    assert_eq!(battery.energy().get::<watt_hour>(), 10.0);
+    // Name is used only if there is no serial number
+    assert!(manager.get_by_id("BAT0").unwrap().is_none());
+    let battery = manager.get_by_id("BAT1").unwrap().unwrap();
+    assert_eq!(battery.name(), Some("BAT1"));
+    assert!(manager.get_by_id("BAT2").unwrap().is_none());
+}
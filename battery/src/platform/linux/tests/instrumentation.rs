@@ -0,0 +1,104 @@
+use std::fmt;
+use std::fs;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::Manager;
+
+/// Subscriber which remembers names of the created spans
+/// and the messages of the emitted events with their levels.
+#[derive(Default)]
+struct Collector {
+    next_id: AtomicU64,
+    spans: Arc<Mutex<Vec<String>>>,
+    events: Arc<Mutex<Vec<(Level, String)>>>,
+}
+
+struct MessageVisitor<'a>(&'a mut String);
+
+impl<'a> Visit for MessageVisitor<'a> {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.push_str(&format!("{:?}", value));
+        }
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        self.spans.lock().unwrap().push(span.metadata().name().to_string());
+
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = String::new();
+        event.record(&mut MessageVisitor(&mut message));
+        self.events.lock().unwrap().push((*event.metadata().level(), message));
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_instrumentation() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("cycle_count"), "65535\n").unwrap();
+
+    let collector = Collector::default();
+    let spans = collector.spans.clone();
+    let events = collector.events.clone();
+
+    tracing::subscriber::with_default(collector, || {
+        let manager = Manager::from(SysFsManager::with_root(root.path()));
+        let mut battery = first_battery(&manager);
+        manager.refresh(&mut battery).unwrap();
+    });
+
+    let spans = spans.lock().unwrap();
+    for name in &["enumerate", "device", "refresh"] {
+        assert!(spans.iter().any(|span| span == name), "{} span is missing", name);
+    }
+
+    let events = events.lock().unwrap();
+    assert!(events.iter().any(|(level, _)| *level == Level::DEBUG));
+    assert!(events
+        .iter()
+        .any(|(level, message)| *level == Level::WARN && message.contains("cycle count")));
+}
+
+#[test]
+fn test_enumeration_span() {
+    let root = tempfile::tempdir().unwrap();
+    for name in &["BAT0", "BAT1", "BAT2"] {
+        create_supply(root.path(), name, "Battery");
+    }
+
+    let collector = Collector::default();
+    let spans = collector.spans.clone();
+
+    tracing::subscriber::with_default(collector, || {
+        let manager = Manager::from(SysFsManager::with_root(root.path()));
+        assert_eq!(manager.batteries().unwrap().count(), 3);
+    });
+
+    let spans = spans.lock().unwrap();
+    assert_eq!(spans.iter().filter(|span| *span == "enumerate").count(), 1);
+}
@@ -0,0 +1,31 @@
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_into_iterator() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    create_supply(root.path(), "BAT1", "Battery");
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let names: Vec<String> = (&manager)
+        .into_iter()
+        .map(|battery| battery.unwrap().name().unwrap().to_string())
+        .collect();
+
+    assert_eq!(names, vec!["BAT0", "BAT1"]);
+}
+
+#[test]
+fn test_into_iterator_failure() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path().join("missing")));
+
+    assert!(manager.batteries().is_err());
+
+    let mut batteries = (&manager).into_iter();
+    assert_eq!(batteries.size_hint(), (1, Some(1)));
+    assert!(batteries.next().unwrap().is_err());
+    assert!(batteries.next().is_none());
+}
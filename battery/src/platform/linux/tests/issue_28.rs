@@ -47,12 +47,15 @@ fn test_issue_28() {
     assert_eq!(device.model(), Some("PABAS0241231"));
     assert_eq!(device.serial_number(), Some("41167"));
     assert_abs_diff_eq!(device.state_of_health().value, 0.9511111);
-    assert_abs_diff_eq!(device.state_of_charge().value, 0.21);
+    // `charge_now / charge_full`, not the rounded `capacity` value
+    assert_abs_diff_eq!(device.state_of_charge().value, 0.21174, epsilon = 0.00001);
     assert_abs_diff_eq!(device.energy().value, 29753.998);
     assert_abs_diff_eq!(device.energy_full().value, 140520.95);
     assert_abs_diff_eq!(device.energy_full_design().value, 147744.0);
     assert_abs_diff_eq!(device.energy_rate().value, 0.0);
     assert_abs_diff_eq!(device.voltage().value, 10.663);
+    assert!(device.energy_until_full().is_none());
+    assert_abs_diff_eq!(device.energy_until_empty().unwrap().value, 29753.998);
 
     fs::remove_dir_all(path).unwrap();
 }
@@ -0,0 +1,98 @@
+use std::fs;
+
+use super::super::{SysFsDevice, SysFsManager};
+use super::{battery_names, create_supply};
+use crate::{Battery, Kind, KindFilter, Manager};
+
+fn names(manager: &Manager, filter: Option<KindFilter>) -> Vec<String> {
+    let batteries = match filter {
+        Some(filter) => manager.batteries_filtered(filter),
+        None => manager.batteries(),
+    };
+
+    battery_names(batteries.unwrap())
+}
+
+#[test]
+fn test_kind_filter() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "AC", "Mains");
+    create_supply(root.path(), "BAT0", "Battery");
+    create_supply(root.path(), "UPS0", "UPS");
+    let mouse = create_supply(root.path(), "hid-mouse", "Battery");
+    fs::write(mouse.join("scope"), "Device\n").unwrap();
+    let unknown = create_supply(root.path(), "misc", "Battery");
+    fs::write(unknown.join("scope"), "Unknown\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(names(&manager, None), vec!["BAT0"]);
+    assert_eq!(names(&manager, Some(KindFilter::SYSTEM)), vec!["BAT0"]);
+    assert_eq!(names(&manager, Some(KindFilter::PERIPHERAL)), vec!["hid-mouse"]);
+    assert_eq!(
+        names(&manager, Some(KindFilter::SYSTEM | KindFilter::UPS)),
+        vec!["BAT0", "UPS0"]
+    );
+    assert_eq!(
+        names(&manager, Some(KindFilter::ALL)),
+        vec!["BAT0", "UPS0", "hid-mouse"]
+    );
+}
+
+#[test]
+fn test_excluded_devices_are_not_read() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let mouse = create_supply(root.path(), "hid-mouse", "Battery");
+    fs::write(mouse.join("scope"), "Device\n").unwrap();
+    // Any attempt to read this attribute fails
+    fs::remove_file(mouse.join("energy_now")).unwrap();
+    fs::create_dir(mouse.join("energy_now")).unwrap();
+    fs::remove_file(mouse.join("present")).ok();
+    fs::create_dir(mouse.join("present")).unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(names(&manager, Some(KindFilter::SYSTEM)), vec!["BAT0"]);
+    assert!(manager
+        .batteries_filtered(KindFilter::PERIPHERAL)
+        .unwrap()
+        .next()
+        .unwrap()
+        .is_err());
+}
+
+#[test]
+fn test_contains() {
+    let mut filter = KindFilter::SYSTEM;
+    assert!(filter.contains(KindFilter::SYSTEM));
+    assert!(!filter.contains(KindFilter::UPS));
+
+    filter |= KindFilter::UPS;
+    assert!(filter.contains(KindFilter::SYSTEM | KindFilter::UPS));
+    assert!(!filter.contains(KindFilter::PERIPHERAL));
+    assert!(KindFilter::ALL.contains(filter));
+}
+
+#[test]
+fn test_kind() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    create_supply(root.path(), "UPS0", "UPS");
+    let mouse = create_supply(root.path(), "hid-mouse", "Battery");
+    fs::write(mouse.join("scope"), "Device\n").unwrap();
+    let unknown = create_supply(root.path(), "misc", "Battery");
+    fs::write(unknown.join("scope"), "Unknown\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let kinds = manager
+        .batteries_filtered(KindFilter::ALL)
+        .unwrap()
+        .map(|battery| battery.unwrap().kind())
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, vec![Kind::System, Kind::Ups, Kind::Peripheral]);
+
+    // Batteries with the unknown scope are never enumerated, but can be loaded directly
+    let battery = Battery::from(SysFsDevice::try_from(unknown).unwrap());
+    assert_eq!(battery.kind(), Kind::Unknown);
+}
@@ -0,0 +1,24 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::Manager;
+
+#[test]
+fn test_last_updated() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+
+    let created = battery.last_updated().unwrap();
+
+    manager.refresh(&mut battery).unwrap();
+    let refreshed = battery.last_updated().unwrap();
+    assert!(refreshed >= created);
+
+    // Failed refresh keeps the old data and its timestamp
+    fs::write(path.join("present"), "0\n").unwrap();
+    assert!(manager.refresh(&mut battery).is_err());
+    assert_eq!(battery.last_updated(), Some(refreshed));
+}
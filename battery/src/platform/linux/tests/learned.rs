@@ -0,0 +1,98 @@
+use std::time::{Duration, Instant};
+
+use approx::assert_abs_diff_eq;
+
+use crate::units::ratio::percent;
+use crate::units::time::minute;
+use crate::units::Ratio;
+use crate::{EstimateSource, LearnedEstimator, State};
+
+// Deterministic noise, in percents
+const NOISE: [f32; 7] = [0.4, -0.3, 0.0, 0.5, -0.5, 0.2, -0.1];
+
+/// Pushes a sample each minute, with the state of charge changing by `rate` percents per minute.
+fn script(
+    estimator: &mut LearnedEstimator,
+    start: Instant,
+    state: State,
+    from: f32,
+    rate: f32,
+    minutes: u64,
+    noise: f32,
+) {
+    for idx in 0..=minutes {
+        let charge = from + rate * idx as f32 + NOISE[idx as usize % NOISE.len()] * noise;
+        // Percentage-only devices are reporting whole percents
+        let charge = Ratio::new::<percent>(charge.round());
+        estimator.push_sample(start + Duration::from_secs(idx * 60), state, charge);
+    }
+}
+
+#[test]
+fn test_discharging() {
+    let mut estimator = LearnedEstimator::new();
+    script(&mut estimator, Instant::now(), State::Discharging, 80.0, -1.0, 15, 1.0);
+
+    let estimate = estimator.time_to_empty().unwrap();
+    assert_eq!(estimate.source(), EstimateSource::Learned);
+    // 65% left, draining 1% per minute
+    assert_abs_diff_eq!(estimate.time().get::<minute>(), 65.0, epsilon = 2.0);
+    assert!(estimator.time_to_full().is_none());
+}
+
+#[test]
+fn test_charging() {
+    let mut estimator = LearnedEstimator::new().min_window(Duration::from_secs(5 * 60));
+    script(&mut estimator, Instant::now(), State::Charging, 40.0, 2.0, 10, 1.0);
+
+    // 60% charged, 40% more at 2% per minute
+    let estimate = estimator.time_to_full().unwrap();
+    assert_abs_diff_eq!(estimate.time().get::<minute>(), 20.0, epsilon = 1.0);
+    assert!(estimator.time_to_empty().is_none());
+}
+
+#[test]
+fn test_window_is_not_covered() {
+    let mut estimator = LearnedEstimator::new();
+    script(&mut estimator, Instant::now(), State::Discharging, 80.0, -1.0, 9, 0.0);
+
+    assert!(estimator.time_to_empty().is_none());
+}
+
+#[test]
+fn test_inconsistent_direction() {
+    let mut estimator = LearnedEstimator::new();
+    script(&mut estimator, Instant::now(), State::Discharging, 50.0, 0.5, 15, 0.0);
+
+    assert!(estimator.time_to_empty().is_none());
+}
+
+#[test]
+fn test_high_variance() {
+    let mut estimator = LearnedEstimator::new();
+    script(&mut estimator, Instant::now(), State::Discharging, 80.0, -1.0, 15, 20.0);
+
+    assert!(estimator.time_to_empty().is_none());
+
+    let mut estimator = LearnedEstimator::new().max_deviation(Ratio::new::<percent>(10.0));
+    script(&mut estimator, Instant::now(), State::Discharging, 80.0, -1.0, 15, 20.0);
+
+    assert!(estimator.time_to_empty().is_some());
+}
+
+#[test]
+fn test_reset_on_state_change() {
+    let start = Instant::now();
+    let mut estimator = LearnedEstimator::new();
+    script(&mut estimator, start, State::Discharging, 80.0, -1.0, 15, 0.0);
+    assert!(estimator.time_to_empty().is_some());
+
+    estimator.push_sample(
+        start + Duration::from_secs(16 * 60),
+        State::Charging,
+        Ratio::new::<percent>(65.0),
+    );
+
+    assert!(estimator.time_to_empty().is_none());
+    assert!(estimator.time_to_full().is_none());
+}
@@ -0,0 +1,54 @@
+use std::fs;
+use std::os::unix::fs::symlink;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_line_power() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let ac = create_supply(root.path(), "AC", "Mains");
+    fs::write(ac.join("online"), "1\n").unwrap();
+    fs::write(ac.join("charge_type"), "Fast\n").unwrap();
+    let adp = create_supply(root.path(), "ADP1", "Mains");
+    fs::write(adp.join("charge_type"), "N/A\n").unwrap();
+    create_supply(root.path(), "ucsi-source-psy-USBC000:001", "USB");
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let supplies = manager.line_power().unwrap();
+
+    assert_eq!(supplies.len(), 2);
+    assert_eq!(supplies[0].name(), "AC");
+    assert_eq!(supplies[0].is_online(), Some(true));
+    assert_eq!(supplies[0].charge_type(), Some("Fast"));
+    assert_eq!(supplies[1].name(), "ADP1");
+    assert_eq!(supplies[1].is_online(), None);
+    assert_eq!(supplies[1].charge_type(), None);
+}
+
+#[test]
+fn test_line_power_missing() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert!(manager.line_power().unwrap().is_empty());
+}
+
+// Power supply removed between the listing and the reading is skipped, as the enumeration does
+#[test]
+fn test_line_power_removed() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "AC", "Mains");
+    symlink(root.path().join("removed"), root.path().join("ADP1")).unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let supplies = manager.line_power().unwrap();
+
+    assert_eq!(supplies.len(), 1);
+    assert_eq!(supplies[0].name(), "AC");
+    assert_eq!(manager.batteries().unwrap().count(), 0);
+}
@@ -0,0 +1,52 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::units::power::watt;
+use crate::Battery;
+
+#[test]
+fn test_charge_voltage() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "charge_now" => 725000,
+        "charge_full" => 3424000,
+        "constant_charge_current_max" => 2000000,
+        "constant_charge_voltage_max" => 13050000,
+        "voltage_min_design" => 11400000,
+        "voltage_now" => 12663000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.max_charge_power().unwrap().get::<watt>(), 26.1, epsilon = 0.001);
+}
+
+#[test]
+fn test_design_voltage() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "charge_now" => 725000,
+        "charge_full" => 3424000,
+        "constant_charge_current_max" => 2000000,
+        "voltage_min_design" => 11400000,
+        "voltage_now" => 12663000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_abs_diff_eq!(battery.max_charge_power().unwrap().get::<watt>(), 22.8, epsilon = 0.001);
+}
+
+#[test]
+fn test_not_reported() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert!(battery.max_charge_power().is_none());
+}
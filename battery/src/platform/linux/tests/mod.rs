@@ -1,3 +1,13 @@
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::SysFsDevice;
+use crate::types::clock::FakeClock;
+use crate::{Batteries, Battery, Config, Manager};
+
 /// This macro generates the bunch of files representing the
 /// `/sys/class/power_supply/{name}/*` directory contents.
 macro_rules! sysfs_test_suite {
@@ -19,5 +29,150 @@ macro_rules! sysfs_test_suite {
     }};
 }
 
+/// Creates `{root}/{name}` directory with a minimal set of files
+/// required to represent a power supply of the given type.
+pub fn create_supply(root: &Path, name: &str, type_: &str) -> PathBuf {
+    let path = root.join(name);
+    fs::create_dir(&path).unwrap();
+
+    let files = [
+        ("type", type_),
+        ("serial_number", name),
+        ("status", "Discharging"),
+        ("energy_now", "25000000"),
+        ("energy_full", "50000000"),
+        ("energy_full_design", "50000000"),
+        ("voltage_now", "12000000"),
+    ];
+    for (name, value) in files.iter() {
+        let mut file = fs::File::create(path.join(name)).unwrap();
+        writeln!(file, "{}", value).unwrap();
+    }
+
+    path
+}
+
+/// Writes the `value` into the `name` attribute of the power supply at `path`.
+pub fn set_attribute<T: fmt::Display>(path: &Path, name: &str, value: T) {
+    fs::write(path.join(name), format!("{}\n", value)).unwrap();
+}
+
+/// Sets the battery `status` and its `energy_now` value, in mWh.
+pub fn set_energy(path: &Path, status: &str, energy_mwh: u32) {
+    set_attribute(path, "status", status);
+    set_attribute(path, "energy_now", energy_mwh * 1_000);
+}
+
+/// Loads the battery from the power supply at `path`, with the `config` attached.
+pub fn load_battery(path: &Path, config: &Config) -> Battery {
+    let device = SysFsDevice::try_from(path.to_path_buf()).unwrap();
+
+    Battery::from(device).with_config(config)
+}
+
+/// Loads the battery created by the `create_supply`, with the `attributes` added or replaced.
+pub fn battery_with(attributes: &[(&str, &str)]) -> Battery {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    for (name, value) in attributes {
+        set_attribute(&path, name, value);
+    }
+
+    load_battery(&path, &Config::default())
+}
+
+/// Returns the first battery found by the `manager`.
+pub fn first_battery(manager: &Manager) -> Battery {
+    manager.batteries().unwrap().next().unwrap().unwrap()
+}
+
+/// Collects the device names of the `batteries`.
+pub fn battery_names(batteries: Batteries) -> Vec<String> {
+    batteries
+        .map(|battery| battery.unwrap().name().unwrap().to_string())
+        .collect()
+}
+
+/// Advances the `clock` by `secs` seconds and refreshes the `battery`.
+pub fn step(manager: &Manager, clock: &FakeClock, battery: &mut Battery, secs: u64) {
+    clock.advance(Duration::from_secs(secs));
+    manager.refresh(battery).unwrap();
+}
+
+mod absolute_soc;
+mod active_battery;
+mod adapter;
+mod alarm;
+#[cfg(feature = "control")]
+mod alarm_control;
+mod battery_by_name;
+mod battery_saver;
+mod calibrating;
+mod capacity;
+mod capacity_resolution;
+mod charge_ratio;
+mod charge_threshold;
+mod charging_phase;
+mod clock;
+mod condition;
+mod cycle_draw;
+mod energy_full_estimated;
+mod energy_rate;
+mod energy_since_start;
+mod enumeration_delta;
+mod failure_risk;
+mod fast_charge;
+#[cfg(feature = "fast-refresh")]
+mod fast_refresh;
+mod full_scan;
+mod full_threshold;
+mod index_map;
+#[cfg(feature = "tracing")]
+mod instrumentation;
+mod into_iterator;
 mod issue_28;
 mod issue_40;
+mod kind_filter;
+mod last_updated;
+mod learned;
+mod line_power;
+mod max_charge_power;
+mod name;
+mod ordering;
+mod partial_refresh;
+mod percentage_bar;
+mod present;
+mod projected_soc;
+#[cfg(feature = "prometheus")]
+mod prometheus;
+mod prompt_segment;
+mod quality;
+mod rate_kind;
+mod rechargeable;
+mod reload;
+mod restricted;
+mod rounding;
+#[cfg(all(feature = "schemars", feature = "serde"))]
+mod schema;
+mod self_discharge;
+mod sentinel;
+mod serial_number;
+#[cfg(feature = "serde")]
+mod serialization;
+mod severity;
+mod skip_empty;
+mod snapshot_parallel;
+mod sorted;
+mod stale;
+mod state_raw;
+mod state_unstable;
+mod summary_line;
+mod system_power_draw;
+mod tapered;
+mod technology_override;
+mod temperature;
+mod threshold;
+mod time_in_state;
+mod validate;
+mod voltage_design;
+mod watcher;
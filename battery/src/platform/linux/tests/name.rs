@@ -0,0 +1,25 @@
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+
+#[test]
+fn test_name_is_sysfs_entry() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT1", "Battery");
+
+    let mut device = SysFsDevice::try_from(path).unwrap();
+    assert_eq!(device.name(), Some("BAT1"));
+
+    device.refresh().unwrap();
+    assert_eq!(device.name(), Some("BAT1"));
+}
+
+#[test]
+fn test_device_path_is_sysfs_directory() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT1", "Battery");
+
+    let device = SysFsDevice::try_from(path.clone()).unwrap();
+    assert_eq!(device.device_path(), path.to_str());
+    assert_eq!(device.location(), None);
+}
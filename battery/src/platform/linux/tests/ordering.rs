@@ -0,0 +1,37 @@
+use std::rc::Rc;
+
+use super::super::{SysFsIterator, SysFsManager};
+use super::create_supply;
+use crate::platform::traits::{BatteryDevice, BatteryIterator};
+
+#[test]
+fn test_deterministic_ordering() {
+    let orders = [
+        ["BAT10", "AC", "BAT2", "BAT1"],
+        ["BAT1", "BAT2", "AC", "BAT10"],
+        ["AC", "BAT2", "BAT10", "BAT1"],
+    ];
+
+    for order in orders.iter() {
+        let root = tempfile::tempdir().unwrap();
+        for name in order.iter() {
+            let type_ = if *name == "AC" { "Mains" } else { "Battery" };
+            create_supply(root.path(), name, type_);
+        }
+
+        let manager = Rc::new(SysFsManager::with_root(root.path()));
+        let serials = SysFsIterator::new(manager)
+            .unwrap()
+            .map(|device| device.unwrap().serial_number().map(ToOwned::to_owned))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            serials,
+            vec![
+                Some("BAT1".to_string()),
+                Some("BAT2".to_string()),
+                Some("BAT10".to_string())
+            ]
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use std::fs;
+use std::path::Path;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::units::energy::watt_hour;
+use crate::units::ratio::percent;
+use crate::{Config, Manager};
+
+fn break_attribute(path: &Path, name: &str) {
+    // Reading a directory fails with `EISDIR`, unlike the missing attributes which are just skipped
+    let _ = fs::remove_file(path.join(name));
+    fs::create_dir(path.join(name)).unwrap();
+}
+
+fn fix_attribute(path: &Path, name: &str, value: &str) {
+    fs::remove_dir(path.join(name)).unwrap();
+    fs::write(path.join(name), value).unwrap();
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+
+    break_attribute(&path, "cycle_count");
+    assert!(manager.refresh(&mut battery).is_err());
+    assert!(battery.field_errors().is_empty());
+}
+
+#[test]
+fn test_failed_fields_are_collected() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("cycle_count"), "42\n").unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().partial_refresh(true));
+    let mut battery = first_battery(&manager);
+    assert_eq!(battery.cycle_count(), Some(42));
+
+    break_attribute(&path, "cycle_count");
+    manager.refresh(&mut battery).unwrap();
+
+    let fields = battery.field_errors().iter().map(|e| e.field()).collect::<Vec<_>>();
+    assert_eq!(fields, vec!["cycle_count"]);
+    assert_eq!(battery.cycle_count(), None);
+    assert_abs_diff_eq!(battery.energy().get::<watt_hour>(), 25.0, epsilon = 0.001);
+
+    fix_attribute(&path, "cycle_count", "43\n");
+    manager.refresh(&mut battery).unwrap();
+
+    assert!(battery.field_errors().is_empty());
+    assert_eq!(battery.cycle_count(), Some(43));
+}
+
+#[test]
+fn test_degraded_energy() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().partial_refresh(true));
+    let mut battery = first_battery(&manager);
+
+    break_attribute(&path, "energy_now");
+    manager.refresh(&mut battery).unwrap();
+
+    assert!(battery.field_errors().iter().any(|e| e.field() == "energy"));
+    // Failed value is kept from the previous refresh instead of dropping to zero
+    assert_abs_diff_eq!(battery.energy().get::<watt_hour>(), 25.0, epsilon = 0.001);
+    assert_abs_diff_eq!(battery.state_of_charge().get::<percent>(), 50.0, epsilon = 0.001);
+    assert_abs_diff_eq!(battery.energy_full().get::<watt_hour>(), 50.0, epsilon = 0.001);
+
+    fix_attribute(&path, "energy_now", "20000000\n");
+    manager.refresh(&mut battery).unwrap();
+
+    assert!(battery.field_errors().is_empty());
+    assert_abs_diff_eq!(battery.energy().get::<watt_hour>(), 20.0, epsilon = 0.001);
+}
+
+#[test]
+fn test_missing_device_fails() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().partial_refresh(true));
+    let mut battery = first_battery(&manager);
+
+    fs::remove_dir_all(&path).unwrap();
+    assert!(manager.refresh(&mut battery).is_err());
+}
+
+// Attributes which are not essential for the battery, and their fields
+const BEST_EFFORT: [(&str, &str); 11] = [
+    ("temp_ambient", "ambient_temperature"),
+    ("temp_min", "temperature_min"),
+    ("temp_max", "temperature_max"),
+    ("temp_alert_max", "temperature_alert_max"),
+    ("voltage_min_design", "voltage_min_design"),
+    ("voltage_max_design", "voltage_max_design"),
+    ("calibrate", "is_calibrating"),
+    ("alarm", "capacity_alarm"),
+    ("charge_type", "fast_charge_active"),
+    ("constant_charge_current_max", "max_charge_power"),
+    ("charge_control_end_threshold", "charge_stop_threshold"),
+];
+
+#[test]
+fn test_best_effort_fields() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    for (attribute, _) in BEST_EFFORT.iter() {
+        break_attribute(&path, attribute);
+    }
+
+    // Neither the load nor the default refresh are failing
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+    manager.refresh(&mut battery).unwrap();
+    assert_eq!(battery.ambient_temperature(), None);
+    assert!(battery.field_errors().is_empty());
+
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().partial_refresh(true));
+    manager.refresh(&mut battery).unwrap();
+    let fields = battery.field_errors().iter().map(|e| e.field()).collect::<Vec<_>>();
+    let expected = BEST_EFFORT.iter().map(|(_, field)| *field).collect::<Vec<_>>();
+    assert_eq!(fields, expected);
+}
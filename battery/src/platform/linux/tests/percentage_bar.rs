@@ -0,0 +1,48 @@
+use std::path::Path;
+
+use super::{create_supply, load_battery, set_attribute};
+use crate::{Battery, Config, DisplayConfig};
+
+fn battery(path: &Path, energy_uwh: u32, config: &Config) -> Battery {
+    set_attribute(path, "energy_now", energy_uwh);
+
+    load_battery(path, config)
+}
+
+#[test]
+fn test_percentage_bar() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::default();
+
+    // 50%
+    let battery = battery(&path, 25_000_000, &config);
+    assert_eq!(battery.as_percentage_bar(10), "[#####-----]");
+    assert_eq!(battery.as_percentage_bar(0), "[]");
+    // Half of a cell is rounded up
+    assert_eq!(battery.as_percentage_bar(1), "[#]");
+    assert_eq!(battery.as_percentage_bar(3), "[##-]");
+}
+
+#[test]
+fn test_percentage_bar_bounds() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::default();
+
+    assert_eq!(battery(&path, 0, &config).as_percentage_bar(4), "[----]");
+    assert_eq!(battery(&path, 50_000_000, &config).as_percentage_bar(4), "[####]");
+    // 2% is less than a half of the cell
+    assert_eq!(battery(&path, 1_000_000, &config).as_percentage_bar(10), "[----------]");
+    assert_eq!(battery(&path, 49_000_000, &config).as_percentage_bar(1), "[#]");
+}
+
+#[test]
+fn test_percentage_bar_chars() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::new().display(DisplayConfig::new().bar_chars('█', '░'));
+
+    // 75%
+    assert_eq!(battery(&path, 37_500_000, &config).as_percentage_bar(4), "[███░]");
+}
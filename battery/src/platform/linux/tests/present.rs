@@ -0,0 +1,32 @@
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::rc::Rc;
+
+use super::super::{SysFsDevice, SysFsIterator, SysFsManager};
+use super::create_supply;
+use crate::platform::traits::BatteryIterator;
+
+// Removable batteries in hot-swap bays are keeping their sysfs directory,
+// flipping the `present` attribute instead.
+#[test]
+fn test_present_transition() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Rc::new(SysFsManager::with_root(root.path()));
+
+    fs::write(path.join("present"), "1\n").unwrap();
+    let mut device = SysFsDevice::try_from(path.clone()).unwrap();
+    assert!(device.refresh().is_ok());
+    assert_eq!(SysFsIterator::new(manager.clone()).unwrap().count(), 1);
+
+    fs::write(path.join("present"), "0\n").unwrap();
+    let err = device.refresh().unwrap_err();
+    let source = err.source().and_then(|e| e.downcast_ref::<io::Error>()).unwrap();
+    assert_eq!(source.kind(), io::ErrorKind::NotFound);
+    assert_eq!(SysFsIterator::new(manager.clone()).unwrap().count(), 0);
+
+    fs::write(path.join("present"), "1\n").unwrap();
+    assert!(device.refresh().is_ok());
+    assert_eq!(SysFsIterator::new(manager).unwrap().count(), 1);
+}
@@ -0,0 +1,84 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use approx::assert_abs_diff_eq;
+
+use super::{create_supply, load_battery, set_attribute};
+use crate::units::ratio::percent;
+use crate::{Battery, Config};
+
+fn battery(path: &Path, status: &str, power_uw: u32) -> Battery {
+    set_attribute(path, "status", status);
+    set_attribute(path, "power_now", power_uw);
+
+    load_battery(path, &Config::default())
+}
+
+#[test]
+fn test_discharging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    // 25 Wh of 50 Wh left, draining 10 W
+    let battery = battery(&path, "Discharging", 10_000_000);
+    let after_hour = battery.projected_soc_after(Duration::from_secs(3600)).unwrap();
+    assert_abs_diff_eq!(after_hour.get::<percent>(), 30.0, epsilon = 0.001);
+
+    let after_day = battery.projected_soc_after(Duration::from_secs(86400)).unwrap();
+    assert_abs_diff_eq!(after_day.get::<percent>(), 0.0);
+}
+
+#[test]
+fn test_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    let battery = battery(&path, "Charging", 10_000_000);
+    let after_half_hour = battery.projected_soc_after(Duration::from_secs(1800)).unwrap();
+    assert_abs_diff_eq!(after_half_hour.get::<percent>(), 60.0, epsilon = 0.001);
+
+    let after_day = battery.projected_soc_after(Duration::from_secs(86400)).unwrap();
+    assert_abs_diff_eq!(after_day.get::<percent>(), 100.0);
+}
+
+#[test]
+fn test_charging_is_limited_by_the_threshold() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("charge_control_end_threshold"), "80\n").unwrap();
+
+    let battery = battery(&path, "Charging", 10_000_000);
+    let after_day = battery.projected_soc_after(Duration::from_secs(86400)).unwrap();
+    assert_abs_diff_eq!(after_day.get::<percent>(), 80.0, epsilon = 0.001);
+}
+
+#[test]
+fn test_unknown_rate() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    let idle = battery(&path, "Discharging", 0);
+    assert_eq!(idle.projected_soc_after(Duration::from_secs(3600)), None);
+
+    let full = battery(&path, "Full", 10_000_000);
+    assert_eq!(full.projected_soc_after(Duration::from_secs(3600)), None);
+}
+
+#[test]
+fn test_will_survive() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    // 25 Wh drained at 10 W lasts for two and a half hours
+    let discharging = battery(&path, "Discharging", 10_000_000);
+    assert_eq!(discharging.will_survive(Duration::from_secs(3600)), Some(true));
+    assert_eq!(discharging.will_survive(Duration::from_secs(9000)), Some(true));
+    assert_eq!(discharging.will_survive(Duration::from_secs(9001)), Some(false));
+
+    let charging = battery(&path, "Charging", 10_000_000);
+    assert_eq!(charging.will_survive(Duration::from_secs(3600)), None);
+
+    let idle = battery(&path, "Discharging", 0);
+    assert_eq!(idle.will_survive(Duration::from_secs(3600)), None);
+}
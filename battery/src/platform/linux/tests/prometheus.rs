@@ -0,0 +1,86 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::export::prometheus::write_metrics;
+use crate::{Battery, Manager, Result};
+
+const EXPECTED: &str = r#"# HELP battery_state_of_charge_ratio Battery state of charge
+# TYPE battery_state_of_charge_ratio gauge
+battery_state_of_charge_ratio{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 0.5
+battery_state_of_charge_ratio{battery="BAT1"} 0.5
+# HELP battery_state_of_health_ratio Battery state of health
+# TYPE battery_state_of_health_ratio gauge
+battery_state_of_health_ratio{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 1
+battery_state_of_health_ratio{battery="BAT1"} 1
+# HELP battery_energy_watt_hours Energy left in the battery
+# TYPE battery_energy_watt_hours gauge
+battery_energy_watt_hours{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 25
+battery_energy_watt_hours{battery="BAT1"} 25
+# HELP battery_energy_full_watt_hours Energy in the fully charged battery
+# TYPE battery_energy_full_watt_hours gauge
+battery_energy_full_watt_hours{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 50
+battery_energy_full_watt_hours{battery="BAT1"} 50
+# HELP battery_energy_full_design_watt_hours Energy in the fully charged battery by design
+# TYPE battery_energy_full_design_watt_hours gauge
+battery_energy_full_design_watt_hours{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 50
+battery_energy_full_design_watt_hours{battery="BAT1"} 50
+# HELP battery_power_watts Battery charging or discharging rate
+# TYPE battery_power_watts gauge
+battery_power_watts{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 12.5
+battery_power_watts{battery="BAT1"} 0
+# HELP battery_voltage_volts Battery voltage
+# TYPE battery_voltage_volts gauge
+battery_voltage_volts{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 12
+battery_voltage_volts{battery="BAT1"} 12
+# HELP battery_temperature_celsius Battery temperature
+# TYPE battery_temperature_celsius gauge
+battery_temperature_celsius{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 30.5
+# HELP battery_cycle_count Number of battery charge/discharge cycles
+# TYPE battery_cycle_count gauge
+battery_cycle_count{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 42
+# HELP battery_time_to_empty_seconds Time left until the battery is fully discharged
+# TYPE battery_time_to_empty_seconds gauge
+battery_time_to_empty_seconds{battery="BAT0",vendor="ACME \"Power\"",model="X1"} 7200
+# HELP battery_state Battery state
+# TYPE battery_state gauge
+battery_state{battery="BAT0",vendor="ACME \"Power\"",model="X1",state="unknown"} 0
+battery_state{battery="BAT0",vendor="ACME \"Power\"",model="X1",state="charging"} 0
+battery_state{battery="BAT0",vendor="ACME \"Power\"",model="X1",state="discharging"} 1
+battery_state{battery="BAT0",vendor="ACME \"Power\"",model="X1",state="empty"} 0
+battery_state{battery="BAT0",vendor="ACME \"Power\"",model="X1",state="full"} 0
+battery_state{battery="BAT1",state="unknown"} 0
+battery_state{battery="BAT1",state="charging"} 0
+battery_state{battery="BAT1",state="discharging"} 0
+battery_state{battery="BAT1",state="empty"} 0
+battery_state{battery="BAT1",state="full"} 1
+"#;
+
+#[test]
+fn test_write_metrics() {
+    let root = tempfile::tempdir().unwrap();
+    let first = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(first.join("manufacturer"), "ACME \"Power\"\n").unwrap();
+    fs::write(first.join("model_name"), "X1\n").unwrap();
+    fs::write(first.join("power_now"), "12500000\n").unwrap();
+    fs::write(first.join("temp"), "305\n").unwrap();
+    fs::write(first.join("cycle_count"), "42\n").unwrap();
+    let second = create_supply(root.path(), "BAT1", "Battery");
+    fs::write(second.join("status"), "Full\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let batteries = manager.batteries().unwrap().collect::<Result<Vec<Battery>>>().unwrap();
+
+    let mut output = String::new();
+    write_metrics(&mut output, &batteries).unwrap();
+
+    assert_eq!(output, EXPECTED);
+}
+
+#[test]
+fn test_no_batteries() {
+    let mut output = String::new();
+    write_metrics(&mut output, &[]).unwrap();
+
+    assert!(output.is_empty());
+}
@@ -0,0 +1,35 @@
+use super::battery_with;
+use crate::Battery;
+
+fn battery(status: &str, capacity: u32) -> Battery {
+    battery_with(&[("status", status), ("capacity", &capacity.to_string())])
+}
+
+#[test]
+fn test_icon_levels() {
+    let levels = [
+        (0, 0),
+        (12, 0),
+        (13, 1),
+        (37, 1),
+        (38, 2),
+        (62, 2),
+        (63, 3),
+        (87, 3),
+        (88, 4),
+        (100, 4),
+    ];
+
+    for (capacity, level) in levels.iter() {
+        let segment = battery("Discharging", *capacity).prompt_segment();
+        assert_eq!(segment.icon_level(), *level, "capacity {}%", capacity);
+        assert_eq!(u32::from(segment.percent()), *capacity);
+    }
+}
+
+#[test]
+fn test_charging_flag() {
+    assert!(battery("Charging", 50).prompt_segment().is_charging());
+    assert!(!battery("Discharging", 50).prompt_segment().is_charging());
+    assert!(!battery("Full", 100).prompt_segment().is_charging());
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use super::super::SysFsDevice;
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, EstimateQuality, LearnedEstimator, State};
+
+#[test]
+fn test_instant_rate_estimate() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 45000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert!(battery.time_to_empty().is_some());
+    assert_eq!(battery.time_to_empty_quality(), Some(EstimateQuality::Medium));
+    assert_eq!(battery.time_to_full_quality(), None);
+}
+
+#[test]
+fn test_inconsistent_battery_estimate() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 56000000,
+        "energy_full" => 55000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.time_to_empty_quality(), Some(EstimateQuality::Low));
+}
+
+#[test]
+fn test_no_estimate() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 45000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert!(battery.time_to_empty().is_none());
+    assert_eq!(battery.time_to_empty_quality(), None);
+}
+
+#[test]
+fn test_learned_estimate() {
+    let start = Instant::now();
+    let mut steady = LearnedEstimator::new();
+    let mut noisy = LearnedEstimator::new();
+    // Noise is within the allowed deviation, but above its half
+    let noise = [0.0, 1.5, -1.5];
+    for idx in 0..=15u64 {
+        let at = start + Duration::from_secs(idx * 60);
+        let charge = 80.0 - idx as f32;
+        steady.push_sample(at, State::Discharging, Ratio::new::<percent>(charge));
+        noisy.push_sample(
+            at,
+            State::Discharging,
+            Ratio::new::<percent>(charge + noise[idx as usize % noise.len()]),
+        );
+    }
+
+    assert_eq!(steady.time_to_empty().unwrap().quality(), EstimateQuality::Medium);
+    assert_eq!(noisy.time_to_empty().unwrap().quality(), EstimateQuality::Low);
+}
+
+#[test]
+fn test_ordering() {
+    assert!(EstimateQuality::Low < EstimateQuality::Medium);
+    assert!(EstimateQuality::Medium < EstimateQuality::High);
+}
@@ -0,0 +1,56 @@
+use super::super::SysFsDevice;
+use crate::units::power::watt;
+use crate::{Battery, EstimateQuality, RateKind};
+
+#[test]
+fn test_instant_power() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 10000000,
+        "power_avg" => 8000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.rate_kind(), Some(RateKind::Instantaneous));
+    assert_eq!(battery.energy_rate().get::<watt>(), 10.0);
+    assert_eq!(battery.time_to_empty_quality(), Some(EstimateQuality::Medium));
+}
+
+#[test]
+fn test_averaged_current() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "charge_now" => 2000000,
+        "charge_full" => 4000000,
+        "charge_full_design" => 4000000,
+        "current_avg" => 1000000,
+        "voltage_max_design" => 12000000,
+        "voltage_now" => 11000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.rate_kind(), Some(RateKind::Averaged));
+    assert_eq!(battery.energy_rate().get::<watt>(), 12.0);
+    assert_eq!(battery.time_to_empty_quality(), Some(EstimateQuality::High));
+}
+
+#[test]
+fn test_missing_rate() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.rate_kind(), None);
+}
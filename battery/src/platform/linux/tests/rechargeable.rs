@@ -0,0 +1,41 @@
+use super::super::device::is_rechargeable;
+use super::super::sysfs::Scope;
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+use crate::Technology;
+
+#[test]
+fn test_rules() {
+    let rules = [
+        (Technology::LithiumIon, Scope::System, false, Some(true)),
+        (Technology::LithiumIon, Scope::Device, true, Some(true)),
+        (Technology::NickelMetalHydride, Scope::Unknown, false, Some(true)),
+        (Technology::Unknown, Scope::System, false, Some(true)),
+        (Technology::Unknown, Scope::System, true, Some(true)),
+        (Technology::Unknown, Scope::Device, true, Some(false)),
+        (Technology::Unknown, Scope::Device, false, None),
+        (Technology::Unknown, Scope::Unknown, true, None),
+    ];
+
+    for (technology, scope, level_only, expected) in rules.iter() {
+        assert_eq!(
+            is_rechargeable(*technology, *scope, *level_only),
+            *expected,
+            "{:?}, {:?}, level only: {}",
+            technology,
+            scope,
+            level_only
+        );
+    }
+}
+
+#[test]
+fn test_system_battery() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_eq!(device.is_rechargeable(), Some(true));
+}
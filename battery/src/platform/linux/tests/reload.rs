@@ -0,0 +1,20 @@
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_reload() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    let mut manager = Manager::from(SysFsManager::with_root(root.path()));
+    let batteries = manager.batteries().unwrap();
+
+    // Hot-added battery is visible with or without reload
+    create_supply(root.path(), "BAT1", "Battery");
+    assert_eq!(manager.batteries().unwrap().count(), 2);
+    manager.reload().unwrap();
+    assert_eq!(manager.batteries().unwrap().count(), 2);
+
+    // Iterator created before the reload is still usable and sees the old listing
+    assert_eq!(batteries.count(), 1);
+}
@@ -0,0 +1,101 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::{Config, Manager};
+
+/// Permissions are not checked for root, so the attributes can't be restricted for it.
+fn is_root() -> bool {
+    unsafe { libc::geteuid() == 0 }
+}
+
+/// Makes the attribute write-only, same as the root-only attributes are for the unprivileged users.
+fn restrict(path: &Path, name: &str) {
+    let attr = path.join(name);
+    let _ = fs::remove_file(&attr);
+    fs::write(&attr, "42\n").unwrap();
+    fs::set_permissions(&attr, fs::Permissions::from_mode(0o200)).unwrap();
+}
+
+#[test]
+fn test_restricted_optional_fields() {
+    if is_root() {
+        return;
+    }
+
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    restrict(&path, "serial_number");
+    restrict(&path, "model_name");
+    restrict(&path, "cycle_count");
+    restrict(&path, "temp");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let battery = first_battery(&manager);
+    assert_eq!(battery.serial_number(), None);
+    assert_eq!(battery.serial_number_raw(), None);
+    assert_eq!(battery.model(), None);
+    assert_eq!(battery.cycle_count(), None);
+    assert_eq!(battery.temperature(), None);
+    assert_eq!(
+        battery.restricted_fields(),
+        vec!["model", "serial_number", "temperature", "cycle_count"]
+    );
+}
+
+#[test]
+fn test_restricted_fields_refresh() {
+    if is_root() {
+        return;
+    }
+
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+    assert!(battery.restricted_fields().is_empty());
+
+    restrict(&path, "cycle_count");
+    manager.refresh(&mut battery).unwrap();
+    assert_eq!(battery.restricted_fields(), vec!["cycle_count"]);
+
+    fs::remove_file(path.join("cycle_count")).unwrap();
+    fs::write(path.join("cycle_count"), "42\n").unwrap();
+    manager.refresh(&mut battery).unwrap();
+    assert_eq!(battery.cycle_count(), Some(42));
+    assert!(battery.restricted_fields().is_empty());
+}
+
+// Restricted values are not the errors, so they are not reported by the partial refresh either
+#[test]
+fn test_partial_refresh() {
+    if is_root() {
+        return;
+    }
+
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().partial_refresh(true));
+    let mut battery = first_battery(&manager);
+
+    restrict(&path, "temp");
+    manager.refresh(&mut battery).unwrap();
+    assert!(battery.field_errors().is_empty());
+    assert_eq!(battery.restricted_fields(), vec!["temperature"]);
+}
+
+#[test]
+fn test_restricted_essential_field() {
+    if is_root() {
+        return;
+    }
+
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    restrict(&path, "energy_now");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert!(manager.batteries().unwrap().next().unwrap().is_err());
+}
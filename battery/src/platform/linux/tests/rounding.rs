@@ -0,0 +1,60 @@
+use std::fs;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::{Battery, Config, DisplayConfig, RoundingMode};
+
+#[test]
+fn test_apply() {
+    let cases = [
+        (49.5, 49, 50, 50),
+        (49.4, 49, 49, 50),
+        (49.1, 49, 49, 50),
+        (49.9, 49, 50, 50),
+        // Floating point noise is not shifting the results
+        (28.999_998, 29, 29, 29),
+        (29.000_002, 29, 29, 29),
+        (0.0, 0, 0, 0),
+        (100.0, 100, 100, 100),
+    ];
+
+    for (value, floor, round, ceil) in cases.iter() {
+        assert_eq!(RoundingMode::Floor.apply(*value), *floor, "floor of {}", value);
+        assert_eq!(RoundingMode::Round.apply(*value), *round, "round of {}", value);
+        assert_eq!(RoundingMode::Ceil.apply(*value), *ceil, "ceil of {}", value);
+    }
+}
+
+#[test]
+fn test_battery_percent() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    // 49.5%
+    fs::write(path.join("energy_now"), "24750000\n").unwrap();
+
+    let device = SysFsDevice::try_from(path.clone()).unwrap();
+    let battery = Battery::from(device);
+    assert_eq!(battery.state_of_charge_percent(RoundingMode::Floor), 49);
+    assert_eq!(battery.state_of_charge_percent(RoundingMode::Round), 50);
+    assert_eq!(battery.prompt_segment().percent(), 50);
+    assert!(battery.summary_line().starts_with("BAT0: 50%,"));
+
+    let display = DisplayConfig::new().rounding_mode(RoundingMode::Floor);
+    let config = Config::new().display(display);
+    let device = SysFsDevice::try_from(path).unwrap();
+    let battery = Battery::from(device).with_config(&config);
+    assert_eq!(battery.prompt_segment().percent(), 49);
+    assert!(battery.summary_line().starts_with("BAT0: 49%,"));
+}
+
+#[test]
+fn test_battery_permille() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    for (energy, permille) in [(24_975_000, 500), (24_970_000, 499), (50_000_000, 1000), (0, 0)].iter() {
+        fs::write(path.join("energy_now"), format!("{}\n", energy)).unwrap();
+        let battery = Battery::from(SysFsDevice::try_from(path.clone()).unwrap());
+        assert_eq!(battery.state_of_charge_permille(), *permille, "{} µWh", energy);
+    }
+}
@@ -0,0 +1,125 @@
+use schemars::{schema_for, JsonSchema};
+use serde::Serialize;
+use serde_json::Value;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::{Battery, BatteryInfo, State, Technology};
+
+/// Checks if the `value` serialized representation is one of the `T` schema enum values.
+fn is_valid<T: JsonSchema, V: Serialize>(value: &V) -> bool {
+    let schema = serde_json::to_value(schema_for!(T)).unwrap();
+    let values = schema["enum"].as_array().unwrap();
+
+    assert_eq!(schema["type"], Value::from("string"));
+    values.contains(&serde_json::to_value(value).unwrap())
+}
+
+#[test]
+fn test_state_schema() {
+    for state in [
+        State::Unknown,
+        State::Charging,
+        State::Discharging,
+        State::Empty,
+        State::Full,
+    ]
+    .iter()
+    {
+        assert!(is_valid::<State, _>(state), "{:?} does not match schema", state);
+    }
+}
+
+#[test]
+fn test_technology_schema() {
+    let technologies = [
+        Technology::Unknown,
+        Technology::LithiumIon,
+        Technology::LeadAcid,
+        Technology::LithiumPolymer,
+        Technology::NickelMetalHydride,
+        Technology::NickelCadmium,
+        Technology::NickelZinc,
+        Technology::LithiumIronPhosphate,
+        Technology::RechargeableAlkalineManganese,
+    ];
+
+    for technology in technologies.iter() {
+        assert!(
+            is_valid::<Technology, _>(technology),
+            "{:?} does not match schema",
+            technology
+        );
+    }
+}
+
+#[test]
+fn test_device_values_schema() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Not charging",
+        "technology" => "Li-poly",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "voltage_now" => 12000000
+    );
+
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert!(is_valid::<State, _>(&device.state()));
+    assert!(is_valid::<Technology, _>(&device.technology()));
+}
+
+#[test]
+fn test_battery_info_schema() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "technology" => "Li-ion",
+        "manufacturer" => "SMP",
+        "energy_now" => 30000000,
+        "energy_full" => 50000000,
+        "power_now" => 10000000,
+        "voltage_now" => 12000000,
+        "cycle_count" => 42
+    );
+    let battery = Battery::from(SysFsDevice::try_from(root.path().to_path_buf()).unwrap());
+
+    let schema = serde_json::to_value(schema_for!(BatteryInfo)).unwrap();
+    let value = serde_json::to_value(BatteryInfo::from(&battery)).unwrap();
+    let properties = schema["properties"].as_object().unwrap();
+    let required = schema["required"].as_array().unwrap();
+    let object = value.as_object().unwrap();
+
+    // Both representations are describing the same fields
+    let mut schema_fields = properties.keys().collect::<Vec<_>>();
+    let mut value_fields = object.keys().collect::<Vec<_>>();
+    schema_fields.sort();
+    value_fields.sort();
+    assert_eq!(schema_fields, value_fields);
+
+    for (name, field) in object.iter() {
+        let types = match &properties[name]["type"] {
+            Value::String(type_) => vec![type_.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            // References to the `State` and `Technology` schemas
+            _ => vec!["string"],
+        };
+        let type_ = match field {
+            Value::Null => "null",
+            Value::String(_) => "string",
+            Value::Number(number) if number.is_u64() && types.contains(&"integer") => "integer",
+            Value::Number(_) => "number",
+            _ => "other",
+        };
+        assert!(types.contains(&type_), "`{}` does not match schema", name);
+
+        let is_optional = types.contains(&"null");
+        assert_eq!(
+            required.contains(&Value::from(name.as_str())),
+            !is_optional,
+            "`{}`",
+            name
+        );
+    }
+}
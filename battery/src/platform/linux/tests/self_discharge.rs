@@ -0,0 +1,81 @@
+use std::fs;
+use std::sync::Arc;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery, set_energy, step};
+use crate::types::clock::FakeClock;
+use crate::units::power::milliwatt;
+use crate::Manager;
+
+const MINUTE: u64 = 60;
+
+#[test]
+fn test_self_discharge_rate() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_energy(&path, "Full", 50_000);
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    // Not enough idle history yet
+    set_energy(&path, "Full", 49_980);
+    step(&manager, &clock, &mut battery, 30 * MINUTE);
+    assert!(battery.self_discharge_rate().is_none());
+
+    // 100 mWh lost in 2 hours
+    set_energy(&path, "Full", 49_900);
+    step(&manager, &clock, &mut battery, 90 * MINUTE);
+    assert_eq!(battery.self_discharge_rate().unwrap().get::<milliwatt>().round(), 50.0);
+
+    // Discharge under load ends the idle period, but keeps the estimate
+    set_energy(&path, "Discharging", 45_000);
+    step(&manager, &clock, &mut battery, 60 * MINUTE);
+    assert_eq!(battery.self_discharge_rate().unwrap().get::<milliwatt>().round(), 50.0);
+}
+
+#[test]
+fn test_self_discharge_rate_top_up() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_energy(&path, "Unknown", 40_000);
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    // Charger had topped the battery up, so the period starts over
+    set_energy(&path, "Unknown", 40_500);
+    step(&manager, &clock, &mut battery, 50 * MINUTE);
+    set_energy(&path, "Unknown", 40_480);
+    step(&manager, &clock, &mut battery, 50 * MINUTE);
+    assert!(battery.self_discharge_rate().is_none());
+
+    set_energy(&path, "Unknown", 40_440);
+    step(&manager, &clock, &mut battery, 10 * MINUTE);
+    assert_eq!(battery.self_discharge_rate().unwrap().get::<milliwatt>().round(), 60.0);
+}
+
+// Unknown state under load is not an idle period
+#[test]
+fn test_self_discharge_rate_unknown_under_load() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    set_energy(&path, "Unknown", 40_000);
+    fs::write(path.join("power_now"), "8000000\n").unwrap();
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    set_energy(&path, "Unknown", 32_000);
+    step(&manager, &clock, &mut battery, 60 * MINUTE);
+    set_energy(&path, "Unknown", 24_000);
+    step(&manager, &clock, &mut battery, 60 * MINUTE);
+    assert!(battery.self_discharge_rate().is_none());
+
+    // Load is gone, so the idle period starts
+    fs::write(path.join("power_now"), "0\n").unwrap();
+    step(&manager, &clock, &mut battery, 10 * MINUTE);
+    set_energy(&path, "Unknown", 23_940);
+    step(&manager, &clock, &mut battery, 60 * MINUTE);
+    assert_eq!(battery.self_discharge_rate().unwrap().get::<milliwatt>().round(), 60.0);
+}
@@ -0,0 +1,95 @@
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use crate::platform::traits::BatteryDevice;
+use crate::units::energy::watt_hour;
+use crate::units::power::watt;
+use crate::units::ratio::percent;
+use crate::Battery;
+
+// Gauges in the middle of a firmware update are reporting "unknown value" markers,
+// which should not leak as an absurd values
+
+#[test]
+fn test_energy_sentinel() {
+    for sentinel in ["65535", "65535000", "4294967295", "4294967295000"].iter() {
+        let root = sysfs_test_suite!(
+            "type" => "Battery",
+            "status" => "Discharging",
+            "energy_now" => sentinel,
+            "energy_full" => 50000000,
+            "energy_full_design" => 50000000,
+            "capacity" => 40,
+            "voltage_now" => 12000000
+        );
+        let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+        // Falling back to the `capacity` value
+        assert_abs_diff_eq!(device.energy().get::<watt_hour>(), 20.0, epsilon = 0.01);
+        assert_abs_diff_eq!(device.state_of_charge().get::<percent>(), 40.0, epsilon = 0.01);
+    }
+}
+
+#[test]
+fn test_rate_sentinel() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 50000000,
+        "energy_full_design" => 50000000,
+        "power_now" => 4294967295000u64,
+        "current_now" => 65535,
+        "voltage_now" => 12000000
+    );
+    let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+    assert_eq!(device.energy_rate().get::<watt>(), 0.0);
+    assert_eq!(device.time_to_empty(), None);
+}
+
+#[test]
+fn test_temperature_sentinel() {
+    for sentinel in ["65535", "4294967295"].iter() {
+        let root = sysfs_test_suite!(
+            "type" => "Battery",
+            "status" => "Discharging",
+            "energy_now" => 25000000,
+            "energy_full" => 50000000,
+            "energy_full_design" => 50000000,
+            "voltage_now" => 12000000,
+            "temp" => sentinel,
+            "cycle_count" => 65535
+        );
+        let device = SysFsDevice::try_from(root.path().to_path_buf()).unwrap();
+
+        assert_eq!(device.temperature(), None);
+        assert_eq!(device.cycle_count(), None);
+    }
+}
+
+#[test]
+fn test_implausible_cycle_count() {
+    for &(cycles, expected) in [
+        (65535, None),
+        (40000, None),
+        (10001, None),
+        (10000, Some(10000)),
+        (512, Some(512)),
+    ]
+    .iter()
+    {
+        let root = sysfs_test_suite!(
+            "type" => "Battery",
+            "status" => "Discharging",
+            "energy_now" => 25000000,
+            "energy_full" => 50000000,
+            "energy_full_design" => 50000000,
+            "voltage_now" => 12000000,
+            "cycle_count" => cycles
+        );
+        let battery = Battery::from(SysFsDevice::try_from(root.path().to_path_buf()).unwrap());
+
+        assert_eq!(battery.cycle_count(), expected);
+    }
+}
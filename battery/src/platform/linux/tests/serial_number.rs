@@ -0,0 +1,42 @@
+use std::fs;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+use crate::Battery;
+
+#[test]
+fn test_textual_serial() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("serial_number"), "S3R1AL\n").unwrap();
+
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    assert_eq!(battery.serial_number(), Some("S3R1AL"));
+    assert_eq!(battery.serial_number_raw(), Some(b"S3R1AL".to_vec()));
+}
+
+#[test]
+fn test_binary_serial() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("serial_number"), b"\x42\xff\xfe\x01\n").unwrap();
+
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    assert_eq!(battery.serial_number(), Some("B\u{FFFD}\u{FFFD}\u{1}"));
+    assert_eq!(battery.serial_number_raw(), Some(vec![0x42, 0xff, 0xfe, 0x01]));
+}
+
+#[test]
+fn test_missing_serial() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::remove_file(path.join("serial_number")).unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_eq!(device.serial_number(), None);
+    assert_eq!(device.serial_number_raw(), None);
+}
@@ -0,0 +1,184 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::units::electric_potential::volt;
+use crate::units::energy::watt_hour;
+use crate::units::power::watt;
+use crate::units::ratio::percent;
+use crate::units::thermodynamic_temperature::degree_celsius;
+use crate::units::time::second;
+use crate::{Battery, BatteryInfo, State, Technology};
+
+/// Snapshot written with the first `BatteryInfo` schema version, should stay loadable.
+const FIXTURE_V1: &str = include_str!("fixtures/battery_info_v1.json");
+
+/// Compares the snapshots, allowing the floating point error in the values.
+fn assert_info_eq(left: &BatteryInfo, right: &BatteryInfo) {
+    assert_eq!(left.id(), right.id());
+    assert_eq!(left.vendor(), right.vendor());
+    assert_eq!(left.model(), right.model());
+    assert_eq!(left.serial_number(), right.serial_number());
+    assert_eq!(left.state(), right.state());
+    assert_eq!(left.technology(), right.technology());
+    assert_eq!(left.cycle_count(), right.cycle_count());
+
+    let pairs = [
+        (
+            left.state_of_charge().get::<percent>(),
+            right.state_of_charge().get::<percent>(),
+        ),
+        (
+            left.state_of_health().get::<percent>(),
+            right.state_of_health().get::<percent>(),
+        ),
+        (left.energy().get::<watt_hour>(), right.energy().get::<watt_hour>()),
+        (
+            left.energy_full().get::<watt_hour>(),
+            right.energy_full().get::<watt_hour>(),
+        ),
+        (
+            left.energy_full_design().get::<watt_hour>(),
+            right.energy_full_design().get::<watt_hour>(),
+        ),
+        (left.energy_rate().get::<watt>(), right.energy_rate().get::<watt>()),
+        (left.voltage().get::<volt>(), right.voltage().get::<volt>()),
+    ];
+    for (left, right) in pairs.iter() {
+        assert_abs_diff_eq!(left, right, epsilon = 0.001);
+    }
+
+    let options = [
+        (
+            left.temperature().map(|value| value.get::<degree_celsius>()),
+            right.temperature().map(|value| value.get::<degree_celsius>()),
+        ),
+        (
+            left.time_to_full().map(|value| value.get::<second>()),
+            right.time_to_full().map(|value| value.get::<second>()),
+        ),
+        (
+            left.time_to_empty().map(|value| value.get::<second>()),
+            right.time_to_empty().map(|value| value.get::<second>()),
+        ),
+    ];
+    for (left, right) in options.iter() {
+        assert_eq!(left.is_some(), right.is_some());
+        if let (Some(left), Some(right)) = (left, right) {
+            assert_abs_diff_eq!(left, right, epsilon = 0.01);
+        }
+    }
+}
+
+#[test]
+fn test_state_round_trip() {
+    let states = [
+        State::Unknown,
+        State::Charging,
+        State::Discharging,
+        State::Empty,
+        State::Full,
+    ];
+
+    for state in states.iter() {
+        let json = serde_json::to_string(state).unwrap();
+        assert_eq!(serde_json::from_str::<State>(&json).unwrap(), *state);
+    }
+
+    assert_eq!(serde_json::to_string(&State::Discharging).unwrap(), "\"discharging\"");
+    assert_eq!(serde_json::from_str::<State>("\"Full\"").unwrap(), State::Full);
+    assert_eq!(serde_json::from_str::<State>("\"pending\"").unwrap(), State::Unknown);
+    assert!(serde_json::from_str::<State>("1").is_err());
+}
+
+#[test]
+fn test_technology_round_trip() {
+    let technologies = [
+        Technology::Unknown,
+        Technology::LithiumIon,
+        Technology::LeadAcid,
+        Technology::LithiumPolymer,
+        Technology::NickelMetalHydride,
+        Technology::NickelCadmium,
+        Technology::NickelZinc,
+        Technology::LithiumIronPhosphate,
+        Technology::RechargeableAlkalineManganese,
+    ];
+
+    for technology in technologies.iter() {
+        let json = serde_json::to_string(technology).unwrap();
+        assert_eq!(serde_json::from_str::<Technology>(&json).unwrap(), *technology);
+    }
+
+    assert_eq!(
+        serde_json::to_string(&Technology::LithiumIon).unwrap(),
+        "\"lithium-ion\""
+    );
+    assert_eq!(
+        serde_json::from_str::<Technology>("\"Li-ion\"").unwrap(),
+        Technology::LithiumIon
+    );
+    assert_eq!(
+        serde_json::from_str::<Technology>("\"graphene\"").unwrap(),
+        Technology::Unknown
+    );
+}
+
+#[test]
+fn test_battery_info_round_trip() {
+    let root = tempfile::tempdir().unwrap();
+    for (name, status, temp) in [("BAT0", "Discharging", Some("315")), ("BAT1", "Charging", None)].iter() {
+        let path = create_supply(root.path(), name, "Battery");
+        fs::write(path.join("status"), status).unwrap();
+        fs::write(path.join("technology"), "Li-poly").unwrap();
+        fs::write(path.join("power_now"), "7500000").unwrap();
+        fs::write(path.join("cycle_count"), "42").unwrap();
+        if let Some(temp) = temp {
+            fs::write(path.join("temp"), temp).unwrap();
+        }
+
+        let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+        let info = BatteryInfo::from(&battery);
+        let json = serde_json::to_string(&info).unwrap();
+
+        assert_info_eq(&serde_json::from_str::<BatteryInfo>(&json).unwrap(), &info);
+    }
+}
+
+#[test]
+fn test_battery_info_field_names() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    let value = serde_json::to_value(BatteryInfo::from(&battery)).unwrap();
+
+    assert_eq!(value["id"], "BAT0");
+    assert_eq!(value["state"], "discharging");
+    assert_eq!(value["energy_wh"], 25.0);
+    assert_eq!(value["energy_full_wh"], 50.0);
+    assert_eq!(value["voltage_v"], 12.0);
+    assert_eq!(value["state_of_charge_percent"], 50.0);
+    assert!(value["temperature_celsius"].is_null());
+}
+
+#[test]
+fn test_battery_info_fixture_v1() {
+    let info = serde_json::from_str::<BatteryInfo>(FIXTURE_V1).unwrap();
+
+    assert_eq!(info.id(), "SMP/5B10W13930/1234");
+    assert_eq!(info.state(), State::Discharging);
+    assert_eq!(info.technology(), Technology::LithiumPolymer);
+    assert_abs_diff_eq!(info.energy().get::<watt_hour>(), 28.5, epsilon = 0.001);
+    assert_abs_diff_eq!(info.voltage().get::<volt>(), 11.4, epsilon = 0.001);
+    assert_eq!(info.cycle_count(), Some(271));
+    assert_eq!(info.temperature(), None);
+    assert_eq!(info.time_to_full(), None);
+    assert_abs_diff_eq!(info.time_to_empty().unwrap().get::<second>(), 10800.0, epsilon = 0.1);
+
+    // Re-serialized snapshot is loaded back the same
+    let json = serde_json::to_string(&info).unwrap();
+    assert_info_eq(&serde_json::from_str::<BatteryInfo>(&json).unwrap(), &info);
+}
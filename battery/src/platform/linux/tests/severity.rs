@@ -0,0 +1,76 @@
+use std::path::Path;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery, set_attribute};
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, Config, Manager, Severity};
+
+// Battery is 50 Wh, so each percent is 0.5 Wh
+fn set_soc(manager: &Manager, path: &Path, battery: &mut Battery, soc: u32) {
+    set_attribute(path, "energy_now", soc * 500_000);
+    manager.refresh(battery).unwrap();
+}
+
+#[test]
+fn test_severity() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+    assert_eq!(battery.severity(), Severity::Normal);
+
+    set_soc(&manager, &path, &mut battery, 19);
+    assert_eq!(battery.severity(), Severity::Low);
+    set_soc(&manager, &path, &mut battery, 4);
+    assert_eq!(battery.severity(), Severity::Critical);
+}
+
+#[test]
+fn test_severity_stable_flapping() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let mut battery = first_battery(&manager);
+
+    // Hovering around the 20% threshold does not change anything
+    for soc in [21, 19, 20, 18, 21].iter() {
+        set_soc(&manager, &path, &mut battery, *soc);
+        assert_eq!(battery.severity_stable(), Severity::Normal, "{}%", soc);
+    }
+    set_soc(&manager, &path, &mut battery, 17);
+    assert_eq!(battery.severity_stable(), Severity::Low);
+
+    for soc in [19, 21, 20].iter() {
+        set_soc(&manager, &path, &mut battery, *soc);
+        assert_eq!(
+            battery.severity(),
+            if *soc < 20 { Severity::Low } else { Severity::Normal }
+        );
+        assert_eq!(battery.severity_stable(), Severity::Low, "{}%", soc);
+    }
+    set_soc(&manager, &path, &mut battery, 22);
+    assert_eq!(battery.severity_stable(), Severity::Normal);
+
+    // Sudden drop skips the intermediate level
+    set_soc(&manager, &path, &mut battery, 2);
+    assert_eq!(battery.severity_stable(), Severity::Critical);
+}
+
+#[test]
+fn test_severity_stable_margin() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::new().severity_hysteresis(Ratio::new::<percent>(5.0));
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(config);
+    let mut battery = first_battery(&manager);
+
+    set_soc(&manager, &path, &mut battery, 16);
+    assert_eq!(battery.severity_stable(), Severity::Normal);
+    set_soc(&manager, &path, &mut battery, 14);
+    assert_eq!(battery.severity_stable(), Severity::Low);
+    set_soc(&manager, &path, &mut battery, 24);
+    assert_eq!(battery.severity_stable(), Severity::Low);
+    set_soc(&manager, &path, &mut battery, 25);
+    assert_eq!(battery.severity_stable(), Severity::Normal);
+}
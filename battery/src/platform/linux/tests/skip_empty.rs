@@ -0,0 +1,49 @@
+use std::fs;
+use std::path::Path;
+
+use super::super::SysFsManager;
+use super::{battery_names, create_supply};
+use crate::{Config, Manager};
+
+fn create_phantom(root: &Path, name: &str) {
+    let path = create_supply(root, name, "Battery");
+    for attr in ["energy_now", "energy_full", "energy_full_design"].iter() {
+        fs::write(path.join(attr), "0\n").unwrap();
+    }
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = tempfile::tempdir().unwrap();
+    create_supply(root.path(), "BAT0", "Battery");
+    create_phantom(root.path(), "BAT1");
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert_eq!(battery_names(manager.batteries().unwrap()), vec!["BAT0", "BAT1"]);
+}
+
+#[test]
+fn test_skip_empty() {
+    let root = tempfile::tempdir().unwrap();
+    create_phantom(root.path(), "BAT0");
+    create_supply(root.path(), "BAT1", "Battery");
+    create_phantom(root.path(), "BAT2");
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().skip_empty(true));
+
+    assert_eq!(battery_names(manager.batteries().unwrap()), vec!["BAT1"]);
+    assert_eq!(manager.full_scan().unwrap().batteries().len(), 1);
+    assert_eq!(manager.snapshot_parallel(2).unwrap().len(), 1);
+}
+
+// Only the batteries with both full and design energy missing are phantom ones
+#[test]
+fn test_partially_empty() {
+    let root = tempfile::tempdir().unwrap();
+    // Drained battery
+    fs::write(create_supply(root.path(), "BAT0", "Battery").join("energy_now"), "0\n").unwrap();
+    // Design capacity is not reported, same as in the #40 issue
+    fs::remove_file(create_supply(root.path(), "BAT1", "Battery").join("energy_full_design")).unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(Config::new().skip_empty(true));
+
+    assert_eq!(battery_names(manager.batteries().unwrap()), vec!["BAT0", "BAT1"]);
+}
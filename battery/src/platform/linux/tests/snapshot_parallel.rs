@@ -0,0 +1,82 @@
+use std::fs;
+use std::time::Instant;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::Manager;
+
+#[test]
+fn test_snapshot_parallel_order() {
+    let root = tempfile::tempdir().unwrap();
+    for idx in 0..20 {
+        create_supply(root.path(), &format!("BAT{}", idx), "Battery");
+    }
+    create_supply(root.path(), "AC", "Mains");
+    fs::write(root.path().join("BAT7").join("present"), "0\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+    let expected = manager
+        .batteries()
+        .unwrap()
+        .map(|battery| battery.unwrap().stable_id())
+        .collect::<Vec<_>>();
+    assert_eq!(expected.len(), 19);
+
+    for threads in [0, 1, 3, 4, 19, 64].iter() {
+        let actual = manager
+            .snapshot_parallel(*threads)
+            .unwrap()
+            .iter()
+            .map(|info| info.id().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(actual, expected, "{} threads", threads);
+    }
+}
+
+#[test]
+fn test_snapshot_parallel_empty() {
+    let root = tempfile::tempdir().unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert!(manager.snapshot_parallel(4).unwrap().is_empty());
+}
+
+#[test]
+fn test_snapshot_parallel_error() {
+    let root = tempfile::tempdir().unwrap();
+    for name in ["BAT0", "BAT1", "BAT2", "BAT3"].iter() {
+        create_supply(root.path(), name, "Battery");
+    }
+    // Malformed value fails the whole battery load
+    fs::write(root.path().join("BAT2").join("energy_now"), "garbage\n").unwrap();
+
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert!(manager.snapshot_parallel(1).is_err());
+    assert!(manager.snapshot_parallel(4).is_err());
+}
+
+/// Compares the sequential and parallel loading on a many-battery fixture, run with
+///
+/// ```text
+/// cargo test --release -p battery bench_snapshot_parallel -- --ignored --nocapture
+/// ```
+#[test]
+#[ignore]
+fn bench_snapshot_parallel() {
+    const ROUNDS: u32 = 10;
+
+    let root = tempfile::tempdir().unwrap();
+    for idx in 0..256 {
+        create_supply(root.path(), &format!("BAT{}", idx), "Battery");
+    }
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    for threads in [1, 2, 4, 8].iter() {
+        let started = Instant::now();
+        for _ in 0..ROUNDS {
+            assert_eq!(manager.snapshot_parallel(*threads).unwrap().len(), 256);
+        }
+        println!("{} threads: {:?} per round", threads, started.elapsed() / ROUNDS);
+    }
+}
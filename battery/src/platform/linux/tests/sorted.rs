@@ -0,0 +1,26 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::units::ratio::percent;
+use crate::Manager;
+
+#[test]
+fn test_batteries_sorted_by() {
+    let root = tempfile::tempdir().unwrap();
+    for (name, energy) in [("BAT0", "40000000"), ("BAT1", "10000000"), ("BAT2", "25000000")].iter() {
+        let path = create_supply(root.path(), name, "Battery");
+        fs::write(path.join("energy_now"), energy).unwrap();
+    }
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let batteries = manager
+        .batteries_sorted_by(|battery| battery.state_of_charge().get::<percent>() as u32)
+        .unwrap();
+    let serials = batteries
+        .iter()
+        .map(|battery| battery.serial_number().unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(serials, vec!["BAT1", "BAT2", "BAT0"]);
+}
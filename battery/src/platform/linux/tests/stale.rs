@@ -0,0 +1,35 @@
+use std::time::Duration;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::{Battery, Config};
+
+#[test]
+fn test_is_stale() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::new().max_data_age(Duration::from_secs(60));
+    let mut battery = Battery::from(SysFsDevice::try_from(path).unwrap()).with_config(&config);
+    let updated = battery.last_updated().unwrap();
+
+    assert!(!battery.is_stale());
+    assert!(!battery.is_stale_at(updated + Duration::from_secs(60)));
+    assert!(battery.is_stale_at(updated + Duration::from_secs(61)));
+
+    // Clock went backwards
+    assert!(!battery.is_stale_at(updated - Duration::from_secs(3600)));
+
+    // Successful refresh makes it fresh again
+    battery.mark_updated(updated + Duration::from_secs(120));
+    assert!(!battery.is_stale_at(updated + Duration::from_secs(150)));
+}
+
+#[test]
+fn test_disabled_by_default() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+    let updated = battery.last_updated().unwrap();
+
+    assert!(!battery.is_stale_at(updated + Duration::from_secs(365 * 24 * 3600)));
+}
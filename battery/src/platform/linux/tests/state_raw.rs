@@ -0,0 +1,48 @@
+use std::borrow::Cow;
+use std::fs;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+use crate::State;
+
+#[test]
+fn test_known_status() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("status"), "Not charging\n").unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_eq!(device.state(), State::Unknown);
+    match device.state_raw() {
+        Some(Cow::Borrowed(raw)) => assert_eq!(raw, "Not charging"),
+        other => panic!("Unexpected raw state: {:?}", other),
+    }
+}
+
+#[test]
+fn test_vendor_status() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let mut device = SysFsDevice::try_from(path.clone()).unwrap();
+    assert_eq!(device.state_raw(), Some(Cow::Borrowed("Discharging")));
+
+    fs::write(path.join("status"), "Idle\n").unwrap();
+    device.refresh().unwrap();
+
+    assert_eq!(device.state(), State::Unknown);
+    assert_eq!(device.state_raw(), Some(Cow::Borrowed("Idle")));
+}
+
+#[test]
+fn test_missing_status() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::remove_file(path.join("status")).unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_eq!(device.state(), State::Unknown);
+    assert_eq!(device.state_raw(), None);
+}
@@ -0,0 +1,52 @@
+use std::path::Path;
+use std::sync::Arc;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery, set_attribute, step};
+use crate::types::clock::FakeClock;
+use crate::{Battery, Manager};
+
+fn report(manager: &Manager, clock: &FakeClock, path: &Path, battery: &mut Battery, status: &str, secs: u64) {
+    set_attribute(path, "status", status);
+    step(manager, clock, battery, secs);
+}
+
+#[test]
+fn test_state_unstable() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    for status in ["Charging", "Discharging", "Charging"].iter() {
+        report(&manager, &clock, &path, &mut battery, status, 30);
+        assert!(!battery.is_state_unstable());
+    }
+    report(&manager, &clock, &path, &mut battery, "Discharging", 30);
+    assert!(battery.is_state_unstable());
+
+    // Flips are forgotten once the state settles
+    report(&manager, &clock, &path, &mut battery, "Discharging", 3 * 60);
+    assert!(battery.is_state_unstable());
+    report(&manager, &clock, &path, &mut battery, "Discharging", 60);
+    assert!(!battery.is_state_unstable());
+}
+
+#[test]
+fn test_state_stable() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let clock = Arc::new(FakeClock::new());
+    let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+    let mut battery = first_battery(&manager);
+
+    // Regular charge session and slow flips are fine
+    for status in ["Charging", "Charging", "Full", "Full", "Discharging"].iter() {
+        report(&manager, &clock, &path, &mut battery, status, 60);
+    }
+    for status in ["Charging", "Discharging", "Charging", "Discharging"].iter() {
+        report(&manager, &clock, &path, &mut battery, status, 2 * 60);
+    }
+    assert!(!battery.is_state_unstable());
+}
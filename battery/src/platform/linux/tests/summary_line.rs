@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{create_supply, load_battery};
+use crate::{Battery, Config, DisplayConfig, TemperatureUnit, TimeFormat};
+
+fn battery(path: &Path, config: &Config) -> Battery {
+    load_battery(path, config)
+}
+
+fn discharging_battery(root: &Path) -> PathBuf {
+    let path = create_supply(root, "BAT0", "Battery");
+    // 25 Wh left at 20 W rate, 1h 15m till empty
+    fs::write(path.join("power_now"), "20000000\n").unwrap();
+    // 30.5 °C
+    fs::write(path.join("temp"), "305\n").unwrap();
+
+    path
+}
+
+#[test]
+fn test_default() {
+    let root = tempfile::tempdir().unwrap();
+    let path = discharging_battery(root.path());
+    let battery = battery(&path, &Config::default());
+
+    let expected = "BAT0: 50%, discharging, empty in 1h 15m, 30.5 °C";
+    assert_eq!(battery.summary_line(), expected);
+    assert_eq!(battery.summary_line_with(&DisplayConfig::default()), expected);
+}
+
+#[test]
+fn test_custom() {
+    let root = tempfile::tempdir().unwrap();
+    let path = discharging_battery(root.path());
+    let display = DisplayConfig::new()
+        .temperature_unit(TemperatureUnit::Fahrenheit)
+        .time_format(TimeFormat::Clock)
+        .show_serial(true);
+    let battery = battery(&path, &Config::new().display(display));
+
+    let expected = "BAT0: 50%, discharging, empty in 1:15, 86.9 °F, serial BAT0";
+    assert_eq!(battery.summary_line(), expected);
+    assert_eq!(
+        battery.summary_line_with(&DisplayConfig::new().time_format(TimeFormat::Minutes)),
+        "BAT0: 50%, discharging, empty in 75 min, 30.5 °C"
+    );
+}
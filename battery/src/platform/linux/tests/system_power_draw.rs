@@ -0,0 +1,65 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::units::power::watt;
+use crate::Manager;
+
+#[test]
+fn test_system_power_draw() {
+    let root = tempfile::tempdir().unwrap();
+    fs::write(
+        create_supply(root.path(), "BAT0", "Battery").join("power_now"),
+        "7000000\n",
+    )
+    .unwrap();
+    fs::write(
+        create_supply(root.path(), "BAT1", "Battery").join("power_now"),
+        "5000000\n",
+    )
+    .unwrap();
+    let ac = create_supply(root.path(), "AC", "Mains");
+    fs::write(ac.join("online"), "0\n").unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let draw = manager.system_power_draw().unwrap().unwrap();
+    assert_eq!(draw.get::<watt>(), 12.0);
+
+    fs::write(ac.join("online"), "1\n").unwrap();
+    assert!(manager.system_power_draw().unwrap().is_none());
+}
+
+// External power status is unknown, but charging battery means it is connected
+#[test]
+fn test_system_power_draw_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let bat0 = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(bat0.join("power_now"), "7000000\n").unwrap();
+    let bat1 = create_supply(root.path(), "BAT1", "Battery");
+    fs::write(bat1.join("power_now"), "5000000\n").unwrap();
+    fs::write(bat1.join("status"), "Charging\n").unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    assert!(manager.system_power_draw().unwrap().is_none());
+
+    fs::write(bat1.join("status"), "Unknown\n").unwrap();
+    let draw = manager.system_power_draw().unwrap().unwrap();
+    assert_eq!(draw.get::<watt>(), 7.0);
+}
+
+// Reported external power status wins over the charging battery, ex. charged by the other one
+#[test]
+fn test_system_power_draw_charging_offline() {
+    let root = tempfile::tempdir().unwrap();
+    let bat0 = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(bat0.join("power_now"), "7000000\n").unwrap();
+    let bat1 = create_supply(root.path(), "BAT1", "Battery");
+    fs::write(bat1.join("power_now"), "5000000\n").unwrap();
+    fs::write(bat1.join("status"), "Charging\n").unwrap();
+    let ac = create_supply(root.path(), "AC", "Mains");
+    fs::write(ac.join("online"), "0\n").unwrap();
+    let manager = Manager::from(SysFsManager::with_root(root.path()));
+
+    let draw = manager.system_power_draw().unwrap().unwrap();
+    assert_eq!(draw.get::<watt>(), 7.0);
+}
@@ -0,0 +1,82 @@
+use std::path::Path;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use super::{create_supply, load_battery, set_attribute};
+use crate::units::ratio::percent;
+use crate::units::time::hour;
+use crate::units::Ratio;
+use crate::{Battery, Config};
+
+fn battery(path: &Path, capacity: u32, config: &Config) -> Battery {
+    set_attribute(path, "status", "Charging");
+    set_attribute(path, "capacity", capacity);
+    // 25 Wh left to charge at 10 W, 2.5 hours with a linear estimation
+    set_attribute(path, "power_now", 10000000);
+
+    load_battery(path, config)
+}
+
+#[test]
+fn test_default_model() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    // 30% in the constant current phase and 20% charged twice slower
+    let below = battery(&path, 50, &Config::default());
+    assert_abs_diff_eq!(below.time_to_full().unwrap().get::<hour>(), 2.5, epsilon = 0.001);
+    assert_abs_diff_eq!(
+        below.time_to_full_tapered().unwrap().get::<hour>(),
+        3.5,
+        epsilon = 0.001
+    );
+
+    // Rate is already partially reduced by the charger, so the correction fades out
+    let above = battery(&path, 85, &Config::default());
+    assert_abs_diff_eq!(
+        above.time_to_full_tapered().unwrap().get::<hour>(),
+        2.5 * 1.75,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_continuous_at_threshold() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+
+    let below = battery(&path, 79, &Config::default()).time_to_full_tapered().unwrap();
+    let at = battery(&path, 80, &Config::default()).time_to_full_tapered().unwrap();
+    let above = battery(&path, 81, &Config::default()).time_to_full_tapered().unwrap();
+
+    assert_abs_diff_eq!(below.get::<hour>(), at.get::<hour>(), epsilon = 0.15);
+    assert_abs_diff_eq!(above.get::<hour>(), at.get::<hour>(), epsilon = 0.15);
+
+    let full = battery(&path, 100, &Config::default());
+    assert_eq!(full.time_to_full_tapered(), full.time_to_full());
+}
+
+#[test]
+fn test_custom_model() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let config = Config::new().charge_taper(Ratio::new::<percent>(60.0), 3.0);
+
+    // 10% in the constant current phase and 40% charged three times slower
+    let battery = battery(&path, 50, &config);
+    assert_abs_diff_eq!(
+        battery.time_to_full_tapered().unwrap().get::<hour>(),
+        6.5,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_not_charging() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    assert_eq!(battery.time_to_full_tapered(), None);
+}
@@ -0,0 +1,40 @@
+use std::fs;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::{Config, Manager, Technology};
+
+#[test]
+fn test_technology_override() {
+    let root = tempfile::tempdir().unwrap();
+    for (name, vendor, model) in [("BAT0", "SMP", "5B10W13930"), ("BAT1", "LGC", "01AV431")].iter() {
+        let path = create_supply(root.path(), name, "Battery");
+        fs::write(path.join("technology"), "Li-ion\n").unwrap();
+        fs::write(path.join("manufacturer"), format!("{}\n", vendor)).unwrap();
+        fs::write(path.join("model_name"), format!("{}\n", model)).unwrap();
+    }
+    let config = Config::new()
+        .technology_override("5B10", Technology::LithiumPolymer)
+        .technology_override("SMP", Technology::LithiumIronPhosphate);
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(config);
+
+    let technologies = manager
+        .batteries()
+        .unwrap()
+        .map(|battery| battery.unwrap().technology())
+        .collect::<Vec<_>>();
+    assert_eq!(technologies, vec![Technology::LithiumPolymer, Technology::LithiumIon]);
+}
+
+#[test]
+fn test_technology_override_case_sensitive() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("technology"), "Li-ion\n").unwrap();
+    fs::write(path.join("manufacturer"), "SMP\n").unwrap();
+    let config = Config::new().technology_override("smp", Technology::LithiumPolymer);
+    let manager = Manager::from(SysFsManager::with_root(root.path())).configured(config);
+
+    let battery = first_battery(&manager);
+    assert_eq!(battery.technology(), Technology::LithiumIon);
+}
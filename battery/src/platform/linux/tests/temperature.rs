@@ -0,0 +1,189 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::sysfs::fs::TemperatureScale;
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::platform::traits::BatteryDevice;
+use crate::units::thermodynamic_temperature::degree_celsius;
+use crate::Battery;
+
+#[test]
+fn test_tenths_of_degree() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "305\n").unwrap();
+    fs::write(path.join("temp_ambient"), "-50\n").unwrap();
+    fs::write(path.join("temp_min"), "0\n").unwrap();
+    fs::write(path.join("temp_max"), "600\n").unwrap();
+    fs::write(path.join("temp_alert_max"), "550\n").unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_abs_diff_eq!(
+        device.temperature().unwrap().get::<degree_celsius>(),
+        30.5,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.ambient_temperature().unwrap().get::<degree_celsius>(),
+        -5.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.temperature_min().unwrap().get::<degree_celsius>(),
+        0.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.temperature_max().unwrap().get::<degree_celsius>(),
+        60.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.temperature_alert_max().unwrap().get::<degree_celsius>(),
+        55.0,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_millidegrees() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "30500\n").unwrap();
+    fs::write(path.join("temp_ambient"), "-5000\n").unwrap();
+    fs::write(path.join("temp_max"), "60000\n").unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_abs_diff_eq!(
+        device.temperature().unwrap().get::<degree_celsius>(),
+        30.5,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.ambient_temperature().unwrap().get::<degree_celsius>(),
+        -5.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.temperature_max().unwrap().get::<degree_celsius>(),
+        60.0,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_scale_conversion() {
+    let decidegrees = TemperatureScale::Decidegrees;
+    let millidegrees = TemperatureScale::Millidegrees;
+
+    assert_abs_diff_eq!(
+        decidegrees.convert(305.0).get::<degree_celsius>(),
+        30.5,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        decidegrees.convert(-50.0).get::<degree_celsius>(),
+        -5.0,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        millidegrees.convert(30500.0).get::<degree_celsius>(),
+        30.5,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        millidegrees.convert(1500.0).get::<degree_celsius>(),
+        1.5,
+        epsilon = 0.001
+    );
+
+    assert_eq!(decidegrees.detect(&[Some(305.0), None, Some(600.0)]), decidegrees);
+    assert_eq!(decidegrees.detect(&[Some(1500.0), None, Some(60000.0)]), millidegrees);
+    assert_eq!(decidegrees.detect(&[Some(-5000.0)]), millidegrees);
+    // Once detected, scale is kept for the small values
+    assert_eq!(millidegrees.detect(&[Some(1500.0), None]), millidegrees);
+    assert_eq!(millidegrees.detect(&[]), millidegrees);
+}
+
+/// Small millidegree values are read in the unit the other device temperatures are reported in.
+#[test]
+fn test_small_millidegrees() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "1500\n").unwrap();
+    fs::write(path.join("temp_min"), "0\n").unwrap();
+    fs::write(path.join("temp_max"), "60000\n").unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert_abs_diff_eq!(
+        device.temperature().unwrap().get::<degree_celsius>(),
+        1.5,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        device.temperature_max().unwrap().get::<degree_celsius>(),
+        60.0,
+        epsilon = 0.001
+    );
+}
+
+/// Scale detected once is kept by the device, even if there is no large value to detect it by anymore.
+#[test]
+fn test_scale_kept_on_refresh() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "30500\n").unwrap();
+
+    let mut device = SysFsDevice::try_from(path.clone()).unwrap();
+    fs::write(path.join("temp"), "1500\n").unwrap();
+    device.refresh().unwrap();
+
+    assert_abs_diff_eq!(
+        device.temperature().unwrap().get::<degree_celsius>(),
+        1.5,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_raw_attributes() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "30500\n").unwrap();
+    fs::write(path.join("temp_ambient"), "-5000\n").unwrap();
+    fs::write(path.join("temp_alert_max"), "55000\n").unwrap();
+    fs::write(path.join("uevent"), "POWER_SUPPLY_NAME=BAT0\n").unwrap();
+    fs::create_dir(path.join("power")).unwrap();
+
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+    let attributes = battery.raw_attributes().unwrap();
+
+    // Values are kept in the driver units
+    assert_eq!(attributes.get("temp").map(String::as_str), Some("30500"));
+    assert_eq!(attributes.get("temp_ambient").map(String::as_str), Some("-5000"));
+    assert_eq!(attributes.get("temp_alert_max").map(String::as_str), Some("55000"));
+    assert_eq!(attributes.get("type").map(String::as_str), Some("Battery"));
+    assert!(!attributes.contains_key("temp_min"));
+    assert!(!attributes.contains_key("uevent"));
+    assert!(!attributes.contains_key("power"));
+}
+
+#[test]
+fn test_missing_limits() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("temp"), "305\n").unwrap();
+
+    let device = SysFsDevice::try_from(path).unwrap();
+
+    assert!(device.temperature().is_some());
+    assert_eq!(device.ambient_temperature(), None);
+    assert_eq!(device.temperature_min(), None);
+    assert_eq!(device.temperature_max(), None);
+    assert_eq!(device.temperature_alert_max(), None);
+}
@@ -0,0 +1,166 @@
+use std::fs;
+use std::path::Path;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::super::SysFsManager;
+use super::{create_supply, first_battery};
+use crate::types::watcher::flatten;
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, Event, Manager, ThresholdEvent, ThresholdWatcher, WatcherHandle};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Scripted battery, which state of charge is set in percents of the 50 Wh `energy_full`.
+struct Script {
+    manager: Manager,
+    battery: Battery,
+    watcher: ThresholdWatcher,
+}
+
+impl Script {
+    fn new(root: &Path, watcher: ThresholdWatcher) -> Script {
+        create_supply(root, "BAT0", "Battery");
+        let manager = Manager::from(SysFsManager::with_root(root));
+        let battery = first_battery(&manager);
+
+        Script {
+            manager,
+            battery,
+            watcher,
+        }
+    }
+
+    fn step(&mut self, status: &str, soc: f32) -> Vec<ThresholdEvent> {
+        let path = Path::new(self.battery.device_path().unwrap()).to_path_buf();
+        fs::write(path.join("status"), status).unwrap();
+        fs::write(path.join("energy_now"), format!("{}\n", (soc * 500_000.0) as u32)).unwrap();
+        self.manager.refresh(&mut self.battery).unwrap();
+
+        self.watcher.update(&self.battery)
+    }
+}
+
+fn level(value: f32) -> Ratio {
+    Ratio::new::<percent>(value)
+}
+
+fn below(value: f32) -> ThresholdEvent {
+    ThresholdEvent::CrossedBelow("BAT0".to_string(), level(value))
+}
+
+fn above(value: f32) -> ThresholdEvent {
+    ThresholdEvent::RecoveredAbove("BAT0".to_string(), level(value))
+}
+
+#[test]
+fn test_oscillation_at_threshold() {
+    let root = tempfile::tempdir().unwrap();
+    let mut script = Script::new(root.path(), ThresholdWatcher::new(&[level(20.0)]));
+
+    assert_eq!(script.step("Discharging", 21.0), vec![]);
+    assert_eq!(script.step("Discharging", 19.9), vec![below(20.0)]);
+    // Load changes are moving the value back and forth around the level
+    assert_eq!(script.step("Discharging", 20.1), vec![]);
+    assert_eq!(script.step("Discharging", 19.9), vec![]);
+    assert_eq!(script.step("Discharging", 21.8), vec![]);
+    assert_eq!(script.step("Discharging", 19.8), vec![]);
+    assert_eq!(script.step("Discharging", 22.0), vec![above(20.0)]);
+    assert_eq!(script.step("Discharging", 19.9), vec![below(20.0)]);
+}
+
+#[test]
+fn test_charge_discharge_transitions() {
+    let root = tempfile::tempdir().unwrap();
+    let mut script = Script::new(root.path(), ThresholdWatcher::new(&[level(20.0)]));
+
+    // Charging battery does not cross the levels
+    assert_eq!(script.step("Charging", 15.0), vec![]);
+    assert_eq!(script.step("Full", 15.0), vec![]);
+    // But the unplugged one does
+    assert_eq!(script.step("Discharging", 15.0), vec![below(20.0)]);
+    assert_eq!(script.step("Charging", 19.0), vec![]);
+    assert_eq!(script.step("Discharging", 18.0), vec![]);
+    assert_eq!(script.step("Charging", 30.0), vec![above(20.0)]);
+    assert_eq!(script.step("Discharging", 25.0), vec![]);
+    assert_eq!(script.step("Discharging", 19.0), vec![below(20.0)]);
+}
+
+#[test]
+fn test_multiple_levels() {
+    let root = tempfile::tempdir().unwrap();
+    let watcher = ThresholdWatcher::new(&[level(5.0), level(20.0), level(5.0)]);
+    assert_eq!(watcher.levels(), &[level(5.0), level(20.0)]);
+    let mut script = Script::new(root.path(), watcher);
+
+    assert_eq!(script.step("Discharging", 50.0), vec![]);
+    assert_eq!(script.step("Discharging", 3.0), vec![below(20.0), below(5.0)]);
+    assert_eq!(script.step("Charging", 10.0), vec![above(5.0)]);
+    assert_eq!(script.step("Discharging", 4.0), vec![below(5.0)]);
+    assert_eq!(script.step("Charging", 60.0), vec![above(5.0), above(20.0)]);
+}
+
+#[test]
+fn test_custom_hysteresis() {
+    let root = tempfile::tempdir().unwrap();
+    let watcher = ThresholdWatcher::new(&[level(20.0)]).hysteresis(level(5.0));
+    let mut script = Script::new(root.path(), watcher);
+
+    assert_eq!(script.step("Discharging", 19.0), vec![below(20.0)]);
+    assert_eq!(script.step("Charging", 24.0), vec![]);
+    assert_eq!(script.step("Charging", 25.0), vec![above(20.0)]);
+}
+
+#[test]
+#[should_panic]
+fn test_negative_hysteresis() {
+    let _ = ThresholdWatcher::new(&[level(20.0)]).hysteresis(level(-2.0));
+}
+
+#[test]
+fn test_watcher_events() {
+    let root = tempfile::tempdir().unwrap();
+    let mut script = Script::new(root.path(), ThresholdWatcher::new(&[level(20.0)]));
+    script.step("Discharging", 10.0);
+    let battery = first_battery(&script.manager);
+    let mut watcher = ThresholdWatcher::new(&[level(20.0)]);
+
+    assert_eq!(watcher.handle(&Event::Added(battery)), vec![below(20.0)]);
+    let battery = first_battery(&script.manager);
+    assert_eq!(watcher.handle(&Event::Changed(battery)), vec![]);
+
+    // Battery is forgotten after removal
+    assert_eq!(watcher.handle(&Event::Removed("BAT0".to_string())), vec![]);
+    let battery = first_battery(&script.manager);
+    assert_eq!(watcher.handle(&Event::Added(battery)), vec![below(20.0)]);
+}
+
+#[test]
+fn test_spawned_watcher() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager_root = root.path().to_path_buf();
+    let mut watcher = ThresholdWatcher::new(&[level(20.0)]);
+    let (tx, rx) = mpsc::channel();
+    let handle = WatcherHandle::spawn_with(
+        move || Ok(Manager::from(SysFsManager::with_root(manager_root))),
+        Duration::from_millis(10),
+        tx,
+        move |manager| flatten(watcher.poll(manager)),
+    )
+    .unwrap();
+
+    fs::write(path.join("energy_now"), "5000000\n").unwrap();
+    assert_eq!(rx.recv_timeout(TIMEOUT).unwrap().unwrap(), below(20.0));
+
+    fs::write(path.join("status"), "Charging\n").unwrap();
+    fs::write(path.join("energy_now"), "40000000\n").unwrap();
+    assert_eq!(rx.recv_timeout(TIMEOUT).unwrap().unwrap(), above(20.0));
+
+    drop(handle);
+    if let Ok(event) = rx.try_recv() {
+        panic!("Unexpected event: {:?}", event);
+    }
+    assert_eq!(rx.try_recv().unwrap_err(), mpsc::TryRecvError::Disconnected);
+}
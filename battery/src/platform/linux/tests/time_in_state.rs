@@ -0,0 +1,38 @@
+use std::fs;
+use std::time::{Duration, Instant};
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::{Battery, State};
+
+#[test]
+fn test_time_in_state() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let mut battery: Battery = SysFsDevice::try_from(path.clone()).unwrap().into();
+    let created = Instant::now();
+
+    // Same state, counter keeps going
+    let previous = battery.state();
+    battery.refresh().unwrap();
+    battery.track_state(previous, created + Duration::from_secs(60));
+    assert!(battery.time_in_state_at(created + Duration::from_secs(120)).unwrap() >= Duration::from_secs(120));
+
+    // State changed, counter is reset
+    fs::write(path.join("status"), "Charging\n").unwrap();
+    let previous = battery.state();
+    battery.refresh().unwrap();
+    battery.track_state(previous, created + Duration::from_secs(180));
+    assert_eq!(battery.state(), State::Charging);
+    assert_eq!(
+        battery.time_in_state_at(created + Duration::from_secs(240)),
+        Some(Duration::from_secs(60))
+    );
+
+    // Unknown state is not tracked
+    fs::write(path.join("status"), "Unknown\n").unwrap();
+    let previous = battery.state();
+    battery.refresh().unwrap();
+    battery.track_state(previous, created + Duration::from_secs(300));
+    assert_eq!(battery.time_in_state_at(created + Duration::from_secs(360)), None);
+}
@@ -0,0 +1,54 @@
+use super::super::SysFsDevice;
+use crate::{Anomaly, Battery};
+
+#[test]
+fn test_consistent_battery() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "energy_now" => 25000000,
+        "energy_full" => 45000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 12000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert!(battery.validate().is_empty());
+}
+
+#[test]
+fn test_inconsistent_battery() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Charging",
+        "energy_now" => 56000000,
+        "energy_full" => 55000000,
+        "energy_full_design" => 50000000,
+        "voltage_now" => 120000000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(
+        battery.validate(),
+        vec![
+            Anomaly::EnergyExceedsFull,
+            Anomaly::HealthAboveDesign,
+            Anomaly::ImplausibleVoltage,
+            Anomaly::ChargingButFull,
+        ]
+    );
+}
+
+// Same as in the #40 issue, both `energy_full_design` and `charge_full_design` are missing
+#[test]
+fn test_zero_design_capacity() {
+    let root = sysfs_test_suite!(
+        "type" => "Battery",
+        "status" => "Discharging",
+        "capacity" => 83,
+        "voltage_now" => 11829000
+    );
+    let battery: Battery = SysFsDevice::try_from(root.path().to_path_buf()).unwrap().into();
+
+    assert_eq!(battery.validate(), vec![Anomaly::ZeroDesignCapacity]);
+}
@@ -0,0 +1,42 @@
+use std::fs;
+
+use approx::assert_abs_diff_eq;
+
+use super::super::SysFsDevice;
+use super::create_supply;
+use crate::units::electric_potential::volt;
+use crate::Battery;
+
+#[test]
+fn test_design_voltage_range() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    fs::write(path.join("voltage_min_design"), "11100000\n").unwrap();
+    fs::write(path.join("voltage_max_design"), "12600000\n").unwrap();
+
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    assert_abs_diff_eq!(
+        battery.voltage_min_design().unwrap().get::<volt>(),
+        11.1,
+        epsilon = 0.001
+    );
+    assert_abs_diff_eq!(
+        battery.voltage_max_design().unwrap().get::<volt>(),
+        12.6,
+        epsilon = 0.001
+    );
+}
+
+#[test]
+fn test_missing_design_voltage() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    // Some drivers are reporting zero instead of omitting the attribute
+    fs::write(path.join("voltage_max_design"), "0\n").unwrap();
+
+    let battery = Battery::from(SysFsDevice::try_from(path).unwrap());
+
+    assert_eq!(battery.voltage_min_design(), None);
+    assert_eq!(battery.voltage_max_design(), None);
+}
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use super::super::SysFsManager;
+use super::create_supply;
+use crate::{Config, Event, Manager, Result, WatcherHandle};
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+fn spawn(root: PathBuf) -> (WatcherHandle, mpsc::Receiver<Result<Event>>) {
+    let (tx, rx) = mpsc::channel();
+    let handle = WatcherHandle::spawn(
+        move || Ok(Manager::from(SysFsManager::with_root(root))),
+        Duration::from_millis(10),
+        tx,
+    )
+    .unwrap();
+
+    (handle, rx)
+}
+
+#[test]
+fn test_watcher_events() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let (handle, rx) = spawn(root.path().to_path_buf());
+
+    match rx.recv_timeout(TIMEOUT).unwrap().unwrap() {
+        Event::Added(battery) => assert_eq!(battery.name(), Some("BAT0")),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    fs::write(path.join("energy_now"), "10000000\n").unwrap();
+    match rx.recv_timeout(TIMEOUT).unwrap().unwrap() {
+        Event::Changed(battery) => assert_eq!(battery.stable_id(), "BAT0"),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    fs::remove_dir_all(&path).unwrap();
+    match rx.recv_timeout(TIMEOUT).unwrap().unwrap() {
+        Event::Removed(id) => assert_eq!(id, "BAT0"),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    drop(handle);
+    // Thread is joined already, so the sender is gone too
+    if let Ok(event) = rx.try_recv() {
+        panic!("Unexpected event: {:?}", event);
+    }
+    assert_eq!(rx.try_recv().unwrap_err(), mpsc::TryRecvError::Disconnected);
+}
+
+#[test]
+fn test_watcher_stale_event() {
+    let root = tempfile::tempdir().unwrap();
+    let path = create_supply(root.path(), "BAT0", "Battery");
+    let manager_root = root.path().to_path_buf();
+    let (tx, rx) = mpsc::channel();
+    let _handle = WatcherHandle::spawn(
+        move || {
+            let config = Config::new().max_data_age(Duration::from_millis(50));
+            Ok(Manager::from(SysFsManager::with_root(manager_root)).configured(config))
+        },
+        Duration::from_millis(10),
+        tx,
+    )
+    .unwrap();
+
+    match rx.recv_timeout(TIMEOUT).unwrap().unwrap() {
+        Event::Added(battery) => assert_eq!(battery.name(), Some("BAT0")),
+        other => panic!("Unexpected event: {:?}", other),
+    }
+
+    // Battery can't be loaded anymore, but it is not gone either
+    fs::remove_file(path.join("energy_now")).unwrap();
+    fs::create_dir(path.join("energy_now")).unwrap();
+    loop {
+        match rx.recv_timeout(TIMEOUT).unwrap() {
+            Err(_) => continue,
+            Ok(Event::Stale(id)) => break assert_eq!(id, "BAT0"),
+            Ok(other) => panic!("Unexpected event: {:?}", other),
+        }
+    }
+
+    fs::remove_dir(path.join("energy_now")).unwrap();
+    fs::write(path.join("energy_now"), "25000000\n").unwrap();
+    loop {
+        match rx.recv_timeout(TIMEOUT).unwrap() {
+            Err(_) => continue,
+            Ok(Event::Changed(battery)) => break assert!(!battery.is_stale()),
+            Ok(other) => panic!("Unexpected event: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_watcher_start_failure() {
+    let (tx, _rx) = mpsc::channel();
+    let result = WatcherHandle::spawn(
+        || Err(io::Error::from(io::ErrorKind::PermissionDenied).into()),
+        Duration::from_millis(10),
+        tx,
+    );
+
+    assert!(result.is_err());
+}
@@ -6,16 +6,33 @@
 //    - NotChargingReason (?)
 
 use num_traits::identities::Zero;
+use std::borrow::Cow;
 use std::boxed::Box;
 use std::fmt;
 use std::str;
 
 use super::traits::DataSource;
 use crate::platform::traits::BatteryDevice;
-use crate::types::{State, Technology};
-use crate::units::{ElectricPotential, Energy, Power, ThermodynamicTemperature, Time};
+use crate::types::{Kind, RateKind, State, Technology};
+use crate::units::{ElectricCharge, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
 use crate::Result;
 
+/// All the combinations of the power source flags, which are used to determine the battery state,
+/// indexed by `ExternalConnected | IsCharging << 1 | FullyCharged << 2` value.
+static SOURCE_STATES: [&str; 8] = [
+    "0",
+    "ExternalConnected",
+    "IsCharging",
+    "ExternalConnected|IsCharging",
+    "FullyCharged",
+    "ExternalConnected|FullyCharged",
+    "IsCharging|FullyCharged",
+    "ExternalConnected|IsCharging|FullyCharged",
+];
+
+/// kIOPMPermanentFailureKey, one of the `ErrorCondition` values
+static PERMANENT_FAILURE_CONDITION: &str = "Permanent Battery Failure";
+
 pub struct IoKitDevice {
     source: Box<dyn DataSource>,
 }
@@ -43,10 +60,28 @@ impl BatteryDevice for IoKitDevice {
         self.source.design_capacity() * self.source.voltage()
     }
 
+    fn charge(&self) -> Option<ElectricCharge> {
+        Some(self.source.current_capacity())
+    }
+
+    fn charge_full(&self) -> Option<ElectricCharge> {
+        Some(self.source.max_capacity())
+    }
+
+    fn reports_energy(&self) -> bool {
+        false
+    }
+
     fn energy_rate(&self) -> Power {
         self.source.amperage() * self.source.voltage()
     }
 
+    // `Amperage` is the Smart Battery `AverageCurrent()` value, which is a rolling one-minute average;
+    // instant reading is available as an `InstantAmperage` key
+    fn rate_kind(&self) -> Option<RateKind> {
+        Some(RateKind::Averaged)
+    }
+
     fn state(&self) -> State {
         match () {
             _ if !self.source.external_connected() => State::Discharging,
@@ -57,6 +92,35 @@ impl BatteryDevice for IoKitDevice {
         }
     }
 
+    fn state_raw(&self) -> Option<Cow<'_, str>> {
+        let idx = self.source.external_connected() as usize
+            | (self.source.is_charging() as usize) << 1
+            | (self.source.fully_charged() as usize) << 2;
+
+        Some(Cow::Borrowed(SOURCE_STATES[idx]))
+    }
+
+    fn external_power(&self) -> Option<bool> {
+        Some(self.source.external_connected())
+    }
+
+    fn is_active_source(&self) -> Option<bool> {
+        self.source.is_active_source()
+    }
+
+    fn measurement_error(&self) -> Option<Ratio> {
+        self.source.max_error()
+    }
+
+    fn is_failed(&self) -> bool {
+        self.source.permanent_failure_status().unwrap_or(0) != 0
+            || self.source.error_condition() == Some(PERMANENT_FAILURE_CONDITION)
+    }
+
+    fn failure_code(&self) -> Option<u32> {
+        self.source.permanent_failure_status()
+    }
+
     fn voltage(&self) -> ElectricPotential {
         self.source.voltage()
     }
@@ -65,6 +129,10 @@ impl BatteryDevice for IoKitDevice {
         self.source.temperature()
     }
 
+    fn name(&self) -> Option<&str> {
+        self.source.name()
+    }
+
     fn vendor(&self) -> Option<&str> {
         self.source.manufacturer()
     }
@@ -77,14 +145,31 @@ impl BatteryDevice for IoKitDevice {
         self.source.serial_number()
     }
 
+    fn manufacturer_data(&self) -> Option<&[u8]> {
+        self.source.manufacturer_data()
+    }
+
     fn technology(&self) -> Technology {
         Technology::Unknown
     }
 
+    fn kind(&self) -> Kind {
+        // `IOPMPowerSource` services are system batteries only
+        Kind::System
+    }
+
     fn cycle_count(&self) -> Option<u32> {
         self.source.cycle_count()
     }
 
+    fn charge_stop_threshold(&self) -> Option<Ratio> {
+        self.source.charge_limit()
+    }
+
+    fn reports_time_estimates(&self) -> bool {
+        true
+    }
+
     fn time_to_full(&self) -> Option<Time> {
         if self.state() == State::Charging {
             self.source.time_remaining()
@@ -100,6 +185,10 @@ impl BatteryDevice for IoKitDevice {
             None
         }
     }
+
+    fn reported_time_to_empty(&self) -> Option<Time> {
+        self.time_to_empty()
+    }
 }
 
 impl<T> From<T> for IoKitDevice
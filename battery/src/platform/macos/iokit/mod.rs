@@ -1,8 +1,10 @@
 #[macro_use]
 mod errors;
 mod power_source;
+mod smc;
 mod sys;
 mod wrappers;
 
 pub use self::power_source::PowerSource;
+pub use self::smc::Connection as SmcConnection;
 pub use self::wrappers::*;
@@ -5,13 +5,14 @@ use std::i32;
 
 use core_foundation::base::{CFType, TCFType};
 use core_foundation::boolean::{CFBoolean, CFBooleanGetTypeID};
+use core_foundation::data::{CFData, CFDataGetTypeID};
 use core_foundation::dictionary::CFDictionary;
 use core_foundation::number::{CFNumber, CFNumberGetTypeID};
 use core_foundation::string::{CFString, CFStringGetTypeID};
 
 use super::super::traits::DataSource;
-use super::IoObject;
-use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, ThermodynamicTemperature, Time};
+use super::{providing_power_source_type, IoObject, SmcConnection};
+use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, Ratio, ThermodynamicTemperature, Time};
 use crate::{Error, Result};
 
 type Properties = CFDictionary<CFString, CFType>;
@@ -27,9 +28,19 @@ static CURRENT_CAPACITY_KEY: &'static str = "CurrentCapacity";
 static TEMPERATURE_KEY: &'static str = "Temperature";
 static CYCLE_COUNT_KEY: &'static str = "CycleCount";
 static TIME_REMAINING_KEY: &'static str = "TimeRemaining";
+static MAX_ERR_KEY: &'static str = "MaxErr";
+static PERMANENT_FAILURE_STATUS_KEY: &'static str = "PermanentFailureStatus";
+static ERROR_CONDITION_KEY: &'static str = "ErrorCondition";
 static MANUFACTURER_KEY: &'static str = "Manufacturer";
 static DEVICE_NAME_KEY: &'static str = "DeviceName";
 static BATTERY_SERIAL_NUMBER_KEY: &'static str = "BatterySerialNumber";
+static MANUFACTURER_DATA_KEY: &'static str = "ManufacturerData";
+
+/// Smart Battery "unknown value" marker, which gauge might report during the firmware update
+const SBS_UNKNOWN: u32 = 0xFFFF;
+
+/// kIOPMBatteryPowerKey, one of the `IOPSGetProvidingPowerSourceType` values
+static BATTERY_POWER_VALUE: &'static str = "Battery Power";
 
 #[derive(Debug)]
 pub struct InstantData {
@@ -44,6 +55,9 @@ pub struct InstantData {
     temperature: Option<ThermodynamicTemperature>,
     cycle_count: Option<u32>,
     time_remaining: Option<Time>,
+    max_error: Option<Ratio>,
+    permanent_failure_status: Option<u32>,
+    error_condition: Option<String>,
 }
 
 impl InstantData {
@@ -52,18 +66,27 @@ impl InstantData {
             fully_charged: Self::get_bool(&props, FULLY_CHARGED_KEY)?,
             external_connected: Self::get_bool(&props, EXTERNAL_CONNECTED_KEY)?,
             is_charging: Self::get_bool(&props, IS_CHARGING_KEY)?,
-            voltage: millivolt!(Self::get_u32(&props, VOLTAGE_KEY)?),
+            voltage: millivolt!(Self::get_measurement(&props, VOLTAGE_KEY)?),
             amperage: milliampere!(Self::get_i32(&props, AMPERAGE_KEY)?.abs()),
-            design_capacity: milliampere_hour!(Self::get_u32(&props, DESIGN_CAPACITY_KEY)?),
-            max_capacity: milliampere_hour!(Self::get_u32(&props, MAX_CAPACITY_KEY)?),
-            current_capacity: milliampere_hour!(Self::get_u32(&props, CURRENT_CAPACITY_KEY)?),
+            design_capacity: milliampere_hour!(Self::get_measurement(&props, DESIGN_CAPACITY_KEY)?),
+            max_capacity: milliampere_hour!(Self::get_measurement(&props, MAX_CAPACITY_KEY)?),
+            current_capacity: milliampere_hour!(Self::get_measurement(&props, CURRENT_CAPACITY_KEY)?),
             temperature: Self::get_i32(&props, TEMPERATURE_KEY)
-                .map(|value| celsius!(value as f32 / 100.0))
-                .ok(),
-            cycle_count: Self::get_u32(&props, CYCLE_COUNT_KEY).ok(),
+                .ok()
+                .filter(|value| *value != SBS_UNKNOWN as i32)
+                .map(|value| celsius!(value as f32 / 100.0)),
+            cycle_count: Self::get_u32(&props, CYCLE_COUNT_KEY)
+                .ok()
+                .filter(|value| *value != SBS_UNKNOWN),
+            max_error: Self::get_u32(&props, MAX_ERR_KEY)
+                .ok()
+                .filter(|value| *value != SBS_UNKNOWN)
+                .map(|value| percent!(value)),
             time_remaining: Self::get_i32(&props, TIME_REMAINING_KEY)
                 .ok()
-                .and_then(|val| if val == i32::MAX { None } else { Some(minute!(val)) }),
+                .and_then(|val| if val == i32::MAX || val == SBS_UNKNOWN as i32 { None } else { Some(minute!(val)) }),
+            permanent_failure_status: Self::get_u32(&props, PERMANENT_FAILURE_STATUS_KEY).ok(),
+            error_condition: Self::get_string(&props, ERROR_CONDITION_KEY).ok(),
         })
     }
 
@@ -102,6 +125,17 @@ impl InstantData {
             .ok_or_else(|| Error::not_found(raw_key))
     }
 
+    /// Same as `get_u32`, but fails if gauge does not know the value at the moment.
+    fn get_measurement(props: &Properties, raw_key: &'static str) -> Result<u32> {
+        match Self::get_u32(props, raw_key)? {
+            SBS_UNKNOWN => {
+                trace_warn!(key = raw_key, "unknown value marker for the required value");
+                Err(Error::invalid_data(raw_key))
+            }
+            value => Ok(value),
+        }
+    }
+
     fn get_i32(props: &Properties, raw_key: &'static str) -> Result<i32> {
         let key = CFString::from_static_string(raw_key);
 
@@ -133,39 +167,78 @@ impl InstantData {
             .map(|cf_string| cf_string.to_string())
             .ok_or_else(|| Error::not_found(raw_key))
     }
+
+    fn get_data(props: &Properties, raw_key: &'static str) -> Result<Vec<u8>> {
+        let key = CFString::from_static_string(raw_key);
+
+        props
+            .find(&key)
+            .and_then(|value_ref| {
+                unsafe {
+                    debug_assert!(value_ref.type_of() == CFDataGetTypeID());
+                }
+
+                value_ref.downcast::<CFData>()
+            })
+            .map(|cf_data| cf_data.bytes().to_vec())
+            .ok_or_else(|| Error::not_found(raw_key))
+    }
 }
 
 pub struct PowerSource {
     object: IoObject,
     data: InstantData,
+    is_active_source: Option<bool>,
+    // Opened once, since the SMC connection is not tied to the battery and is not going away
+    smc: Option<SmcConnection>,
+    charge_limit: Option<Ratio>,
 
+    name: Option<String>,
     manufacturer: Option<String>,
     device_name: Option<String>,
     serial_number: Option<String>,
+    manufacturer_data: Option<Vec<u8>>,
 }
 
 impl PowerSource {
     pub fn try_from(io_obj: IoObject) -> Result<PowerSource> {
         let props = io_obj.properties()?;
         let data = InstantData::try_from(&props)?;
+        let name = io_obj.name().ok();
         let manufacturer = InstantData::get_string(&props, MANUFACTURER_KEY).ok();
         let device_name = InstantData::get_string(&props, DEVICE_NAME_KEY).ok();
         let serial_number = InstantData::get_string(&props, BATTERY_SERIAL_NUMBER_KEY).ok();
+        let manufacturer_data = InstantData::get_data(&props, MANUFACTURER_DATA_KEY).ok();
+        let smc = SmcConnection::open();
+        let charge_limit = smc.as_ref().and_then(SmcConnection::charge_limit);
 
         Ok(PowerSource {
             object: io_obj,
             data,
+            is_active_source: Self::is_battery_powered(),
+            smc,
+            charge_limit,
+            name,
             manufacturer,
             device_name,
             serial_number,
+            manufacturer_data,
         })
     }
 }
 
+impl PowerSource {
+    fn is_battery_powered() -> Option<bool> {
+        providing_power_source_type().map(|source_type| source_type == BATTERY_POWER_VALUE)
+    }
+}
+
 impl DataSource for PowerSource {
     fn refresh(&mut self) -> Result<()> {
         let props = self.object.properties()?;
         self.data = InstantData::try_from(&props)?;
+        self.is_active_source = Self::is_battery_powered();
+        self.charge_limit = self.smc.as_ref().and_then(SmcConnection::charge_limit);
 
         Ok(())
     }
@@ -214,6 +287,30 @@ impl DataSource for PowerSource {
         self.data.time_remaining
     }
 
+    fn max_error(&self) -> Option<Ratio> {
+        self.data.max_error
+    }
+
+    fn permanent_failure_status(&self) -> Option<u32> {
+        self.data.permanent_failure_status
+    }
+
+    fn error_condition(&self) -> Option<&str> {
+        self.data.error_condition.as_ref().map(AsRef::as_ref)
+    }
+
+    fn is_active_source(&self) -> Option<bool> {
+        self.is_active_source
+    }
+
+    fn charge_limit(&self) -> Option<Ratio> {
+        self.charge_limit
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.name.as_ref().map(AsRef::as_ref)
+    }
+
     fn manufacturer(&self) -> Option<&str> {
         self.manufacturer.as_ref().map(AsRef::as_ref)
     }
@@ -225,6 +322,10 @@ impl DataSource for PowerSource {
     fn serial_number(&self) -> Option<&str> {
         self.serial_number.as_ref().map(AsRef::as_ref)
     }
+
+    fn manufacturer_data(&self) -> Option<&[u8]> {
+        self.manufacturer_data.as_ref().map(AsRef::as_ref)
+    }
 }
 
 impl fmt::Debug for PowerSource {
@@ -0,0 +1,165 @@
+// System Management Controller access via the `AppleSMC` user client.
+// Structures layout and commands are the same as used by the `smcFanControl` project,
+// see https://github.com/hholtmann/smcFanControl/blob/master/smc-command/smc.h
+
+use std::mem;
+
+use libc::{c_char, c_void};
+use mach::{kern_return, traps};
+
+use super::sys;
+use crate::units::Ratio;
+
+const SMC_SERVICE_NAME: *const c_char = b"AppleSMC\0".as_ptr() as *const c_char;
+
+const KERNEL_INDEX_SMC: u32 = 2;
+const SMC_CMD_READ_BYTES: u8 = 5;
+const SMC_CMD_READ_KEYINFO: u8 = 9;
+
+/// Battery charge level maximum, in percents (Intel Macs).
+const BCLM_KEY: &[u8; 4] = b"BCLM";
+/// Charge limit to 80% flag, which is the only limit Apple Silicon firmware exposes.
+const CHWA_KEY: &[u8; 4] = b"CHWA";
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct KeyDataVersion {
+    major: u8,
+    minor: u8,
+    build: u8,
+    reserved: u8,
+    release: u16,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct KeyDataLimit {
+    version: u16,
+    length: u16,
+    cpu_limit: u32,
+    gpu_limit: u32,
+    mem_limit: u32,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct KeyInfo {
+    data_size: u32,
+    data_type: u32,
+    data_attributes: u8,
+}
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+struct KeyData {
+    key: u32,
+    version: KeyDataVersion,
+    limit: KeyDataLimit,
+    key_info: KeyInfo,
+    result: u8,
+    status: u8,
+    data8: u8,
+    data32: u32,
+    bytes: [u8; 32],
+}
+
+/// Opened `AppleSMC` user client connection.
+///
+/// Connection is opened once per battery and closed on drop, so the refresh costs
+/// a few `IOConnectCallStructMethod` calls only.
+#[derive(Debug)]
+pub struct Connection(sys::io_connect_t);
+
+impl Connection {
+    /// Returns `None` if SMC is not accessible.
+    pub fn open() -> Option<Connection> {
+        unsafe {
+            // Matching dictionary is consumed by the `IOServiceGetMatchingService`
+            let service =
+                sys::IOServiceGetMatchingService(sys::kIOMasterPortDefault, sys::IOServiceMatching(SMC_SERVICE_NAME));
+            if service == 0 {
+                return None;
+            }
+
+            let mut connection = 0;
+            let result = sys::IOServiceOpen(service, traps::mach_task_self(), 0, &mut connection);
+            sys::IOObjectRelease(service);
+
+            if result == kern_return::KERN_SUCCESS {
+                Some(Connection(connection))
+            } else {
+                None
+            }
+        }
+    }
+
+    fn call(&self, input: &KeyData) -> Option<KeyData> {
+        let mut output = KeyData::default();
+        let mut output_size = mem::size_of::<KeyData>();
+        let result = unsafe {
+            sys::IOConnectCallStructMethod(
+                self.0,
+                KERNEL_INDEX_SMC,
+                input as *const KeyData as *const c_void,
+                mem::size_of::<KeyData>(),
+                &mut output as *mut KeyData as *mut c_void,
+                &mut output_size,
+            )
+        };
+
+        // Non-zero `result` means that SMC does not know the key
+        if result == kern_return::KERN_SUCCESS && output.result == 0 {
+            Some(output)
+        } else {
+            None
+        }
+    }
+
+    fn read(&self, key: &[u8; 4]) -> Option<Vec<u8>> {
+        let key = u32::from_be_bytes(*key);
+        let info = self.call(&KeyData {
+            key,
+            data8: SMC_CMD_READ_KEYINFO,
+            ..Default::default()
+        })?;
+        let data = self.call(&KeyData {
+            key,
+            key_info: info.key_info,
+            data8: SMC_CMD_READ_BYTES,
+            ..Default::default()
+        })?;
+
+        let size = (info.key_info.data_size as usize).min(data.bytes.len());
+        let value = data.bytes[..size].to_vec();
+        trace_debug!(key = %String::from_utf8_lossy(&key.to_be_bytes()), ?value);
+
+        Some(value)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        unsafe {
+            sys::IOServiceClose(self.0);
+        }
+    }
+}
+
+impl Connection {
+    /// Reads the charge limit, returns `None` if the limit is not set.
+    ///
+    /// Keys are undocumented, so any unexpected value is treated as a missing limit.
+    pub fn charge_limit(&self) -> Option<Ratio> {
+        match self.read(BCLM_KEY) {
+            Some(ref value) if value.len() == 1 && value[0] > 0 && value[0] <= 100 => {
+                return Some(percent!(value[0]));
+            }
+            _ => {}
+        }
+
+        match self.read(CHWA_KEY) {
+            Some(ref value) if value.first() == Some(&1) => Some(percent!(80)),
+            _ => None,
+        }
+    }
+}
@@ -3,20 +3,28 @@
 // Functions here are copied from the `IOKit-sys` (https://crates.io/crates/iokit-sys) crate
 // and rewritten to use `core_foundation` types.
 
-use core_foundation::base::{mach_port_t, CFAllocatorRef};
+use core_foundation::base::{mach_port_t, CFAllocatorRef, CFTypeRef};
 use core_foundation::dictionary::{CFDictionaryRef, CFMutableDictionaryRef};
-use libc::c_char;
+use core_foundation::string::CFStringRef;
+use libc::{c_char, c_void, size_t};
 use mach::{boolean, kern_return};
 
 pub type io_object_t = mach_port_t;
 pub type io_registry_entry_t = io_object_t;
 pub type io_service_t = io_object_t;
 pub type io_iterator_t = io_object_t;
+pub type io_connect_t = io_object_t;
 
 pub type IOOptionBits = u32;
 
+pub type io_name_t = [c_char; 128];
+pub type io_string_t = [c_char; 512];
+
 pub const IOPM_SERVICE_NAME: *const c_char = b"IOPMPowerSource\0".as_ptr() as *const c_char;
 
+/// kIOServicePlane
+pub const IO_SERVICE_PLANE: *const c_char = b"IOService\0".as_ptr() as *const c_char;
+
 extern "C" {
     // https://developer.apple.com/documentation/iokit/kiomasterportdefault
     pub static kIOMasterPortDefault: mach_port_t;
@@ -48,11 +56,61 @@ extern "C" {
         options: IOOptionBits,
     ) -> kern_return::kern_return_t;
 
+    // https://developer.apple.com/documentation/iokit/1514719-ioregistryentrygetregistryentryid
+    pub fn IORegistryEntryGetRegistryEntryID(
+        entry: io_registry_entry_t,
+        entryID: *mut u64,
+    ) -> kern_return::kern_return_t;
+
+    // https://developer.apple.com/documentation/iokit/1514323-ioregistryentrygetname
+    pub fn IORegistryEntryGetName(entry: io_registry_entry_t, name: *mut c_char) -> kern_return::kern_return_t;
+
+    // https://developer.apple.com/documentation/iokit/1514276-ioregistryentrygetpath
+    pub fn IORegistryEntryGetPath(
+        entry: io_registry_entry_t,
+        plane: *const c_char,
+        path: *mut c_char,
+    ) -> kern_return::kern_return_t;
+
     // https://developer.apple.com/documentation/iokit/1514741-ioiteratornext
     // The element should be released by the caller when it is finished.
+    // https://developer.apple.com/documentation/iokit/1514535-ioservicegetmatchingservice
+    // Consumes one `matching` reference, returned service should be released by the caller.
+    pub fn IOServiceGetMatchingService(masterPort: mach_port_t, matching: CFDictionaryRef) -> io_service_t;
+
+    // https://developer.apple.com/documentation/iokit/1514515-ioserviceopen
+    // Connection should be closed with `IOServiceClose`.
+    pub fn IOServiceOpen(
+        service: io_service_t,
+        owningTask: mach_port_t,
+        type_: u32,
+        connect: *mut io_connect_t,
+    ) -> kern_return::kern_return_t;
+
+    // https://developer.apple.com/documentation/iokit/1514627-ioserviceclose
+    pub fn IOServiceClose(connect: io_connect_t) -> kern_return::kern_return_t;
+
+    // https://developer.apple.com/documentation/iokit/1514274-ioconnectcallstructmethod
+    pub fn IOConnectCallStructMethod(
+        connection: io_connect_t,
+        selector: u32,
+        inputStruct: *const c_void,
+        inputStructCnt: size_t,
+        outputStruct: *mut c_void,
+        outputStructCnt: *mut size_t,
+    ) -> kern_return::kern_return_t;
+
     pub fn IOIteratorNext(iterator: io_iterator_t) -> io_object_t;
 
     pub fn IOIteratorIsValid(iterator: io_iterator_t) -> boolean::boolean_t;
 
     pub fn IOObjectRelease(object: io_object_t) -> kern_return::kern_return_t;
+
+    // https://developer.apple.com/documentation/iokit/1523839-iopscopypowersourcesinfo
+    // The caller should release the returned snapshot with CFRelease.
+    pub fn IOPSCopyPowerSourcesInfo() -> CFTypeRef;
+
+    // https://developer.apple.com/documentation/iokit/1523841-iopsgetprovidingpowersourcetype
+    // Returned string is owned by the `snapshot` and should not be released by the caller.
+    pub fn IOPSGetProvidingPowerSourceType(snapshot: CFTypeRef) -> CFStringRef;
 }
@@ -1,3 +1,4 @@
+use std::ffi::CStr;
 use std::mem;
 use std::ops::{Deref, DerefMut};
 
@@ -48,6 +49,25 @@ impl Drop for IoMasterPort {
     }
 }
 
+/// Returns the type of the power source, which is providing power to the system at the moment,
+/// ex. `"AC Power"` or `"Battery Power"`.
+pub fn providing_power_source_type() -> Option<String> {
+    unsafe {
+        let snapshot = sys::IOPSCopyPowerSourcesInfo();
+        if snapshot.is_null() {
+            return None;
+        }
+        let snapshot = CFType::wrap_under_create_rule(snapshot);
+
+        let source_type = sys::IOPSGetProvidingPowerSourceType(snapshot.as_CFTypeRef());
+        if source_type.is_null() {
+            None
+        } else {
+            Some(CFString::wrap_under_get_rule(source_type).to_string())
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct IoObject(sys::io_object_t);
 
@@ -72,6 +92,44 @@ impl IoObject {
             Ok(CFMutableDictionary::wrap_under_create_rule(props).to_immutable())
         }
     }
+
+    /// Returns the name of this registry entry, ex. `AppleSmartBattery`.
+    pub fn name(&self) -> Result<String> {
+        let mut name: sys::io_name_t = [0; 128];
+
+        unsafe {
+            kern_try!(sys::IORegistryEntryGetName(self.0, name.as_mut_ptr()));
+
+            Ok(CStr::from_ptr(name.as_ptr()).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Returns the path of this registry entry in the service plane,
+    /// ex. `IOService:/AppleACPIPlatformExpert/SMB0/AppleECSMBusController/AppleSmartBatteryManager/AppleSmartBattery`.
+    pub fn registry_path(&self) -> Result<String> {
+        let mut path: sys::io_string_t = [0; 512];
+
+        unsafe {
+            kern_try!(sys::IORegistryEntryGetPath(
+                self.0,
+                sys::IO_SERVICE_PLANE,
+                path.as_mut_ptr()
+            ));
+
+            Ok(CStr::from_ptr(path.as_ptr()).to_string_lossy().into_owned())
+        }
+    }
+
+    /// Returns the system-wide unique ID of this registry entry.
+    pub fn registry_entry_id(&self) -> Result<u64> {
+        let mut entry_id: u64 = 0;
+
+        unsafe {
+            kern_try!(sys::IORegistryEntryGetRegistryEntryID(self.0, &mut entry_id));
+        }
+
+        Ok(entry_id)
+    }
 }
 
 impl Drop for IoObject {
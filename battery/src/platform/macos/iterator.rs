@@ -1,14 +1,17 @@
 use std::fmt;
 use std::rc::Rc;
+use std::vec;
 
 use super::{iokit, IoKitDevice, IoKitManager};
+use crate::platform::ordering::sort_natural;
 use crate::platform::traits::BatteryIterator;
-use crate::Result;
+use crate::{KindFilter, Result};
 
 pub struct IoKitIterator {
     #[allow(dead_code)]
     manager: Rc<IoKitManager>,
-    inner: iokit::IoIterator,
+    // Services are paired with their registry entry IDs
+    inner: vec::IntoIter<(u64, iokit::IoObject)>,
 }
 
 impl Iterator for IoKitIterator {
@@ -17,7 +20,7 @@ impl Iterator for IoKitIterator {
     fn next(&mut self) -> Option<Self::Item> {
         match self.inner.next() {
             None => None,
-            Some(io_obj) => match iokit::PowerSource::try_from(io_obj) {
+            Some((_, io_obj)) => match iokit::PowerSource::try_from(io_obj) {
                 Ok(source) => Some(Ok(source.into())),
                 Err(e) => Some(Err(e)),
             },
@@ -25,7 +28,7 @@ impl Iterator for IoKitIterator {
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        (0, Some(self.inner.len()))
     }
 }
 
@@ -33,16 +36,50 @@ impl BatteryIterator for IoKitIterator {
     type Manager = IoKitManager;
     type Device = IoKitDevice;
 
-    fn new(manager: Rc<Self::Manager>) -> Result<Self> {
-        let services = manager.get_services()?;
+    fn with_filter(manager: Rc<Self::Manager>, filter: KindFilter) -> Result<Self> {
+        // `IOPMPowerSource` services are system batteries only,
+        // there is no need to touch IOKit at all if they are filtered out
+        if !filter.contains(KindFilter::SYSTEM) {
+            return Ok(Self {
+                manager,
+                inner: vec![].into_iter(),
+            });
+        }
+
+        let services = manager
+            .get_services()?
+            .map(|io_obj| {
+                // Path is needed for the ordering only, so the service is not failed without it
+                let path = io_obj.registry_path().ok();
+                let id = io_obj.registry_entry_id()?;
+                Ok((path, id, io_obj))
+            })
+            .collect::<Result<Vec<_>>>()?;
 
         Ok(Self {
             manager,
-            inner: services,
+            inner: sort_services(services).into_iter(),
         })
     }
 }
 
+/// Orders the matched services, since IOKit does not guarantee any specific order for them.
+///
+/// Services are sorted by their registry paths first, which are depending on the hardware only.
+/// Registry entry IDs, which are following the services registration order, are breaking the ties
+/// and are ordering the services which path is not available, which are going last.
+pub(crate) fn sort_services<T>(mut services: Vec<(Option<String>, u64, T)>) -> Vec<(u64, T)> {
+    services.sort_by_key(|&(_, id, _)| id);
+    let (mut found, missing): (Vec<_>, Vec<_>) = services.into_iter().partition(|(path, _, _)| path.is_some());
+    sort_natural(&mut found, |(path, _, _)| path.as_ref().map_or("", String::as_str));
+
+    found
+        .into_iter()
+        .chain(missing)
+        .map(|(_, id, service)| (id, service))
+        .collect()
+}
+
 impl fmt::Debug for IoKitIterator {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (start, end) = self.size_hint();
@@ -1,10 +1,15 @@
+use approx::assert_abs_diff_eq;
+
 use super::device::IoKitDevice;
+use super::iterator::sort_services;
 use super::traits::DataSource;
 use crate::platform::traits::BatteryDevice;
 use crate::units::energy::watt_hour;
 use crate::units::power::milliwatt;
-use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, ThermodynamicTemperature, Time};
-use crate::Result;
+use crate::units::ratio::percent;
+use crate::units::time::hour;
+use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, Ratio, ThermodynamicTemperature, Time};
+use crate::{Anomaly, Battery, Condition, Result};
 
 /// This data source is not using uom types, because it is easier to create test suites
 /// from the `ioreg` tool output that way (which values are in mV, mA, mAh and mWh).
@@ -20,6 +25,11 @@ struct TestDataSource {
     current_capacity: u32,
     temperature: Option<f32>,
     cycle_count: Option<u32>,
+    max_error: Option<u32>,
+    permanent_failure_status: Option<u32>,
+    error_condition: Option<&'static str>,
+    manufacturer_data: Option<&'static [u8]>,
+    time_remaining: Option<u32>,
 }
 
 impl DataSource for TestDataSource {
@@ -68,6 +78,30 @@ impl DataSource for TestDataSource {
     }
 
     fn time_remaining(&self) -> Option<Time> {
+        self.time_remaining.map(|minutes| minute!(minutes))
+    }
+
+    fn max_error(&self) -> Option<Ratio> {
+        self.max_error.map(|value| percent!(value))
+    }
+
+    fn permanent_failure_status(&self) -> Option<u32> {
+        self.permanent_failure_status
+    }
+
+    fn error_condition(&self) -> Option<&str> {
+        self.error_condition
+    }
+
+    fn is_active_source(&self) -> Option<bool> {
+        None
+    }
+
+    fn charge_limit(&self) -> Option<Ratio> {
+        None
+    }
+
+    fn name(&self) -> Option<&str> {
         None
     }
 
@@ -82,6 +116,10 @@ impl DataSource for TestDataSource {
     fn serial_number(&self) -> Option<&str> {
         None
     }
+
+    fn manufacturer_data(&self) -> Option<&[u8]> {
+        self.manufacturer_data
+    }
 }
 
 // Based on the https://github.com/svartalf/rust-battery/issues/8
@@ -105,3 +143,188 @@ fn test_energy_calculation() {
     assert_eq!(device.energy_full().get::<watt_hour>().floor(), 52.0);
     assert_eq!(device.energy_full_design().get::<watt_hour>().floor(), 55.0);
 }
+
+#[test]
+fn test_healthy_battery() {
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        permanent_failure_status: Some(0),
+        ..Default::default()
+    };
+    let device: IoKitDevice = data.into();
+
+    assert!(!device.is_failed());
+    assert_eq!(device.failure_code(), Some(0));
+}
+
+// Synthetic values in the `ioreg -r -c AppleSmartBattery` format, modelling a battery with the permanently failed gauge
+#[test]
+fn test_permanent_failure_status() {
+    let data = TestDataSource {
+        current_capacity: 1710,
+        design_capacity: 5088,
+        max_capacity: 4206,
+        voltage: 11164,
+        amperage: -1520,
+        cycle_count: Some(1042),
+        permanent_failure_status: Some(0x0000_0200),
+        ..Default::default()
+    };
+    let device: IoKitDevice = data.into();
+
+    assert!(device.is_failed());
+    assert_eq!(device.failure_code(), Some(0x0000_0200));
+
+    let battery = Battery::from(device);
+    assert!(battery.validate().contains(&Anomaly::PermanentFailure));
+    assert_eq!(battery.condition(), Condition::Failed);
+}
+
+// Older drivers are not reporting the `PermanentFailureStatus` key at all
+#[test]
+fn test_permanent_failure_condition() {
+    let data = TestDataSource {
+        current_capacity: 2430,
+        design_capacity: 5770,
+        max_capacity: 3012,
+        voltage: 12111,
+        error_condition: Some("Permanent Battery Failure"),
+        ..Default::default()
+    };
+    let device: IoKitDevice = data.into();
+
+    assert!(device.is_failed());
+    assert_eq!(device.failure_code(), None);
+    assert_eq!(Battery::from(device).condition(), Condition::Failed);
+
+    let data = TestDataSource {
+        error_condition: Some("Connection Drop"),
+        ..Default::default()
+    };
+    let device: IoKitDevice = data.into();
+
+    assert!(!device.is_failed());
+}
+
+#[test]
+fn test_manufacturer_data() {
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        manufacturer_data: Some(&[0x00, 0x00, 0x00, 0x00, 0x03, 0x39, 0x00, 0x00]),
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+
+    assert_eq!(
+        battery.manufacturer_data(),
+        Some(vec![0x00, 0x00, 0x00, 0x00, 0x03, 0x39, 0x00, 0x00])
+    );
+
+    let battery = Battery::from(IoKitDevice::from(TestDataSource::default()));
+
+    assert_eq!(battery.manufacturer_data(), None);
+}
+
+#[test]
+fn test_eta_comparison() {
+    // 50.477 Wh drained at 13.292 W is 3.8 hours, while the OS says it is 4 hours left
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        amperage: -1037,
+        time_remaining: Some(240),
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+    let comparison = battery.eta_comparison().unwrap();
+
+    assert_eq!(battery.time_to_empty(), Some(comparison.reported()));
+    assert_abs_diff_eq!(comparison.reported().get::<hour>(), 4.0, epsilon = 0.001);
+    assert_abs_diff_eq!(comparison.computed().get::<hour>(), 3.797, epsilon = 0.001);
+    assert_abs_diff_eq!(comparison.difference().get::<hour>(), 0.203, epsilon = 0.001);
+
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        amperage: -1037,
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+
+    assert_eq!(battery.eta_comparison(), None);
+}
+
+#[test]
+fn test_measurement_error() {
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        max_error: Some(3),
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+    assert_abs_diff_eq!(battery.measurement_error().unwrap().get::<percent>(), 3.0);
+
+    // Not calibrated gauge
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        max_error: Some(35),
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+    assert_abs_diff_eq!(battery.measurement_error().unwrap().get::<percent>(), 35.0);
+
+    let data = TestDataSource {
+        current_capacity: 3938,
+        design_capacity: 4315,
+        max_capacity: 4119,
+        voltage: 12818,
+        ..Default::default()
+    };
+    let battery = Battery::from(IoKitDevice::from(data));
+    assert_eq!(battery.measurement_error(), None);
+}
+
+// Mocked `IOPMPowerSource` services, one of which registry path failed to be read
+#[test]
+fn test_services_order() {
+    let path = |name: &str| {
+        Some(format!(
+            "IOService:/AppleACPIPlatformExpert/SMB0/{}/AppleSmartBattery",
+            name
+        ))
+    };
+    let services = vec![
+        (path("AppleSmartBatteryManager10"), 12, "fifth"),
+        (None, 20, "seventh"),
+        (path("AppleSmartBatteryManager2"), 15, "fourth"),
+        (path("AppleSmartBatteryManager1"), 11, "third"),
+        (None, 3, "sixth"),
+        (path("AppleSmartBatteryManager1"), 10, "second"),
+        (path("AppleSmartBatteryManager0"), 30, "first"),
+    ];
+
+    let order = sort_services(services)
+        .into_iter()
+        .map(|(_, name)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        order,
+        vec!["first", "second", "third", "fourth", "fifth", "sixth", "seventh"]
+    );
+}
@@ -1,6 +1,6 @@
 use std::fmt::Debug;
 
-use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, ThermodynamicTemperature, Time};
+use crate::units::{ElectricCharge, ElectricCurrent, ElectricPotential, Ratio, ThermodynamicTemperature, Time};
 use crate::Result;
 
 /// Used for IOPMPowerSource wrapper and for tests.
@@ -8,7 +8,7 @@ use crate::Result;
 /// Only keys declared at https://developer.apple.com/documentation/kernel/iopmpowersource?language=objc
 /// should be used in this trait and trait implementors, otherwise bugs might happen
 /// as in [#11](https://github.com/svartalf/rust-battery/pull/11)
-pub trait DataSource: Debug + 'static {
+pub trait DataSource: Debug + Send + 'static {
     fn refresh(&mut self) -> Result<()>;
 
     /// kIOPMFullyChargedKey
@@ -48,6 +48,33 @@ pub trait DataSource: Debug + 'static {
     /// kIOPMPSTimeRemainingKey, minutes
     fn time_remaining(&self) -> Option<Time>;
 
+    /// MaxErr, %
+    ///
+    /// Not declared in the documentation, but is reported by the `AppleSmartBattery` driver.
+    fn max_error(&self) -> Option<Ratio>;
+
+    /// PermanentFailureStatus
+    ///
+    /// Not declared in the documentation, but is reported by the `AppleSmartBattery` driver,
+    /// non-zero value is a gauge permanent failure flags.
+    fn permanent_failure_status(&self) -> Option<u32>;
+
+    /// kIOPMPSErrorConditionKey
+    fn error_condition(&self) -> Option<&str>;
+
+    /// `IOPSGetProvidingPowerSourceType` result is `kIOPMBatteryPowerKey`.
+    ///
+    /// It is a system-wide value, not the per-battery one.
+    fn is_active_source(&self) -> Option<bool>;
+
+    /// SMC `BCLM` or `CHWA` key, %
+    ///
+    /// Not a power source property, but it caps the charging the same way.
+    fn charge_limit(&self) -> Option<Ratio>;
+
+    /// IORegistryEntryGetName
+    fn name(&self) -> Option<&str>;
+
     /// kIOPMPSManufacturerKey
     fn manufacturer(&self) -> Option<&str>;
 
@@ -56,6 +83,12 @@ pub trait DataSource: Debug + 'static {
 
     /// kIOPMPSSerialKey
     fn serial_number(&self) -> Option<&str>;
+
+    /// ManufacturerData
+    ///
+    /// Not declared in the documentation, but is reported by the `AppleSmartBattery` driver,
+    /// opaque vendor-specific block.
+    fn manufacturer_data(&self) -> Option<&[u8]>;
 }
 
 impl<T> DataSource for Box<T>
@@ -110,6 +143,30 @@ where
         (**self).time_remaining()
     }
 
+    fn max_error(&self) -> Option<Ratio> {
+        (**self).max_error()
+    }
+
+    fn permanent_failure_status(&self) -> Option<u32> {
+        (**self).permanent_failure_status()
+    }
+
+    fn error_condition(&self) -> Option<&str> {
+        (**self).error_condition()
+    }
+
+    fn is_active_source(&self) -> Option<bool> {
+        (**self).is_active_source()
+    }
+
+    fn charge_limit(&self) -> Option<Ratio> {
+        (**self).charge_limit()
+    }
+
+    fn name(&self) -> Option<&str> {
+        (**self).name()
+    }
+
     fn manufacturer(&self) -> Option<&str> {
         (**self).manufacturer()
     }
@@ -121,4 +178,8 @@ where
     fn serial_number(&self) -> Option<&str> {
         (**self).serial_number()
     }
+
+    fn manufacturer_data(&self) -> Option<&[u8]> {
+        (**self).manufacturer_data()
+    }
 }
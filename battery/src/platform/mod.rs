@@ -29,4 +29,25 @@ cfg_if! {
     }
 }
 
+#[cfg(any(target_os = "linux", target_os = "macos", target_os = "windows"))]
+mod ordering;
 pub mod traits;
+
+/// Checks if the current platform has a battery backend implemented.
+///
+/// Linux, MacOS, Windows, FreeBSD and DragonFlyBSD are supported. The crate fails to build for other
+/// targets instead of falling back to an empty backend, so this function always returns `true` for now.
+///
+/// Supported platform does not mean that there are batteries available,
+/// ex. desktop computers are usually reporting none.
+///
+/// # Example
+///
+/// ```edition2018
+/// if !battery::supported() {
+///     println!("Battery information is not available for this platform");
+/// }
+/// ```
+pub fn supported() -> bool {
+    true
+}
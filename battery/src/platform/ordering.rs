@@ -0,0 +1,114 @@
+//! Deterministic batteries ordering, shared by the platforms which have to sort
+//! the enumerated devices themselves.
+
+use std::cmp::Ordering;
+
+/// Sorts `items` by their string keys in a [natural](fn.natural_cmp.html) order.
+///
+/// Sort is stable, so items with equal keys are keeping their relative order.
+pub fn sort_natural<T, F>(items: &mut [T], key: F)
+where
+    F: Fn(&T) -> &str,
+{
+    items.sort_by(|left, right| natural_cmp(key(left), key(right)));
+}
+
+/// Compares two strings in a "natural" way, where the digit sequences
+/// are compared by their numeric values, so `BAT2` goes before `BAT10`.
+pub fn natural_cmp(left: &str, right: &str) -> Ordering {
+    let (left, right) = (left.as_bytes(), right.as_bytes());
+    let (mut l_idx, mut r_idx) = (0, 0);
+
+    while l_idx < left.len() && r_idx < right.len() {
+        if left[l_idx].is_ascii_digit() && right[r_idx].is_ascii_digit() {
+            let l_end = digits_end(left, l_idx);
+            let r_end = digits_end(right, r_idx);
+
+            // Comparing digits without parsing them, so leading zeroes and huge numbers are not an issue
+            let l_digits = trim_zeroes(&left[l_idx..l_end]);
+            let r_digits = trim_zeroes(&right[r_idx..r_end]);
+            let ordering = l_digits
+                .len()
+                .cmp(&r_digits.len())
+                .then_with(|| l_digits.cmp(r_digits))
+                .then_with(|| (l_end - l_idx).cmp(&(r_end - r_idx)));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+
+            l_idx = l_end;
+            r_idx = r_end;
+        } else {
+            if left[l_idx] != right[r_idx] {
+                return left[l_idx].cmp(&right[r_idx]);
+            }
+
+            l_idx += 1;
+            r_idx += 1;
+        }
+    }
+
+    (left.len() - l_idx).cmp(&(right.len() - r_idx))
+}
+
+fn digits_end(value: &[u8], start: usize) -> usize {
+    value[start..]
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .map_or(value.len(), |offset| start + offset)
+}
+
+fn trim_zeroes(digits: &[u8]) -> &[u8] {
+    match digits.iter().position(|byte| *byte != b'0') {
+        Some(idx) => &digits[idx..],
+        None => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+
+    use super::{natural_cmp, sort_natural};
+
+    #[test]
+    fn test_natural_cmp() {
+        assert_eq!(natural_cmp("BAT2", "BAT10"), Ordering::Less);
+        assert_eq!(natural_cmp("BAT10", "BAT2"), Ordering::Greater);
+        assert_eq!(natural_cmp("BAT1", "BAT1"), Ordering::Equal);
+        assert_eq!(natural_cmp("BAT", "BAT0"), Ordering::Less);
+        assert_eq!(natural_cmp("BAT1", "BAT01"), Ordering::Less);
+        assert_eq!(natural_cmp("AC", "BAT0"), Ordering::Less);
+        assert_eq!(natural_cmp("CMB1", "BAT1"), Ordering::Greater);
+    }
+
+    // Windows device interface paths, in the order `SetupDiEnumDeviceInterfaces` might yield them
+    #[test]
+    fn test_device_paths() {
+        let guid = "{72631e54-78a4-11d0-bcf7-00aa00b7b32a}";
+        let mut paths = vec![
+            (format!(r"\\?\acpi#pnp0c0a#10#{}", guid), 0),
+            (format!(r"\\?\acpi#pnp0c0a#2#{}", guid), 1),
+            (format!(r"\\?\acpi#pnp0c0a#1#{}", guid), 2),
+        ];
+        sort_natural(&mut paths, |(path, _)| path);
+
+        let indices = paths.iter().map(|&(_, index)| index).collect::<Vec<_>>();
+        assert_eq!(indices, vec![2, 1, 0]);
+    }
+
+    // MacOS registry paths, already sorted by their registry entry IDs
+    #[test]
+    fn test_registry_paths() {
+        let mut services = vec![
+            ("IOService:/AppleACPIPlatformExpert/SMB1/AppleSmartBattery", 0x1000_0500),
+            ("IOService:/AppleACPIPlatformExpert/SMB0/AppleSmartBattery", 0x1000_0600),
+            ("IOService:/AppleACPIPlatformExpert/SMB0/AppleSmartBattery", 0x1000_0700),
+        ];
+        sort_natural(&mut services, |&(path, _)| path);
+
+        let ids = services.iter().map(|&(_, id)| id).collect::<Vec<_>>();
+        // Services with the same path are keeping the registry entry IDs order
+        assert_eq!(ids, vec![0x1000_0600, 0x1000_0700, 0x1000_0500]);
+    }
+}
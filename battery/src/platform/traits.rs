@@ -1,33 +1,112 @@
 //! Platform-specific types are required to implement the following traits.
 
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+#[cfg(feature = "control")]
+use std::io;
 use std::rc::Rc;
 
 use num_traits::identities::Zero;
+use uom::si::ratio::percent;
 use uom::si::time::{day, hour};
 
-use crate::units::{Bound, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
-use crate::{Result, State, Technology};
+use crate::units::{Bound, ElectricCharge, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
+#[cfg(feature = "control")]
+use crate::Error;
+use crate::{FieldError, Kind, KindFilter, LinePower, RateKind, Result, State, Technology};
 
 pub trait BatteryManager: Debug + Sized {
-    type Iterator: BatteryIterator;
+    type Iterator: BatteryIterator<Manager = Self>;
 
     fn new() -> Result<Self>;
 
     fn refresh(&self, battery: &mut <Self::Iterator as BatteryIterator>::Device) -> Result<()>;
+
+    /// Creates a new manager for the same system, re-opening all the platform handles.
+    ///
+    /// Default implementation is a plain `new`.
+    fn reloaded(&self) -> Result<Self> {
+        Self::new()
+    }
+
+    /// Same as `refresh`, but the failed reads of the separate battery values are returned
+    /// instead of failing the whole refresh.
+    ///
+    /// Default implementation is a plain `refresh`, for the platforms loading all the values at once.
+    fn refresh_partial(&self, battery: &mut <Self::Iterator as BatteryIterator>::Device) -> Result<Vec<FieldError>> {
+        self.refresh(battery).map(|_| Vec::new())
+    }
+
+    /// Checks if the operating system low-power mode (ex. Windows battery saver) is on,
+    /// `None` if it can't be determined.
+    fn battery_saver_active(&self) -> Result<Option<bool>> {
+        Ok(None)
+    }
+
+    /// Loads the external line power supplies information.
+    ///
+    /// Default implementation returns no supplies, for the platforms which are not exposing them.
+    fn line_power(&self) -> Result<Vec<LinePower>> {
+        Ok(Vec::new())
+    }
+
+    /// Loads the battery with the given operating system `name`, `None` if there is no such battery.
+    ///
+    /// Default implementation enumerates the batteries and picks the first one with a matching name.
+    fn find_by_name(manager: &Rc<Self>, name: &str) -> Result<Option<<Self::Iterator as BatteryIterator>::Device>> {
+        for device in Self::Iterator::new(manager.clone())? {
+            let device = device?;
+            if device.name() == Some(name) {
+                return Ok(Some(device));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loads all batteries at once and checks if the system is connected to an external power supply.
+    ///
+    /// Default implementation is a plain `Self::Iterator` run without the external power status,
+    /// platforms are free to provide something more efficient.
+    #[allow(clippy::type_complexity)]
+    fn scan(manager: &Rc<Self>) -> Result<(Vec<<Self::Iterator as BatteryIterator>::Device>, Option<bool>)> {
+        let devices = Self::Iterator::new(manager.clone())?.collect::<Result<Vec<_>>>()?;
+
+        Ok((devices, None))
+    }
+
+    /// Loads all batteries, same as the `Self::Iterator` would yield them and in the same order,
+    /// using up to `threads` threads.
+    ///
+    /// Default implementation is a plain `Self::Iterator` run in the calling thread,
+    /// for the platforms which can't read devices independently.
+    fn scan_parallel(manager: &Rc<Self>, _threads: usize) -> Result<Vec<<Self::Iterator as BatteryIterator>::Device>> {
+        Self::Iterator::new(manager.clone())?.collect()
+    }
 }
 
 pub trait BatteryIterator: Iterator<Item = Result<<Self as BatteryIterator>::Device>> + Debug + Sized {
     type Manager: BatteryManager<Iterator = Self>;
     type Device: BatteryDevice;
 
+    /// Battery kinds yielded by the unfiltered enumeration.
+    const DEFAULT_FILTER: KindFilter = KindFilter::SYSTEM;
+
+    fn new(manager: Rc<Self::Manager>) -> Result<Self> {
+        Self::with_filter(manager, Self::DEFAULT_FILTER)
+    }
+
     /// Iterator is required to store reference to the `Self::Manager` type,
     /// even if it does not use it.
     /// In that case all iterator instances will be freed before the manager.
     ///
     /// Implemented `next()` for `<Self as Iterator>` must preload all needed battery data
     /// in this method, because `BatteryDevice` methods are infallible.
-    fn new(manager: Rc<Self::Manager>) -> Result<Self>;
+    ///
+    /// Devices not matching the `filter` should be skipped as early as possible,
+    /// before their data is loaded.
+    fn with_filter(manager: Rc<Self::Manager>, filter: KindFilter) -> Result<Self>;
 }
 
 /// Underline type for `Battery`, different for each supported platform.
@@ -44,30 +123,224 @@ pub trait BatteryDevice: Sized + Debug {
         (self.energy() / self.energy_full()).into_bounded()
     }
 
+    /// Same as the Smart Battery `AbsoluteStateOfCharge`, relative to the design capacity
+    /// instead of the full one; `None` if design capacity is unknown.
+    fn absolute_state_of_charge(&self) -> Option<Ratio> {
+        let energy_full_design = self.energy_full_design();
+        if energy_full_design.is_zero() {
+            None
+        } else {
+            Some((self.energy() / energy_full_design).into_bounded())
+        }
+    }
+
     fn energy(&self) -> Energy;
 
     fn energy_full(&self) -> Energy;
 
     fn energy_full_design(&self) -> Energy;
 
+    /// `true` if `energy_full` is not reported and is substituted with an estimate.
+    fn energy_full_is_estimated(&self) -> bool {
+        false
+    }
+
     fn energy_rate(&self) -> Power;
 
+    fn rate_kind(&self) -> Option<RateKind> {
+        None
+    }
+
     fn state(&self) -> State;
 
+    fn state_raw(&self) -> Option<Cow<'_, str>> {
+        None
+    }
+
+    fn is_active_source(&self) -> Option<bool> {
+        None
+    }
+
+    fn measurement_error(&self) -> Option<Ratio> {
+        None
+    }
+
+    /// Battery controller reports a permanent failure and the battery needs a service.
+    fn is_failed(&self) -> bool {
+        false
+    }
+
+    fn failure_code(&self) -> Option<u32> {
+        None
+    }
+
+    fn is_calibrating(&self) -> Option<bool> {
+        None
+    }
+
     fn voltage(&self) -> ElectricPotential;
 
+    fn voltage_min_design(&self) -> Option<ElectricPotential> {
+        None
+    }
+
+    fn voltage_max_design(&self) -> Option<ElectricPotential> {
+        None
+    }
+
     fn temperature(&self) -> Option<ThermodynamicTemperature>;
 
+    fn ambient_temperature(&self) -> Option<ThermodynamicTemperature> {
+        None
+    }
+
+    fn temperature_min(&self) -> Option<ThermodynamicTemperature> {
+        None
+    }
+
+    fn temperature_max(&self) -> Option<ThermodynamicTemperature> {
+        None
+    }
+
+    fn temperature_alert_max(&self) -> Option<ThermodynamicTemperature> {
+        None
+    }
+
+    /// Reads the platform attributes as they are, ex. for the diagnostics.
+    fn raw_attributes(&self) -> Result<BTreeMap<String, String>> {
+        Ok(BTreeMap::new())
+    }
+
+    fn name(&self) -> Option<&str> {
+        None
+    }
+
+    fn location(&self) -> Option<&str> {
+        None
+    }
+
+    fn device_path(&self) -> Option<&str> {
+        None
+    }
+
     fn vendor(&self) -> Option<&str>;
 
     fn model(&self) -> Option<&str>;
 
     fn serial_number(&self) -> Option<&str>;
 
+    fn serial_number_raw(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Charge stored in the battery, if the platform reports it natively.
+    fn charge(&self) -> Option<ElectricCharge> {
+        None
+    }
+
+    /// Charge in the full battery, if the platform reports it natively.
+    fn charge_full(&self) -> Option<ElectricCharge> {
+        None
+    }
+
+    /// `false` if the energy values are converted from the charge ones.
+    fn reports_energy(&self) -> bool {
+        true
+    }
+
+    /// Voltage used to convert between the charge and the energy values.
+    fn conversion_voltage(&self) -> ElectricPotential {
+        self.voltage()
+    }
+
+    /// Raw vendor-specific data block, ex. the Smart Battery `ManufacturerData`.
+    fn manufacturer_data(&self) -> Option<&[u8]> {
+        None
+    }
+
+    /// Optional values, which were not read because of the insufficient permissions.
+    fn restricted_fields(&self) -> Vec<&'static str> {
+        Vec::new()
+    }
+
     fn technology(&self) -> Technology;
 
+    fn kind(&self) -> Kind {
+        Kind::Unknown
+    }
+
     fn cycle_count(&self) -> Option<u32>;
 
+    /// Returns `true` if `time_to_full` and `time_to_empty` values are provided by the operating system
+    /// or battery firmware, rather than calculated from the instant energy rate.
+    fn reports_time_estimates(&self) -> bool {
+        false
+    }
+
+    fn capacity_alarm(&self) -> Option<Energy> {
+        None
+    }
+
+    /// Writes the capacity alarm into the battery firmware and refreshes the device data.
+    #[cfg(feature = "control")]
+    fn set_capacity_alarm(&mut self, _energy: Energy) -> Result<()> {
+        Err(Error::new(
+            io::Error::from(io::ErrorKind::Other),
+            "Capacity alarm can't be set on this platform",
+        ))
+    }
+
+    fn charge_stop_threshold(&self) -> Option<Ratio> {
+        None
+    }
+
+    /// Energy at which the charging stops, which is lower than `energy_full`
+    /// if the charge stop threshold is set.
+    fn energy_full_target(&self) -> Energy {
+        match self.charge_stop_threshold() {
+            Some(threshold) if threshold.get::<percent>() < 100.0 => self.energy_full() * threshold,
+            _ => self.energy_full(),
+        }
+    }
+
+    fn max_charge_power(&self) -> Option<Power> {
+        None
+    }
+
+    fn adapter_power(&self) -> Option<Power> {
+        None
+    }
+
+    /// Whether the external power (ex. AC adapter) is connected, if the platform tells it for the battery.
+    fn external_power(&self) -> Option<bool> {
+        None
+    }
+
+    fn fast_charge_active(&self) -> Option<bool> {
+        None
+    }
+
+    fn is_rechargeable(&self) -> Option<bool> {
+        // System batteries, which are the only ones enumerated for the most platforms, are rechargeable
+        Some(true)
+    }
+
+    fn energy_until_full(&self) -> Option<Energy> {
+        match self.state() {
+            // Some drivers might report that `energy_full` is lower than `energy`,
+            // clamping the difference to zero in that case
+            State::Charging => Some((self.energy_full_target() - self.energy()).max(Energy::zero())),
+            _ => None,
+        }
+    }
+
+    fn energy_until_empty(&self) -> Option<Energy> {
+        match self.state() {
+            State::Discharging => Some(self.energy().max(Energy::zero())),
+            _ => None,
+        }
+    }
+
     // Default implementation for `time_to_full` and `time_to_empty`
     // uses calculation based on the current energy flow,
     // but if device provides by itself provides these **instant** values (do not use average values),
@@ -84,7 +357,7 @@ pub trait BatteryDevice: Sized + Debug {
                 // but battery is still charging. What should we do in that case?
                 // As for now, assuming that battery is fully charged, since we can't guess,
                 // how much time left.
-                let energy_left = match self.energy_full() - self.energy() {
+                let energy_left = match self.energy_full_target() - self.energy() {
                     value if value.is_sign_positive() => value,
                     _ => return None,
                 };
@@ -102,6 +375,17 @@ pub trait BatteryDevice: Sized + Debug {
     }
 
     fn time_to_empty(&self) -> Option<Time> {
+        self.computed_time_to_empty()
+    }
+
+    /// Time to empty estimate provided by the operating system or battery firmware,
+    /// even if the `time_to_empty` is calculated by the crate.
+    fn reported_time_to_empty(&self) -> Option<Time> {
+        None
+    }
+
+    /// Default `time_to_empty` implementation, which is never overridden.
+    fn computed_time_to_empty(&self) -> Option<Time> {
         let energy_rate = self.energy_rate();
         match self.state() {
             // In some cases energy_rate can be 0 while Discharging, for example just after
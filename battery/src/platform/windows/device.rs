@@ -1,33 +1,49 @@
+use std::borrow::Cow;
 use std::convert::AsRef;
 use std::fmt;
 
 use super::ffi::{BatteryQueryInformation, DeviceHandle};
 use crate::platform::traits::BatteryDevice;
-use crate::units::{ElectricPotential, Energy, Power, ThermodynamicTemperature};
-use crate::{Error, Result, State, Technology};
+use crate::units::{ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
+use crate::{Error, Kind, KindFilter, Result, State, Technology};
 
 #[derive(Default)]
 pub struct PowerDevice {
     // Used later for information refreshing
     tag: BatteryQueryInformation,
 
+    display_name: Option<String>,
+    location: Option<String>,
+    device_path: Option<String>,
     technology: Technology,
+    kind: Kind,
+    rechargeable: bool,
     state: State,
+    state_raw: Option<Cow<'static, str>>,
+    external_power: bool,
     voltage: ElectricPotential,
     energy_rate: Power,
     capacity: Energy,
     design_capacity: Energy,
     full_charged_capacity: Energy,
     temperature: Option<ThermodynamicTemperature>,
+    estimated_time: Option<Time>,
+    measurement_error: Option<Ratio>,
     cycle_count: Option<u32>,
+    capacity_alarm: Option<Energy>,
     device_name: Option<String>,
     manufacturer: Option<String>,
     serial_number: Option<String>,
 }
 
 impl PowerDevice {
-    pub fn try_from(mut handle: DeviceHandle) -> Result<Option<PowerDevice>> {
+    pub fn try_from(mut handle: DeviceHandle, filter: KindFilter) -> Result<Option<PowerDevice>> {
         let info = handle.information()?;
+        let kind = info.kind();
+        match kind.filter() {
+            Some(kind) if filter.contains(kind) => {}
+            _ => return Ok(None),
+        }
         if info.is_relative() {
             // We can't support batteries with relative data so far
             return Ok(None);
@@ -48,7 +64,12 @@ impl PowerDevice {
 
         let mut device = PowerDevice {
             tag: handle.tag.clone(),
+            display_name: handle.properties.display_name.take(),
+            location: handle.properties.location.take(),
+            device_path: handle.properties.device_path.take(),
             technology: info.technology(),
+            kind,
+            rechargeable: info.is_rechargeable(),
             device_name,
             manufacturer,
             serial_number,
@@ -66,27 +87,56 @@ impl PowerDevice {
             None => return Err(Error::invalid_data("Device rate value is unknown")),
             Some(value) => milliwatt!(value),
         };
-        let capacity = match status.capacity() {
+        let capacity_mwh = match status.capacity() {
             None => return Err(Error::invalid_data("Device capacity value is unknown")),
-            Some(value) => milliwatt_hour!(value),
+            Some(value) => value,
         };
         let voltage = match status.voltage() {
             None => return Err(Error::invalid_data("Device voltage value is unknown")),
             Some(value) => millivolt!(value),
         };
         let temperature = match handle.temperature() {
+            // Smart Battery and ACPI "unknown value" markers, reported by some gauges during the firmware update
+            Ok(0xFFFF) | Ok(0xFFFF_FFFF) => {
+                trace_warn!("temperature unknown value marker is ignored");
+                None
+            }
             Ok(value) => Some(decikelvin!(value)),
             Err(_) => None,
         };
+        let estimated_time = match handle.estimated_time() {
+            // `BATTERY_UNKNOWN_TIME`, reported while the battery is not discharging
+            Ok(0xFFFF_FFFF) => None,
+            Ok(value) => Some(second!(value)),
+            Err(_) => None,
+        };
+
+        // Granularity of the current capacity value relative to the full capacity
+        // is the best approximation of the gauge error available in Windows
+        let full_charged_capacity = info.full_charged_capacity();
+        let measurement_error = match handle.granularity() {
+            Ok(ref scales) if full_charged_capacity > 0 => scales
+                .granularity(capacity_mwh)
+                .map(|granularity| percent!(100.0 * granularity as f32 / full_charged_capacity as f32)),
+            _ => None,
+        };
 
         self.state = status.state();
+        self.state_raw = Some(status.power_state());
+        self.external_power = status.is_power_on_line();
         self.energy_rate = rate;
         self.design_capacity = milliwatt_hour!(info.designed_capacity());
         self.full_charged_capacity = milliwatt_hour!(info.full_charged_capacity());
         self.cycle_count = info.cycle_count();
-        self.capacity = capacity;
+        self.capacity_alarm = match info.default_alert1() {
+            0 => None,
+            value => Some(milliwatt_hour!(value)),
+        };
+        self.capacity = milliwatt_hour!(capacity_mwh);
         self.voltage = voltage;
         self.temperature = temperature;
+        self.estimated_time = estimated_time;
+        self.measurement_error = measurement_error;
 
         Ok(())
     }
@@ -117,6 +167,14 @@ impl BatteryDevice for PowerDevice {
         self.state
     }
 
+    fn state_raw(&self) -> Option<Cow<'_, str>> {
+        self.state_raw.as_ref().map(|raw| Cow::Borrowed(raw.as_ref()))
+    }
+
+    fn external_power(&self) -> Option<bool> {
+        Some(self.external_power)
+    }
+
     fn voltage(&self) -> ElectricPotential {
         self.voltage
     }
@@ -125,6 +183,18 @@ impl BatteryDevice for PowerDevice {
         self.temperature
     }
 
+    fn name(&self) -> Option<&str> {
+        self.display_name.as_ref().map(AsRef::as_ref)
+    }
+
+    fn location(&self) -> Option<&str> {
+        self.location.as_ref().map(AsRef::as_ref)
+    }
+
+    fn device_path(&self) -> Option<&str> {
+        self.device_path.as_ref().map(AsRef::as_ref)
+    }
+
     fn vendor(&self) -> Option<&str> {
         self.manufacturer.as_ref().map(AsRef::as_ref)
     }
@@ -141,9 +211,32 @@ impl BatteryDevice for PowerDevice {
         self.technology
     }
 
+    fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    fn is_rechargeable(&self) -> Option<bool> {
+        Some(self.rechargeable)
+    }
+
+    fn measurement_error(&self) -> Option<Ratio> {
+        self.measurement_error
+    }
+
     fn cycle_count(&self) -> Option<u32> {
         self.cycle_count
     }
+
+    fn capacity_alarm(&self) -> Option<Energy> {
+        self.capacity_alarm
+    }
+
+    fn reported_time_to_empty(&self) -> Option<Time> {
+        match self.state {
+            State::Discharging => self.estimated_time,
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Debug for PowerDevice {
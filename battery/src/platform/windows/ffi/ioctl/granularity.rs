@@ -0,0 +1,89 @@
+//! https://docs.microsoft.com/en-us/windows/win32/power/battery-reporting-scale-str
+
+#![allow(non_snake_case)]
+
+use std::mem;
+
+use winapi::shared::ntdef;
+
+/// Maximum amount of scales battery can report.
+pub const MAX_SCALES: usize = 4;
+
+STRUCT! {#[cfg_attr(target_arch = "x86", repr(packed))] #[derive(Debug)] struct BATTERY_REPORTING_SCALE {
+    Granularity: ntdef::ULONG, // mWh
+    Capacity: ntdef::ULONG, // mWh
+}}
+
+impl Default for BATTERY_REPORTING_SCALE {
+    #[inline]
+    fn default() -> Self {
+        unsafe { mem::zeroed() }
+    }
+}
+
+/// Granularity of the capacity reporting, which might be different
+/// for the different capacity ranges.
+#[derive(Debug, Default)]
+pub struct BatteryReportingScales {
+    scales: [BATTERY_REPORTING_SCALE; MAX_SCALES],
+    count: usize,
+}
+
+impl BatteryReportingScales {
+    pub fn as_mut_ptr(&mut self) -> *mut BATTERY_REPORTING_SCALE {
+        self.scales.as_mut_ptr()
+    }
+
+    pub fn set_len(&mut self, count: usize) {
+        self.count = count.min(MAX_SCALES);
+    }
+
+    /// Returns granularity (in mWh) for the given `capacity` (in mWh).
+    ///
+    /// Scales are ordered by their capacity, and each one of them
+    /// is applied to the capacities up to the scale `Capacity` value.
+    pub fn granularity(&self, capacity: u32) -> Option<u32> {
+        let scales = &self.scales[..self.count];
+
+        scales
+            .iter()
+            // Braces are copying the packed struct fields, so no unaligned references are created
+            .find(|scale| capacity <= { scale.Capacity })
+            .or_else(|| scales.last())
+            .map(|scale| scale.Granularity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatteryReportingScales, BATTERY_REPORTING_SCALE};
+
+    fn scales(values: &[(u32, u32)]) -> BatteryReportingScales {
+        let mut scales = BatteryReportingScales::default();
+        for (idx, (granularity, capacity)) in values.iter().enumerate() {
+            scales.scales[idx] = BATTERY_REPORTING_SCALE {
+                Granularity: *granularity,
+                Capacity: *capacity,
+            };
+        }
+        scales.set_len(values.len());
+
+        scales
+    }
+
+    #[test]
+    fn test_granularity() {
+        let scales = scales(&[(10, 5000), (100, 50000)]);
+
+        assert_eq!(scales.granularity(1000), Some(10));
+        assert_eq!(scales.granularity(5000), Some(10));
+        assert_eq!(scales.granularity(20000), Some(100));
+        // Capacities above the last scale are using it too
+        assert_eq!(scales.granularity(60000), Some(100));
+    }
+
+    #[test]
+    fn test_no_scales() {
+        assert_eq!(scales(&[]).granularity(1000), None);
+    }
+}
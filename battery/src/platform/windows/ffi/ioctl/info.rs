@@ -7,11 +7,12 @@ use std::mem;
 use std::ops;
 use std::str::{self, FromStr};
 
-use crate::Technology;
+use crate::{Kind, Technology};
 use winapi::shared::ntdef;
 
 pub const BATTERY_CAPACITY_RELATIVE: ntdef::ULONG = 0x40000000;
 pub const BATTERY_SYSTEM_BATTERY: ntdef::ULONG = 0x80000000;
+pub const BATTERY_IS_SHORT_TERM: ntdef::ULONG = 0x20000000;
 
 STRUCT! {#[cfg_attr(target_arch = "x86", repr(packed))] #[derive(Debug)] struct BATTERY_INFORMATION {
     Capabilities: ntdef::ULONG,
@@ -61,11 +62,29 @@ impl BatteryInformation {
         (self.0.Capabilities & BATTERY_SYSTEM_BATTERY) != 0
     }
 
+    /// UPS are reported as system batteries with a `BATTERY_IS_SHORT_TERM` capability.
+    pub fn kind(&self) -> Kind {
+        if (self.0.Capabilities & BATTERY_IS_SHORT_TERM) != 0 {
+            Kind::Ups
+        } else if self.is_system_battery() {
+            Kind::System
+        } else {
+            Kind::Peripheral
+        }
+    }
+
     #[inline]
     pub fn is_relative(&self) -> bool {
         (self.0.Capabilities & BATTERY_CAPACITY_RELATIVE) != 0
     }
 
+    /// `Technology` field is `0` for nonrechargeable batteries (ex. alkaline)
+    /// and `1` for rechargeable ones.
+    #[inline]
+    pub fn is_rechargeable(&self) -> bool {
+        self.0.Technology != 0
+    }
+
     pub fn technology(&self) -> Technology {
         let raw = unsafe { str::from_utf8_unchecked(&self.0.Chemistry) };
         match Technology::from_str(raw) {
@@ -86,8 +105,15 @@ impl BatteryInformation {
         self.0.FullChargedCapacity
     }
 
+    // Originally `mWh`, zero if manufacturer does not suggest any
+    #[inline]
+    pub fn default_alert1(&self) -> u32 {
+        self.0.DefaultAlert1
+    }
+
     pub fn cycle_count(&self) -> Option<u32> {
-        if self.0.CycleCount == 0 {
+        // `0xFFFFFFFF` is an ACPI "unknown value" marker
+        if self.0.CycleCount == 0 || self.0.CycleCount == 0xFFFFFFFF {
             None
         } else {
             Some(self.0.CycleCount)
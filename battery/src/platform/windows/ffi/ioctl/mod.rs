@@ -5,11 +5,13 @@
 
 use winapi::shared::minwindef;
 
+mod granularity;
 mod info;
 mod query_info;
 mod status;
 mod wait_status;
 
+pub use self::granularity::{BatteryReportingScales, BATTERY_REPORTING_SCALE, MAX_SCALES};
 pub use self::info::BatteryInformation;
 pub use self::query_info::BatteryQueryInformation;
 pub use self::status::BatteryStatus;
@@ -30,9 +32,9 @@ pub mod info_level {
     pub type BATTERY_QUERY_INFORMATION_LEVEL = u32;
 
     //    pub const BatteryInformation: BATTERY_QUERY_INFORMATION_LEVEL = 0;
-    //    pub const BatteryGranularityInformation: BATTERY_QUERY_INFORMATION_LEVEL = 1;
+    pub const BatteryGranularityInformation: BATTERY_QUERY_INFORMATION_LEVEL = 1;
     pub const BatteryTemperature: BATTERY_QUERY_INFORMATION_LEVEL = 2;
-    //    pub const BatteryEstimatedTime: BATTERY_QUERY_INFORMATION_LEVEL = 3;
+    pub const BatteryEstimatedTime: BATTERY_QUERY_INFORMATION_LEVEL = 3;
     pub const BatteryDeviceName: BATTERY_QUERY_INFORMATION_LEVEL = 4;
     //    pub const BatteryManufactureDate: BATTERY_QUERY_INFORMATION_LEVEL = 5;
     pub const BatteryManufactureName: BATTERY_QUERY_INFORMATION_LEVEL = 6;
@@ -2,6 +2,7 @@
 
 #![allow(non_snake_case, clippy::unreadable_literal)]
 
+use std::borrow::Cow;
 use std::default::Default;
 use std::mem;
 use std::ops;
@@ -27,6 +28,26 @@ const BATTERY_DISCHARGING: ntdef::ULONG = 0x00000002;
 /// Indicates that the system has access to AC power, so no batteries are being discharged.
 const BATTERY_POWER_ON_LINE: ntdef::ULONG = 0x00000001;
 
+/// All the `PowerState` flags combinations rendered, indexed by the flags value.
+static POWER_STATES: [&str; 16] = [
+    "0",
+    "POWER_ON_LINE",
+    "DISCHARGING",
+    "POWER_ON_LINE|DISCHARGING",
+    "CHARGING",
+    "POWER_ON_LINE|CHARGING",
+    "DISCHARGING|CHARGING",
+    "POWER_ON_LINE|DISCHARGING|CHARGING",
+    "CRITICAL",
+    "POWER_ON_LINE|CRITICAL",
+    "DISCHARGING|CRITICAL",
+    "POWER_ON_LINE|DISCHARGING|CRITICAL",
+    "CHARGING|CRITICAL",
+    "POWER_ON_LINE|CHARGING|CRITICAL",
+    "DISCHARGING|CHARGING|CRITICAL",
+    "POWER_ON_LINE|DISCHARGING|CHARGING|CRITICAL",
+];
+
 STRUCT! {#[cfg_attr(target_arch = "x86", repr(packed))] #[derive(Debug)] struct BATTERY_STATUS {
     PowerState: ntdef::ULONG,
     Capacity: ntdef::ULONG, // mWh or BATTERY_UNKNOWN_CAPACITY
@@ -94,6 +115,15 @@ impl BatteryStatus {
         }
     }
 
+    /// Renders `PowerState` flags, undocumented bits are appended as a hex value.
+    pub fn power_state(&self) -> Cow<'static, str> {
+        let known = POWER_STATES[(self.0.PowerState & 0x0F) as usize];
+        match self.0.PowerState & !0x0F {
+            0 => Cow::Borrowed(known),
+            unknown => Cow::Owned(format!("{}|{:#010X}", known, unknown)),
+        }
+    }
+
     pub fn voltage(&self) -> Option<u32> {
         if self.0.Voltage == BATTERY_UNKNOWN_VOLTAGE {
             None
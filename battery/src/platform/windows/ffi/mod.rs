@@ -5,6 +5,7 @@ use std::io;
 use std::iter;
 use std::mem;
 use std::ops::DerefMut;
+use std::vec;
 
 use winapi::ctypes;
 use winapi::shared::{basetsd, devguid, minwindef, ntdef, windef, winerror};
@@ -17,6 +18,7 @@ mod wrappers;
 pub(crate) use self::ioctl::BatteryQueryInformation;
 use self::wide_string::WideString;
 use self::wrappers::*;
+use crate::platform::ordering::sort_natural;
 
 #[inline]
 fn get_last_error() -> io::Error {
@@ -24,10 +26,29 @@ fn get_last_error() -> io::Error {
     io::Error::from_raw_os_error(error_type as i32)
 }
 
+/// `SYSTEM_POWER_STATUS.SystemStatusFlag` value when the battery saver is on,
+/// the field is declared as `Reserved1` by `winapi` (and by the pre-Windows 10 SDKs).
+const BATTERY_SAVER_ON: minwindef::BYTE = 1;
+
+/// Checks if the Windows 10 battery saver is on.
+///
+/// Older Windows versions are always reporting zero flag, which means that battery saver is off.
+pub fn battery_saver_active() -> io::Result<bool> {
+    let mut status = winbase::SYSTEM_POWER_STATUS::default();
+    let res = unsafe { winbase::GetSystemPowerStatus(&mut status) };
+
+    if res == 0 {
+        Err(get_last_error())
+    } else {
+        Ok(status.Reserved1 == BATTERY_SAVER_ON)
+    }
+}
+
 #[derive(Debug)]
 pub struct DeviceIterator {
     device: setupapi::HDEVINFO,
-    current: minwindef::DWORD,
+    // Device interfaces indices, ordered by their device instance paths
+    indices: vec::IntoIter<minwindef::DWORD>,
 }
 
 impl DeviceIterator {
@@ -41,16 +62,35 @@ impl DeviceIterator {
             )
         };
         if hdev == handleapi::INVALID_HANDLE_VALUE {
-            Err(get_last_error())
-        } else {
-            Ok(DeviceIterator {
-                device: hdev,
-                current: 0,
-            })
+            return Err(get_last_error());
+        }
+
+        let mut iterator = DeviceIterator {
+            device: hdev,
+            indices: Vec::new().into_iter(),
+        };
+        iterator.indices = iterator.sorted_indices()?.into_iter();
+
+        Ok(iterator)
+    }
+
+    // Enumeration order might change across boots,
+    // so all the device interfaces are fetched first and sorted by their instance paths
+    // in a natural order, so the `#2` instance goes before the `#10` one.
+    fn sorted_indices(&self) -> io::Result<Vec<minwindef::DWORD>> {
+        let mut paths = Vec::new();
+        let mut index = 0;
+        while let Ok(mut interface_data) = self.get_interface_data(index) {
+            let (interface_detail_data, _) = self.get_interface_detail(&mut interface_data)?;
+            paths.push((interface_detail_data.device_path(), index));
+            index += 1;
         }
+        sort_natural(&mut paths, |(path, _)| path);
+
+        Ok(paths.into_iter().map(|(_, index)| index).collect())
     }
 
-    fn get_interface_data(&self) -> io::Result<setupapi::SP_DEVICE_INTERFACE_DATA> {
+    fn get_interface_data(&self, index: minwindef::DWORD) -> io::Result<setupapi::SP_DEVICE_INTERFACE_DATA> {
         let mut data = setupapi::SP_DEVICE_INTERFACE_DATA::default();
         data.cbSize = mem::size_of::<setupapi::SP_DEVICE_INTERFACE_DATA>() as u32;
         let result = unsafe {
@@ -58,7 +98,7 @@ impl DeviceIterator {
                 self.device,
                 ntdef::NULL as *mut setupapi::SP_DEVINFO_DATA,
                 &devguid::GUID_DEVCLASS_BATTERY,
-                self.current,
+                index,
                 &mut data,
             )
         };
@@ -67,7 +107,10 @@ impl DeviceIterator {
         if result == 0 { Err(get_last_error()) } else { Ok(data) }
     }
 
-    fn get_interface_detail(&self, data: &mut setupapi::SP_DEVICE_INTERFACE_DATA) -> io::Result<InterfaceDetailData> {
+    fn get_interface_detail(
+        &self,
+        data: &mut setupapi::SP_DEVICE_INTERFACE_DATA,
+    ) -> io::Result<(InterfaceDetailData, setupapi::SP_DEVINFO_DATA)> {
         let mut buf_size: minwindef::DWORD = 0;
         unsafe {
             setupapi::SetupDiGetDeviceInterfaceDetailW(
@@ -91,6 +134,8 @@ impl DeviceIterator {
         unsafe {
             (*pdidd).cbSize = mem::size_of::<setupapi::SP_DEVICE_INTERFACE_DETAIL_DATA_W>() as u32;
         }
+        let mut devinfo_data = setupapi::SP_DEVINFO_DATA::default();
+        devinfo_data.cbSize = mem::size_of::<setupapi::SP_DEVINFO_DATA>() as u32;
         unsafe {
             setupapi::SetupDiGetDeviceInterfaceDetailW(
                 self.device,
@@ -98,7 +143,7 @@ impl DeviceIterator {
                 pdidd,
                 buf_size,
                 &mut buf_size,
-                0 as setupapi::PSP_DEVINFO_DATA,
+                &mut devinfo_data,
             )
         };
         let result = unsafe { errhandlingapi::GetLastError() };
@@ -106,7 +151,76 @@ impl DeviceIterator {
             return Err(io::Error::from_raw_os_error(result as i32));
         }
 
-        Ok(pdidd.into())
+        Ok((pdidd.into(), devinfo_data))
+    }
+
+    /// Fetches the device properties from the registry, while the device info set is still open.
+    ///
+    /// None of them are mandatory, so the failed reads are not failing the enumeration.
+    fn get_properties(
+        &self,
+        interface_detail_data: &InterfaceDetailData,
+        devinfo_data: &mut setupapi::SP_DEVINFO_DATA,
+    ) -> DeviceProperties {
+        DeviceProperties {
+            display_name: self.get_display_name(devinfo_data),
+            location: self
+                .get_registry_string(devinfo_data, setupapi::SPDRP_LOCATION_INFORMATION)
+                .ok(),
+            device_path: Some(interface_detail_data.device_path()),
+        }
+    }
+
+    /// Fetches the human-facing device name, same as the Device Manager displays:
+    /// friendly name if it is set, or the device description otherwise.
+    fn get_display_name(&self, devinfo_data: &mut setupapi::SP_DEVINFO_DATA) -> Option<String> {
+        self.get_registry_string(devinfo_data, setupapi::SPDRP_FRIENDLYNAME)
+            .or_else(|_| self.get_registry_string(devinfo_data, setupapi::SPDRP_DEVICEDESC))
+            .ok()
+    }
+
+    fn get_registry_string(
+        &self,
+        devinfo_data: &mut setupapi::SP_DEVINFO_DATA,
+        property: minwindef::DWORD,
+    ) -> io::Result<String> {
+        let mut buf_size: minwindef::DWORD = 0;
+        unsafe {
+            setupapi::SetupDiGetDeviceRegistryPropertyW(
+                self.device,
+                devinfo_data,
+                property,
+                ntdef::NULL as minwindef::PDWORD,
+                ntdef::NULL as minwindef::PBYTE,
+                0,
+                &mut buf_size,
+            )
+        };
+        let result = unsafe { errhandlingapi::GetLastError() };
+        if result != winerror::ERROR_INSUFFICIENT_BUFFER {
+            return Err(io::Error::from_raw_os_error(result as i32));
+        }
+
+        // `buf_size` is in bytes and it includes the terminating null character
+        let mut buf = WideString::with_capacity((buf_size as usize + 1) / 2);
+        let res = unsafe {
+            setupapi::SetupDiGetDeviceRegistryPropertyW(
+                self.device,
+                devinfo_data,
+                property,
+                ntdef::NULL as minwindef::PDWORD,
+                buf.as_mut_ptr() as minwindef::PBYTE,
+                (buf.len() * 2) as minwindef::DWORD,
+                &mut buf_size,
+            )
+        };
+        if res == 0 {
+            return Err(get_last_error());
+        }
+
+        buf.truncate(buf_size as usize);
+
+        Ok(buf.into())
     }
 
     fn get_handle(&self, pdidd: &InterfaceDetailData) -> io::Result<Handle> {
@@ -155,10 +269,16 @@ impl DeviceIterator {
     }
 
     pub fn prepare_handle(&self) -> io::Result<Handle> {
-        let mut interface_data = self.get_interface_data()?;
-        let interface_detail_data = self.get_interface_detail(&mut interface_data)?;
+        self.prepare_handle_at(0).map(|(handle, _)| handle)
+    }
+
+    fn prepare_handle_at(&self, index: minwindef::DWORD) -> io::Result<(Handle, DeviceProperties)> {
+        let mut interface_data = self.get_interface_data(index)?;
+        let (interface_detail_data, mut devinfo_data) = self.get_interface_detail(&mut interface_data)?;
+        let handle = self.get_handle(&interface_detail_data)?;
+        let properties = self.get_properties(&interface_detail_data, &mut devinfo_data);
 
-        self.get_handle(&interface_detail_data)
+        Ok((handle, properties))
     }
 }
 
@@ -166,8 +286,9 @@ impl iter::Iterator for DeviceIterator {
     type Item = DeviceHandle;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let mut handle = match self.prepare_handle() {
-            Ok(h) => h,
+        let index = self.indices.next()?;
+        let (mut handle, properties) = match self.prepare_handle_at(index) {
+            Ok(prepared) => prepared,
             Err(_) => return None,
         };
 
@@ -176,14 +297,17 @@ impl iter::Iterator for DeviceIterator {
             Err(_) => return None,
         };
 
-        self.current += 1;
-
         Some(DeviceHandle {
             //            interface_details: interface_detail_data,
             handle: handle,
             tag: tag,
+            properties,
         })
     }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.indices.len()))
+    }
 }
 
 impl Drop for DeviceIterator {
@@ -200,6 +324,19 @@ pub struct DeviceHandle {
     pub handle: Handle,
     // TODO: Carry only `.BatteryTag` field ?
     pub tag: ioctl::BatteryQueryInformation,
+    // Fetched during the enumeration only, since they can't change later
+    pub properties: DeviceProperties,
+}
+
+/// Device properties stored by the SetupAPI, which are not available via the battery IOCTLs.
+#[derive(Debug, Default)]
+pub struct DeviceProperties {
+    /// Friendly name or the device description.
+    pub display_name: Option<String>,
+    /// Location information, as displayed by the Device Manager.
+    pub location: Option<String>,
+    /// Device interface path.
+    pub device_path: Option<String>,
 }
 
 impl DeviceHandle {
@@ -255,9 +392,18 @@ impl DeviceHandle {
 
     // 10ths of a degree Kelvin (or decikelvin)
     pub fn temperature(&mut self) -> io::Result<ntdef::ULONG> {
+        self.query_ulong(ioctl::info_level::BatteryTemperature)
+    }
+
+    // Seconds, estimated by the driver for the current discharge rate
+    pub fn estimated_time(&mut self) -> io::Result<ntdef::ULONG> {
+        self.query_ulong(ioctl::info_level::BatteryEstimatedTime)
+    }
+
+    fn query_ulong(&mut self, level: ioctl::info_level::BATTERY_QUERY_INFORMATION_LEVEL) -> io::Result<ntdef::ULONG> {
         let mut query = ioctl::BatteryQueryInformation::default();
         query.BatteryTag = self.tag.BatteryTag;
-        query.InformationLevel = ioctl::info_level::BatteryTemperature;
+        query.InformationLevel = level;
         let mut out: ntdef::ULONG = 0;
         let mut bytes_returned: minwindef::DWORD = 0;
 
@@ -280,6 +426,38 @@ impl DeviceHandle {
         if res == 0 { Err(get_last_error()) } else { Ok(out) }
     }
 
+    pub fn granularity(&mut self) -> io::Result<ioctl::BatteryReportingScales> {
+        let mut query = ioctl::BatteryQueryInformation::default();
+        query.BatteryTag = self.tag.BatteryTag;
+        query.InformationLevel = ioctl::info_level::BatteryGranularityInformation;
+        let mut out = ioctl::BatteryReportingScales::default();
+        let mut bytes_returned: minwindef::DWORD = 0;
+        let scale_size = mem::size_of::<ioctl::BATTERY_REPORTING_SCALE>();
+
+        let res = unsafe {
+            ioapiset::DeviceIoControl(
+                *self.handle,
+                ioctl::IOCTL_BATTERY_QUERY_INFORMATION,
+                query.deref_mut() as *mut _ as minwindef::LPVOID,
+                // Since wrapper is a newtype struct, `mem::size_of` will be the same as with
+                // underline structure. Yet, this might lead to bug if wrapper structure will change.
+                // TODO: Get memory size of the underline struct directly
+                mem::size_of::<ioctl::BatteryQueryInformation>() as minwindef::DWORD,
+                out.as_mut_ptr() as minwindef::LPVOID,
+                (scale_size * ioctl::MAX_SCALES) as minwindef::DWORD,
+                &mut bytes_returned as *mut _,
+                ntdef::NULL as minwinbase::LPOVERLAPPED,
+            )
+        };
+
+        if res == 0 {
+            Err(get_last_error())
+        } else {
+            out.set_len(bytes_returned as usize / scale_size);
+            Ok(out)
+        }
+    }
+
     pub fn device_name(&mut self) -> io::Result<String> {
         self.query_string(ioctl::info_level::BatteryDeviceName)
     }
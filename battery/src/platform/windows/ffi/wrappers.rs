@@ -1,7 +1,10 @@
 // Wrappers around the FFI things that should be freed later.
 // It is better to Drop than free them manually.
 
+use std::ffi::OsString;
 use std::ops;
+use std::os::windows::ffi::OsStringExt;
+use std::slice;
 
 use winapi::ctypes::c_void;
 use winapi::shared::ntdef;
@@ -16,6 +19,23 @@ impl From<setupapi::PSP_DEVICE_INTERFACE_DETAIL_DATA_W> for InterfaceDetailData
     }
 }
 
+impl InterfaceDetailData {
+    /// Returns the device instance path, which is a null-terminated string
+    /// stored right after the struct header.
+    pub fn device_path(&self) -> String {
+        unsafe {
+            let ptr = (*self.0).DevicePath.as_ptr();
+            let mut len = 0;
+            while *ptr.add(len) != 0 {
+                len += 1;
+            }
+            let raw = OsString::from_wide(slice::from_raw_parts(ptr, len));
+
+            raw.to_string_lossy().to_string()
+        }
+    }
+}
+
 impl ops::Deref for InterfaceDetailData {
     type Target = setupapi::PSP_DEVICE_INTERFACE_DETAIL_DATA_W;
 
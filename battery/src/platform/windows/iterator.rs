@@ -3,12 +3,13 @@ use std::rc::Rc;
 
 use super::{ffi, PowerDevice, PowerManager};
 use crate::platform::traits::BatteryIterator;
-use crate::Result;
+use crate::{KindFilter, Result};
 
 pub struct PowerIterator {
     #[allow(dead_code)]
     manager: Rc<PowerManager>,
     inner: ffi::DeviceIterator,
+    filter: KindFilter,
 }
 
 impl Iterator for PowerIterator {
@@ -19,7 +20,7 @@ impl Iterator for PowerIterator {
             match self.inner.next() {
                 None => return None,
                 Some(handle) => {
-                    match PowerDevice::try_from(handle) {
+                    match PowerDevice::try_from(handle, self.filter) {
                         Ok(Some(device)) => return Some(Ok(device)),
                         Ok(None) => continue,
                         Err(e) => return Some(Err(e)),
@@ -38,11 +39,15 @@ impl BatteryIterator for PowerIterator {
     type Manager = PowerManager;
     type Device = PowerDevice;
 
-    fn new(manager: Rc<Self::Manager>) -> Result<Self> {
+    // All the batteries were always returned for Windows
+    const DEFAULT_FILTER: KindFilter = KindFilter::ALL;
+
+    fn with_filter(manager: Rc<Self::Manager>, filter: KindFilter) -> Result<Self> {
         let inner = ffi::DeviceIterator::new()?;
         Ok(Self {
             manager,
             inner,
+            filter,
         })
     }
 }
@@ -21,11 +21,16 @@ impl BatteryManager for PowerManager {
         let device_handle = ffi::DeviceHandle {
             handle,
             tag: battery_tag,
+            properties: Default::default(),
         };
         device.refresh(device_handle)?;
 
         Ok(())
     }
+
+    fn battery_saver_active(&self) -> Result<Option<bool>> {
+        Ok(Some(ffi::battery_saver_active()?))
+    }
 }
 
 impl fmt::Debug for PowerManager {
@@ -0,0 +1,44 @@
+use num_traits::identities::Zero;
+
+use crate::units::Power;
+use crate::{Battery, State};
+
+/// Picks the battery which is currently supplying the power.
+///
+/// Systems with multiple batteries (ex. ThinkPads with two packs) are usually
+/// discharging them sequentially, and this function helps to find
+/// which one of them is the one to look at for the time estimations.
+///
+/// Battery is considered active if it is discharging at non-zero rate.
+/// During the handoff between packs both of them might report discharging state
+/// for a short time; in that case battery with the highest energy rate wins,
+/// and if rates are equal too, the one with lower index is chosen.
+///
+/// If no battery is discharging (ex. system is on AC power),
+/// charging battery with the highest energy rate is returned instead.
+///
+/// # Returns
+///
+/// Index of the active battery in the `batteries` slice
+/// or `None` if none of them are charging or discharging.
+pub fn active_battery(batteries: &[Battery]) -> Option<usize> {
+    pick(batteries, State::Discharging, true).or_else(|| pick(batteries, State::Charging, false))
+}
+
+fn pick(batteries: &[Battery], state: State, require_rate: bool) -> Option<usize> {
+    let mut active: Option<(usize, Power)> = None;
+
+    for (idx, battery) in batteries.iter().enumerate() {
+        let rate = battery.energy_rate();
+        if battery.state() != state || (require_rate && rate.is_zero()) {
+            continue;
+        }
+
+        match active {
+            Some((_, max_rate)) if max_rate >= rate => {}
+            _ => active = Some((idx, rate)),
+        }
+    }
+
+    active.map(|(idx, _)| idx)
+}
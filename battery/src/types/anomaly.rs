@@ -0,0 +1,84 @@
+use std::fmt;
+
+use num_traits::identities::Zero;
+
+use crate::units::electric_potential::volt;
+use crate::{Battery, State};
+
+/// Lowest voltage considered to be plausible, a bit lower than a single drained NiMH cell has.
+const MIN_PLAUSIBLE_VOLTAGE: f32 = 0.5;
+/// Highest voltage considered to be plausible, e-bikes and power stations are using 48-52 V packs.
+const MAX_PLAUSIBLE_VOLTAGE: f32 = 60.0;
+
+/// Inconsistencies in the information reported by the operating system or battery controller,
+/// and the faults reported by the battery controller itself.
+///
+/// See [Battery::validate](struct.Battery.html#method.validate).
+#[allow(clippy::manual_non_exhaustive)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Anomaly {
+    /// Current energy is greater than the energy of the full battery.
+    EnergyExceedsFull,
+    /// Energy of the full battery is greater than the designed one.
+    HealthAboveDesign,
+    /// Voltage is outside of the plausible range for the batteries.
+    ImplausibleVoltage,
+    /// Designed energy capacity is zero or is not reported at all.
+    ZeroDesignCapacity,
+    /// Battery is reported as charging, while it is full already.
+    ChargingButFull,
+    /// Battery controller reports a permanent failure, see [Battery::is_failed](struct.Battery.html#method.is_failed).
+    PermanentFailure,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Anomaly {
+    // `RangeInclusive::contains` is not available for the MSRV
+    #[allow(clippy::manual_range_contains)]
+    pub(crate) fn detect(battery: &Battery) -> Vec<Anomaly> {
+        let mut anomalies = Vec::new();
+        let energy_full = battery.energy_full();
+        let energy_full_design = battery.energy_full_design();
+        let voltage = battery.voltage().get::<volt>();
+
+        if battery.energy() > energy_full {
+            anomalies.push(Anomaly::EnergyExceedsFull);
+        }
+        if !energy_full_design.is_zero() && energy_full > energy_full_design {
+            anomalies.push(Anomaly::HealthAboveDesign);
+        }
+        if voltage < MIN_PLAUSIBLE_VOLTAGE || voltage > MAX_PLAUSIBLE_VOLTAGE {
+            anomalies.push(Anomaly::ImplausibleVoltage);
+        }
+        if energy_full_design.is_zero() {
+            anomalies.push(Anomaly::ZeroDesignCapacity);
+        }
+        if battery.state() == State::Charging && !energy_full.is_zero() && battery.energy() >= energy_full {
+            anomalies.push(Anomaly::ChargingButFull);
+        }
+        if battery.is_failed() {
+            anomalies.push(Anomaly::PermanentFailure);
+        }
+
+        anomalies
+    }
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            Anomaly::EnergyExceedsFull => "energy exceeds full energy",
+            Anomaly::HealthAboveDesign => "full energy exceeds design energy",
+            Anomaly::ImplausibleVoltage => "implausible voltage",
+            Anomaly::ZeroDesignCapacity => "zero design capacity",
+            Anomaly::ChargingButFull => "charging, but full",
+            Anomaly::PermanentFailure => "permanent failure",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
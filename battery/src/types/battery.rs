@@ -1,10 +1,21 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt;
 use std::ops::{Deref, DerefMut};
+use std::time::{Duration, Instant, SystemTime};
+
+use num_traits::identities::Zero;
 
 use crate::platform::traits::*;
 use crate::platform::Device;
-use crate::units::{ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
-use crate::{State, Technology};
+use crate::units::power::watt;
+use crate::units::ratio::percent;
+use crate::units::time::second;
+use crate::units::{ElectricCharge, ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
+use crate::{
+    Anomaly, Capacity, CapacitySource, ChargePhase, Condition, Config, DisplayConfig, EstimateQuality, EtaComparison,
+    FieldError, Kind, PromptSegment, RateKind, Result, RiskLevel, RoundingMode, Severity, State, Technology,
+};
 
 /// Battery instant information representation.
 ///
@@ -16,9 +27,58 @@ use crate::{State, Technology};
 /// represented as a units from the [uom](https://crates.io/crates/uom) crate.\
 /// If you are unfamiliar with `uom`, check the [units](./units/) module documentation for a few examples
 /// of how to get the values from them.
-pub struct Battery(Device)
+pub struct Battery
 where
-    Device: BatteryDevice;
+    Device: BatteryDevice,
+{
+    device: Device,
+    // When the current `State` was observed for the first time
+    state_since: Instant,
+    // When the device data was successfully loaded for the last time
+    updated_at: Option<SystemTime>,
+    // Configuration of the `Manager` this battery was created by
+    config: Config,
+    // Values failed to be read during the last partial refresh
+    field_errors: Vec<FieldError>,
+    // Start time and energy of the ongoing discharge from the full battery
+    discharge_start: Option<(Instant, Energy)>,
+    // Average power drawn during the last full to empty discharge
+    last_cycle_draw: Option<Power>,
+    // Start time and energy of the ongoing idle period
+    idle_start: Option<(Instant, Energy)>,
+    // Energy loss rate measured during the idle periods
+    self_discharge: Option<Power>,
+    // When the battery had switched from or to discharging during the last `STATE_FLIPS_WINDOW`
+    state_flips: VecDeque<Instant>,
+    // State of charge seen during the previous refresh
+    observed_soc: Ratio,
+    // Smallest state of charge change seen so far and the number of changes seen
+    soc_resolution: Option<Ratio>,
+    soc_changes: usize,
+    // Severity with the hysteresis applied across the refreshes
+    severity: Severity,
+}
+
+/// Cycle count above which the gauge value is considered to be bogus.
+///
+/// Even the LiFePO4 cells are rated for a few thousands of cycles.
+const MAX_PLAUSIBLE_CYCLES: u32 = 10_000;
+/// State of charge, in percents, at or above which the discharge is considered to start from the full battery.
+const CYCLE_START_SOC: f32 = 95.0;
+/// State of charge, in percents, at or below which the discharge is considered to reach the empty battery.
+const CYCLE_END_SOC: f32 = 5.0;
+/// Window in which the discharging state flips are counted for the state stability check.
+const STATE_FLIPS_WINDOW: Duration = Duration::from_secs(5 * 60);
+/// Number of the discharging state flips during the `STATE_FLIPS_WINDOW`, at which the state is unstable.
+const UNSTABLE_STATE_FLIPS: usize = 4;
+/// Number of the state of charge changes to observe before reporting its resolution.
+const MIN_SOC_CHANGES: usize = 3;
+/// Idle period length after which the self-discharge rate is estimated,
+/// shorter ones are dominated by the gauge resolution.
+const MIN_IDLE_DURATION: Duration = Duration::from_secs(60 * 60);
+/// Energy rate, in watts, below which the battery can be idle; self-discharge alone is way below it,
+/// while some drivers are reporting the unknown state for the battery under load.
+const MAX_IDLE_RATE: f32 = 0.5;
 
 impl Battery {
     /// Battery state of charge.
@@ -35,36 +95,147 @@ impl Battery {
     /// instead of the manual calculation, because many device drivers are providing
     /// this value more precisely, and this method takes that into account.
     ///
+    /// On Linux, for charge-based drivers the value is calculated as a `charge_now / charge_full` ratio,
+    /// which is independent from the battery voltage fluctuations; it takes precedence
+    /// over the driver-provided `capacity` attribute and the energy-based calculation.
+    ///
     /// See also:
     ///  * [https://en.wikipedia.org/wiki/State_of_charge](https://en.wikipedia.org/wiki/State_of_charge)
     ///  * [https://www.mpoweruk.com/soc.htm](https://www.mpoweruk.com/soc.htm)
     pub fn state_of_charge(&self) -> Ratio {
-        self.0.state_of_charge()
+        self.device.state_of_charge()
+    }
+
+    /// Relative state of charge, which is the same as the [state_of_charge](#method.state_of_charge).
+    ///
+    /// Smart Battery Data Specification distinguishes the `RelativeStateOfCharge`,
+    /// which is a percentage of the full battery capacity, from the
+    /// [absolute](#method.absolute_soc) one; this method exists to make the distinction explicit.
+    pub fn relative_soc(&self) -> Ratio {
+        self.state_of_charge()
+    }
+
+    /// Absolute state of charge, which is a percentage of the battery *design* capacity,
+    /// same as the Smart Battery `AbsoluteStateOfCharge` value.
+    ///
+    /// Unlike the [relative one](#method.relative_soc), it keeps going down as the battery wears out:
+    /// fully charged battery with a 80% [state of health](#method.state_of_health)
+    /// has 100% relative and 80% absolute state of charge.
+    ///
+    /// Returns `None` if the design capacity is not reported.
+    pub fn absolute_soc(&self) -> Option<Ratio> {
+        self.device.absolute_state_of_charge()
     }
 
     /// Amount of energy currently available in the battery.
     pub fn energy(&self) -> Energy {
-        self.0.energy()
+        self.device.energy()
     }
 
     /// Amount of energy in the battery when it's considered full.
+    ///
+    /// If the driver does not report it, [designed energy](#method.energy_full_design) is used instead,
+    /// see [energy_full_is_estimated](#method.energy_full_is_estimated).
     pub fn energy_full(&self) -> Energy {
-        self.0.energy_full()
+        self.device.energy_full()
+    }
+
+    /// Checks if the [energy_full](#method.energy_full) value is not reported by the driver
+    /// and is substituted with the [designed energy](#method.energy_full_design), so the battery wear is unknown:
+    /// [state of health](#method.state_of_health) is 100% for such batteries.
+    ///
+    ///  * Linux: neither `energy_full` nor `charge_full` sysfs attribute is provided
+    ///  * Other platforms: always `false`, as the full capacity is always reported
+    ///
+    /// Separate state of health value is not reported by any platform, so there is nothing better
+    /// to estimate the full energy from.
+    pub fn energy_full_is_estimated(&self) -> bool {
+        self.device.energy_full_is_estimated()
     }
 
     /// Amount of energy the battery is designed to hold when it's considered full.
     pub fn energy_full_design(&self) -> Energy {
-        self.0.energy_full_design()
+        self.device.energy_full_design()
+    }
+
+    /// Battery capacity in both energy and charge forms, each flagged as reported by the platform
+    /// or converted from the other one.
+    ///
+    /// See [Capacity](struct.Capacity.html) for what is reported natively on each platform.
+    pub fn capacity(&self) -> Capacity {
+        let voltage = self.device.conversion_voltage();
+        // Unknown voltage means that the charge is unknown too, not a zero one
+        let to_charge = |energy: Energy| -> Option<ElectricCharge> {
+            if voltage.is_zero() {
+                None
+            } else {
+                Some(energy / voltage)
+            }
+        };
+        let (charge, charge_full, charge_source) = match (self.device.charge(), self.device.charge_full()) {
+            (Some(charge), Some(charge_full)) => (Some(charge), Some(charge_full), CapacitySource::Native),
+            _ => (
+                to_charge(self.energy()),
+                to_charge(self.energy_full()),
+                CapacitySource::Computed,
+            ),
+        };
+        let energy_source = if self.device.reports_energy() {
+            CapacitySource::Native
+        } else {
+            CapacitySource::Computed
+        };
+
+        Capacity {
+            energy: self.energy(),
+            energy_full: self.energy_full(),
+            energy_source,
+            charge,
+            charge_full,
+            charge_source,
+            voltage,
+        }
     }
 
     /// Amount of energy being drained from the battery.
     pub fn energy_rate(&self) -> Power {
-        self.0.energy_rate()
+        self.device.energy_rate()
+    }
+
+    /// Tells if the [energy rate](#method.energy_rate) is an instant reading or a running average,
+    /// so the consumer can decide if it needs to be smoothed before calculating the time estimates.
+    ///
+    ///  * Linux: `Instantaneous` for the `power_now` and `current_now` sysfs attributes, `Averaged` if only the
+    ///    `power_avg` or `current_avg` one is provided
+    ///  * MacOS: `Averaged`, as the `Amperage` value is averaged by the battery firmware over about a minute
+    ///  * Windows and FreeBSD: `None`, as the rate comes from the ACPI `_BST` method, which is averaged or not
+    ///    depending on the firmware, and there is no way to find it out
+    ///
+    /// Returns `None` on Linux too if the driver does not provide the rate at all.
+    pub fn rate_kind(&self) -> Option<RateKind> {
+        self.device.rate_kind()
     }
 
     /// Battery voltage.
     pub fn voltage(&self) -> ElectricPotential {
-        self.0.voltage()
+        self.device.voltage()
+    }
+
+    /// Minimal voltage the battery is designed for, which is usually the cutoff voltage
+    /// of the empty battery; [voltage](#method.voltage) approaching it means that shutdown is imminent.
+    ///
+    ///  * Linux: `voltage_min_design` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn voltage_min_design(&self) -> Option<ElectricPotential> {
+        self.device.voltage_min_design()
+    }
+
+    /// Maximal voltage the battery is designed for, which is usually the voltage of the fully charged battery.
+    ///
+    ///  * Linux: `voltage_max_design` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn voltage_max_design(&self) -> Option<ElectricPotential> {
+        self.device.voltage_max_design()
     }
 
     /// Gets battery state of health.
@@ -80,46 +251,802 @@ impl Battery {
     ///  * [https://en.wikipedia.org/wiki/State_of_health](https://en.wikipedia.org/wiki/State_of_health)
     ///  * [https://www.mpoweruk.com/soh.htm](https://www.mpoweruk.com/soh.htm)
     pub fn state_of_health(&self) -> Ratio {
-        self.0.state_of_health()
+        self.device.state_of_health()
+    }
+
+    /// At-a-glance battery wear descriptor, combining the [cycle count](#method.cycle_count)
+    /// and the [state of health](#method.state_of_health).
+    ///
+    /// See [Condition](enum.Condition.html) for the thresholds used.
+    pub fn condition(&self) -> Condition {
+        Condition::detect(self)
+    }
+
+    /// Signals that the battery pack might be failing, ex. swollen, and should be replaced,
+    /// aggregated from the [state of health](#method.state_of_health), full energy drift,
+    /// [self-discharge rate](#method.self_discharge_rate) and [temperature](#method.temperature).
+    ///
+    /// Self-discharge rate is known only after an idle period was observed by this instance,
+    /// so the risk might rise later for the same battery data.
+    ///
+    /// See [RiskLevel](enum.RiskLevel.html) for the contributing factors and their thresholds.
+    pub fn failure_risk(&self) -> RiskLevel {
+        RiskLevel::detect(self)
+    }
+
+    /// Error margin of the battery gauge, as reported by the battery itself.
+    ///
+    ///  * MacOS: `MaxErr` value of the Smart Battery
+    ///  * Windows: capacity reporting granularity relative to the full charge capacity
+    ///
+    /// Values might be quite large (ex. 30% or more) before the battery calibration cycle,
+    /// they are returned as is.
+    ///
+    /// Returns `None` if value is not available or is not supported for the current platform.
+    pub fn measurement_error(&self) -> Option<Ratio> {
+        self.device.measurement_error()
     }
 
     /// Battery current state.
     ///
     /// See [State](enum.State.html) enum for possible values.
+    ///
+    /// If the [Config::full_threshold](struct.Config.html#method.full_threshold) was set
+    /// for the [Manager](struct.Manager.html) and the state of charge had reached it,
+    /// battery on external power is reported as [full](enum.State.html#variant.Full).
+    /// Batteries in the unknown state are not considered to be on external power
+    /// if the platform does not report it (ex. FreeBSD, or Linux without the AC adapter supply).
     pub fn state(&self) -> State {
-        self.0.state()
+        if self.is_treated_as_full() {
+            State::Full
+        } else {
+            self.device.state()
+        }
+    }
+
+    fn is_treated_as_full(&self) -> bool {
+        let threshold = match self.config.full_threshold {
+            Some(threshold) => threshold,
+            None => return false,
+        };
+        let state = self.device.state();
+        if state != State::Charging && state != State::Unknown {
+            return false;
+        }
+
+        // Charging battery is on external power even if the platform does not tell it
+        let external_power = self.device.external_power().unwrap_or(state == State::Charging);
+        external_power && self.device.state_of_charge() >= threshold
+    }
+
+    pub(crate) fn with_config(mut self, config: &Config) -> Battery {
+        self.config = config.clone();
+        // Battery is created right before the config is attached, so the timestamps are taken
+        // from the configured clock again
+        self.state_since = self.config.clock.now();
+        self.updated_at = Some(self.config.clock.system_now());
+        self.discharge_start = None;
+        self.idle_start = None;
+        self.state_flips.clear();
+        self.observed_soc = self.state_of_charge();
+        self.soc_resolution = None;
+        self.soc_changes = 0;
+        self.track_cycle(self.state_since);
+        self.track_idle(self.state_since);
+        self.severity = Severity::detect(self.state_of_charge());
+        self
+    }
+
+    /// Current time of the clock configured for this battery.
+    pub(crate) fn now(&self) -> Instant {
+        self.config.clock.now()
+    }
+
+    /// How urgent the current [state of charge](#method.state_of_charge) is.
+    ///
+    /// See [Severity](enum.Severity.html) enum for the thresholds.
+    pub fn severity(&self) -> Severity {
+        Severity::detect(self.state_of_charge())
+    }
+
+    /// Same as the [severity](#method.severity), but tracked across the
+    /// [Manager::refresh](struct.Manager.html#method.refresh) calls with a hysteresis,
+    /// so the state of charge hovering near a threshold does not make it flap between two values.
+    ///
+    /// Severity changes only once the state of charge goes further than the
+    /// [hysteresis margin](struct.Config.html#method.severity_hysteresis) past the threshold:
+    /// with the default `2%` margin, battery becomes `Low` below `18%` and `Normal` again at `22%` or above.
+    pub fn severity_stable(&self) -> Severity {
+        self.severity
+    }
+
+    /// Battery state as it was reported by the operating system during the last refresh.
+    ///
+    /// Intended for debugging the cases when [state](#method.state) value looks wrong:
+    ///
+    ///  * Linux: unmodified content of the `status` sysfs attribute
+    ///  * MacOS: set `ExternalConnected`, `IsCharging` and `FullyCharged` flags, joined with `|`
+    ///  * Windows: set `BATTERY_STATUS.PowerState` flags, joined with `|` (ex. `POWER_ON_LINE|CHARGING`)
+    ///
+    /// Returns `None` if the value is not available or is not supported for the current platform.
+    pub fn state_raw(&self) -> Option<Cow<'_, str>> {
+        self.device.state_raw()
+    }
+
+    /// Checks if the operating system considers this battery to be the power source currently in use.
+    ///
+    /// Unlike the [state](#method.state) value, it reflects the own operating system decision,
+    /// which is helpful to highlight the battery in use for the systems with multiple power sources.
+    ///
+    /// Available only for MacOS, returns `None` for other platforms.
+    ///
+    /// MacOS reports the type of the providing power source for the whole system only,
+    /// so this value is the same for all batteries: it is `true` for each of them while the system
+    /// is running on battery power. Macs have one internal battery, so it matters only if there is
+    /// an additional battery-powered source, ex. an UPS, which is reported as a battery too.
+    pub fn is_active_source(&self) -> Option<bool> {
+        self.device.is_active_source()
+    }
+
+    /// State of charge in integer percents, rounded with the given `mode`.
+    ///
+    /// See [RoundingMode](enum.RoundingMode.html) for the modes description.
+    pub fn state_of_charge_percent(&self, mode: RoundingMode) -> u8 {
+        mode.apply(self.state_of_charge().get::<percent>())
+    }
+
+    /// State of charge in tenths of a percent, from `0` to `1000`, for the consumers without float math
+    /// (ex. embedded displays).
+    ///
+    /// Value is rounded to the nearest integer, half away from zero, ex. `49.94%` becomes `499`
+    /// and `49.95%` becomes `500`.
+    pub fn state_of_charge_permille(&self) -> u16 {
+        let permille = self.state_of_charge().get::<percent>() * 10.0;
+        // Same as for the `RoundingMode`, floating point noise should not shift the result
+        let permille = ((permille * 100.0).round() / 100.0).round();
+
+        // `f32::clamp` requires Rust 1.50
+        if permille <= 0.0 {
+            0
+        } else if permille >= 1_000.0 {
+            1_000
+        } else {
+            permille as u16
+        }
+    }
+
+    /// Data required to render a battery segment in a shell prompt.
+    ///
+    /// See [PromptSegment](struct.PromptSegment.html) for details.
+    pub fn prompt_segment(&self) -> PromptSegment {
+        PromptSegment::new(self, &self.config.display)
+    }
+
+    /// Single-line human-readable battery summary, ex. `BAT0: 87%, charging, full in 1h 05m, 31.5 °C`.
+    ///
+    /// Formatting follows the [Config::display](struct.Config.html#method.display) preferences
+    /// of the [Manager](struct.Manager.html) which created this battery,
+    /// see [summary_line_with](#method.summary_line_with) to override them.
+    pub fn summary_line(&self) -> String {
+        self.config.display.summary_line(self)
+    }
+
+    /// Same as the [summary_line](#method.summary_line), but formatted with the given preferences.
+    pub fn summary_line_with(&self, config: &DisplayConfig) -> String {
+        config.summary_line(self)
+    }
+
+    /// Renders the state of charge as a `[#####-----]` bar for the terminal tools,
+    /// with `width` cells between the brackets; filled cells count is rounded to the nearest integer.
+    ///
+    /// Cell characters can be changed with the [DisplayConfig::bar_chars](struct.DisplayConfig.html#method.bar_chars)
+    /// of the [Manager](struct.Manager.html) which created this battery.
+    pub fn as_percentage_bar(&self, width: usize) -> String {
+        self.config.display.percentage_bar(self, width)
+    }
+
+    /// Checks the battery information for the inconsistencies.
+    ///
+    /// Detected [anomalies](enum.Anomaly.html) usually mean that battery controller
+    /// or operating system are reporting incorrect values, and might be worth
+    /// attaching to the bug reports.
+    ///
+    /// Empty vector is returned if no anomalies were found.
+    pub fn validate(&self) -> Vec<Anomaly> {
+        Anomaly::detect(self)
+    }
+
+    /// Checks if the battery controller reports a permanent battery failure.
+    ///
+    /// Failed battery needs a service, since it might stop providing power long before it is empty;
+    /// same condition is reported by [validate](#method.validate) as the
+    /// [PermanentFailure](enum.Anomaly.html#variant.PermanentFailure) anomaly.
+    ///
+    ///  * MacOS: non-zero `PermanentFailureStatus` or `Permanent Battery Failure` error condition
+    ///  * Other platforms: always `false`
+    pub fn is_failed(&self) -> bool {
+        self.device.is_failed()
+    }
+
+    /// Raw failure status code, as it was reported by the battery controller, intended for the diagnostics tools.
+    ///
+    ///  * MacOS: `PermanentFailureStatus` value of the `AppleSmartBattery` registry entry
+    ///  * Other platforms: not available
+    ///
+    /// Zero code means that no failures are reported.
+    pub fn failure_code(&self) -> Option<u32> {
+        self.device.failure_code()
+    }
+
+    /// Checks if the battery gauge is learning the battery capacity at the moment.
+    ///
+    /// Capacity values are unreliable during the learning and might jump when it is finished,
+    /// so the [state of health](#method.state_of_health) should not be trusted until then.
+    ///
+    ///  * Linux: non-zero `calibrate` sysfs attribute, which is provided by a few drivers only
+    ///  * Other platforms: not available
+    pub fn is_calibrating(&self) -> Option<bool> {
+        self.device.is_calibrating()
+    }
+
+    /// Time passed since the battery has entered its current [state](#method.state).
+    ///
+    /// State is tracked across [Manager::refresh](struct.Manager.html#method.refresh) calls
+    /// and the counter resets each time refresh finds the battery in a different state.
+    ///
+    /// Since it is unknown, when the battery had entered the state
+    /// it was in at the moment of `Battery` creation, time for that initial state
+    /// is counted from the `Battery` creation and should be considered as a lower bound.
+    ///
+    /// If battery state is [unknown](enum.State.html#variant.Unknown), this method will return `None`.
+    pub fn time_in_state(&self) -> Option<Duration> {
+        self.time_in_state_at(self.config.clock.now())
+    }
+
+    pub(crate) fn time_in_state_at(&self, now: Instant) -> Option<Duration> {
+        match self.state() {
+            State::Unknown => None,
+            _ => Some(now.duration_since(self.state_since)),
+        }
+    }
+
+    /// Records the state of charge change since the previous refresh, keeping the smallest one.
+    fn track_soc_resolution(&mut self) {
+        let soc = self.state_of_charge();
+        let change = if soc > self.observed_soc {
+            soc - self.observed_soc
+        } else {
+            self.observed_soc - soc
+        };
+        self.observed_soc = soc;
+        if change.is_zero() {
+            return;
+        }
+
+        self.soc_changes += 1;
+        match self.soc_resolution {
+            Some(resolution) if resolution <= change => {}
+            _ => self.soc_resolution = Some(change),
+        }
+    }
+
+    /// Estimated granularity of the [state of charge](#method.state_of_charge) reported by the gauge,
+    /// ex. `1%` for the gauges reporting the integer percents only, or even `10%` for the coarse ones.
+    ///
+    /// Tools can use it to decide how many decimals are worth displaying, and how much to smooth
+    /// the time estimations, which are jumping along with the state of charge steps.
+    ///
+    /// It is derived from the history: the smallest state of charge change seen across the
+    /// [Manager::refresh](struct.Manager.html#method.refresh) calls. Refreshes should be frequent enough
+    /// to catch the single steps, otherwise the value is overestimated.
+    ///
+    /// Returns `None` until at least 3 changes are observed by this `Battery` instance.
+    pub fn capacity_resolution(&self) -> Option<Ratio> {
+        if self.soc_changes >= MIN_SOC_CHANGES {
+            self.soc_resolution
+        } else {
+            None
+        }
+    }
+
+    fn forget_state_flips(&mut self, now: Instant) {
+        while let Some(flip) = self.state_flips.front() {
+            if now.duration_since(*flip) < STATE_FLIPS_WINDOW {
+                break;
+            }
+            self.state_flips.pop_front();
+        }
+    }
+
+    /// Checks if the battery keeps rapidly switching between discharging and the other states,
+    /// which usually means a marginal charger or a loose connector.
+    ///
+    /// State changes are tracked across [Manager::refresh](struct.Manager.html#method.refresh) calls,
+    /// and only the ones from or to the [discharging](enum.State.html#variant.Discharging) state are counted.
+    /// State is unstable once there were at least 4 such changes during the last 5 minutes,
+    /// ex. the charger was unplugged and plugged back twice; refreshes should be made
+    /// at least every minute or so to notice them.
+    pub fn is_state_unstable(&self) -> bool {
+        let now = self.config.clock.now();
+        let recent = self
+            .state_flips
+            .iter()
+            .filter(|flip| now.duration_since(**flip) < STATE_FLIPS_WINDOW)
+            .count();
+
+        recent >= UNSTABLE_STATE_FLIPS
+    }
+
+    /// Time of the last successful battery information update.
+    ///
+    /// It is set when `Battery` is created and updated on each successful
+    /// [Manager::refresh](struct.Manager.html#method.refresh) call; failed refresh keeps the old
+    /// information and this value intact, so it can be used to find out how old the information is.
+    ///
+    /// Returns `None` if battery information was never loaded.
+    pub fn last_updated(&self) -> Option<SystemTime> {
+        self.updated_at
+    }
+
+    /// Checks if the battery information is older than the
+    /// [Config::max_data_age](struct.Config.html#method.max_data_age) of the [Manager](struct.Manager.html)
+    /// which created this battery.
+    ///
+    /// Stale battery keeps returning the last known values from all the other methods,
+    /// it is up to the caller to decide how to present them (ex. grey out the indicator).
+    ///
+    /// Returns `false` if maximum age was not configured.
+    pub fn is_stale(&self) -> bool {
+        self.is_stale_at(self.config.clock.system_now())
+    }
+
+    pub(crate) fn is_stale_at(&self, now: SystemTime) -> bool {
+        self.config.is_stale(self.updated_at, now)
+    }
+
+    pub(crate) fn mark_updated(&mut self, now: SystemTime) {
+        self.updated_at = Some(now);
+    }
+
+    pub(crate) fn set_field_errors(&mut self, errors: Vec<FieldError>) {
+        self.field_errors = errors;
+    }
+
+    /// Values which had failed to be read during the last [Manager::refresh](struct.Manager.html#method.refresh),
+    /// if the [partial refresh](struct.Config.html#method.partial_refresh) is enabled.
+    ///
+    /// Each error is named after the `Battery` method which value is degraded; such methods are returning
+    /// the value read by the previous refresh, or `None` for the optional values, until the next successful
+    /// refresh.
+    ///
+    /// Empty if all the values were read successfully, or if the partial refresh is disabled.
+    pub fn field_errors(&self) -> &[FieldError] {
+        &self.field_errors
+    }
+
+    /// Resets the state tracking if the battery state had changed since the `previous` one.
+    pub(crate) fn track_state(&mut self, previous: State, now: Instant) {
+        if self.state() != previous {
+            self.state_since = now;
+            if self.state() == State::Discharging || previous == State::Discharging {
+                self.state_flips.push_back(now);
+            }
+        }
+        self.forget_state_flips(now);
+        self.track_soc_resolution();
+        self.track_cycle(now);
+        self.track_idle(now);
+        let margin = self
+            .config
+            .severity_hysteresis
+            .unwrap_or_else(|| Ratio::new::<percent>(2.0));
+        self.severity = Severity::detect_with_hysteresis(self.severity, self.state_of_charge(), margin);
+    }
+
+    /// Tracks the discharge from the full battery, recording its average draw once the battery is empty.
+    fn track_cycle(&mut self, now: Instant) {
+        let soc = self.state_of_charge().get::<percent>();
+        let discharging = self.state() == State::Discharging || self.state() == State::Empty;
+        match self.discharge_start {
+            None if self.state() == State::Discharging && soc >= CYCLE_START_SOC => {
+                self.discharge_start = Some((now, self.energy()));
+            }
+            Some((started, energy)) if discharging && soc <= CYCLE_END_SOC => {
+                let elapsed = duration_to_time(now.duration_since(started));
+                if elapsed.get::<second>() > 0.0 && energy > self.energy() {
+                    self.last_cycle_draw = Some((energy - self.energy()) / elapsed);
+                }
+                self.discharge_start = None;
+            }
+            Some(_) if discharging => {}
+            // Discharge was interrupted or had not started from the full battery
+            _ => self.discharge_start = None,
+        }
+    }
+
+    /// Average power drawn from the battery during the most recent discharge
+    /// from the full battery to the empty one, which is a more typical usage figure
+    /// than the instant [energy rate](#method.energy_rate).
+    ///
+    /// Discharge is tracked across [Manager::refresh](struct.Manager.html#method.refresh) calls:
+    /// it starts when the battery is found discharging at 95% state of charge or above,
+    /// and completes once it is at 5% or below; charging the battery in between drops it.
+    ///
+    /// Returns `None` until such a discharge is observed by this `Battery` instance.
+    pub fn last_cycle_average_draw(&self) -> Option<Power> {
+        self.last_cycle_draw
+    }
+
+    /// Tracks the energy of the idle battery, updating the self-discharge rate once it was idle for long enough.
+    fn track_idle(&mut self, now: Instant) {
+        let idle = (self.state() == State::Full || self.state() == State::Unknown)
+            && self.energy_rate() < Power::new::<watt>(MAX_IDLE_RATE);
+        match self.idle_start {
+            // Charger topping the battery up starts the measurement over
+            Some((_, energy)) if idle && self.energy() > energy => self.idle_start = Some((now, self.energy())),
+            Some((started, energy)) if idle => {
+                let elapsed = now.duration_since(started);
+                if elapsed >= MIN_IDLE_DURATION {
+                    self.self_discharge = Some((energy - self.energy()) / duration_to_time(elapsed));
+                }
+            }
+            None if idle => self.idle_start = Some((now, self.energy())),
+            _ => self.idle_start = None,
+        }
+    }
+
+    /// Rate at which the idle battery loses its energy on its own.
+    ///
+    /// Battery is considered idle while it is neither charging nor discharging, which is
+    /// the [full](enum.State.html#variant.Full) or [unknown](enum.State.html#variant.Unknown)
+    /// ("not charging") state on the external power with a near zero [energy rate](#method.energy_rate).
+    /// Idle periods are tracked across
+    /// [Manager::refresh](struct.Manager.html#method.refresh) calls, and the rate is estimated
+    /// from the energy decline once the battery was idle for at least an hour;
+    /// charger topping the battery up restarts the period. An unusually high rate hints at a failing cell.
+    ///
+    /// Returns `None` until such an idle period is observed by this `Battery` instance,
+    /// after which the latest estimate is kept.
+    pub fn self_discharge_rate(&self) -> Option<Power> {
+        self.self_discharge
     }
 
     /// Battery technology.
     ///
     /// See [Technology](enum.Technology.html) enum for possible values.
+    /// Wrong value reported by the driver can be corrected with the
+    /// [Config::technology_override](struct.Config.html#method.technology_override).
     pub fn technology(&self) -> Technology {
-        self.0.technology()
+        self.config
+            .technology_for(self.vendor(), self.model())
+            .unwrap_or_else(|| self.device.technology())
+    }
+
+    /// Kind of the device the battery is powering: whole system, peripheral device or an UPS.
+    ///
+    /// See [Kind](enum.Kind.html) enum for the per-platform derivation.
+    pub fn kind(&self) -> Kind {
+        self.device.kind()
     }
 
     /// Battery temperature.
+    ///
+    /// If the battery pack has several temperature sensors, this is the primary one,
+    /// which is the sensor the operating system reports as the battery temperature.
     pub fn temperature(&self) -> Option<ThermodynamicTemperature> {
-        self.0.temperature()
+        self.device.temperature()
+    }
+
+    /// Temperature around the battery, as measured by the battery fuel gauge.
+    ///
+    ///  * Linux: `temp_ambient` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn ambient_temperature(&self) -> Option<ThermodynamicTemperature> {
+        self.device.ambient_temperature()
+    }
+
+    /// Minimal battery temperature allowed by the driver.
+    ///
+    ///  * Linux: `temp_min` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn temperature_min(&self) -> Option<ThermodynamicTemperature> {
+        self.device.temperature_min()
+    }
+
+    /// Maximal battery temperature allowed by the driver.
+    ///
+    ///  * Linux: `temp_max` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn temperature_max(&self) -> Option<ThermodynamicTemperature> {
+        self.device.temperature_max()
+    }
+
+    /// Battery temperature, above which the driver raises an alert.
+    ///
+    ///  * Linux: `temp_alert_max` sysfs attribute
+    ///  * Other platforms: not available
+    pub fn temperature_alert_max(&self) -> Option<ThermodynamicTemperature> {
+        self.device.temperature_alert_max()
+    }
+
+    /// Platform attributes of the battery by their names, with the values as they are reported
+    /// by the driver, ex. to include them into the bug reports.
+    ///
+    ///  * Linux: all readable sysfs attributes of the power supply, including the ones which are not exposed by the
+    ///    other methods, and the temperatures (`temp`, `temp_ambient`, `temp_min`, `temp_max` and `temp_alert_max`) in
+    ///    the driver units
+    ///  * Other platforms: empty map
+    ///
+    /// Unlike the other methods, attributes are read from the system on each call.
+    pub fn raw_attributes(&self) -> Result<BTreeMap<String, String>> {
+        self.device.raw_attributes()
     }
 
     /// Number of charge/discharge cycles.
+    ///
+    /// Buggy gauges are reporting the `65535` "unknown value" marker or absurdly large values,
+    /// so counts above `10000` cycles are returned as `None`.
     pub fn cycle_count(&self) -> Option<u32> {
-        self.0.cycle_count()
+        match self.device.cycle_count() {
+            Some(cycles) if cycles > MAX_PLAUSIBLE_CYCLES => {
+                trace_warn!(cycles, "implausible cycle count is ignored");
+                None
+            }
+            cycles => cycles,
+        }
+    }
+
+    /// Remaining energy threshold configured in the battery firmware,
+    /// below which it considers the battery to be low (Smart Battery `RemainingCapacityAlarm`).
+    ///
+    /// Tools might use it to align their own low battery warnings with the firmware ones.
+    ///
+    ///  * Linux: `alarm` sysfs attribute, if it is set
+    ///  * Windows: `BATTERY_INFORMATION.DefaultAlert1` value
+    ///  * Other platforms: not available
+    pub fn capacity_alarm(&self) -> Option<Energy> {
+        self.device.capacity_alarm()
+    }
+
+    /// Sets the [capacity alarm](#method.capacity_alarm) threshold,
+    /// so the firmware raises the low battery event earlier or later.
+    ///
+    /// Energy is converted into the units the device is reporting its capacity in (µWh or µAh),
+    /// written into the `alarm` sysfs attribute and read back to confirm that device accepted it;
+    /// battery information is refreshed as well.
+    ///
+    /// Available for Linux only, which requires root privileges for it,
+    /// and requires the `control` feature to be enabled.
+    ///
+    /// ## Errors
+    ///
+    /// * `PermissionDenied` kind, if the process is not allowed to write the attribute
+    /// * `NotFound` kind, if device does not support the alarm
+    /// * `InvalidData` kind, if device has not accepted the value
+    /// * `Other` kind for the platforms without alarm control
+    #[cfg(feature = "control")]
+    pub fn set_alarm_energy(&mut self, energy: Energy) -> Result<()> {
+        let state = self.state();
+        self.device.set_capacity_alarm(energy)?;
+        let (now, system_now) = (self.config.clock.now(), self.config.clock.system_now());
+        self.track_state(state, now);
+        self.mark_updated(system_now);
+
+        Ok(())
+    }
+
+    /// State of charge at which the charging stops, if the charge limit is configured.
+    ///
+    /// Charge limit explains why the battery is "stuck" below 100% while on external power;
+    /// [time_to_full](#method.time_to_full) and [energy_until_full](#method.energy_until_full)
+    /// are calculated till this threshold.
+    ///
+    ///  * Linux: `charge_control_end_threshold` sysfs attribute
+    ///  * MacOS: SMC `BCLM` key on Intel Macs, or 80% if the `CHWA` limit flag is set on Apple Silicon
+    ///  * Other platforms: not available
+    ///
+    /// MacOS value is a best effort: SMC keys are private Apple API, which may be inaccessible
+    /// (ex. from the sandboxed applications) or change with the firmware updates, in which case it is `None`.
+    /// The temporary 80% hold of the "Optimized Battery Charging" feature is not reported either,
+    /// as it is decided by the `powerd` daemon and is not exposed via IOKit or SMC.
+    pub fn charge_stop_threshold(&self) -> Option<Ratio> {
+        self.device.charge_stop_threshold()
+    }
+
+    /// Maximum charging power the battery is rated for, as specified by its design.
+    ///
+    /// Comparing it with the [energy rate](#method.energy_rate) while charging helps to find out
+    /// if the charging speed is limited by the battery or by the [charger](#method.adapter_power).
+    ///
+    ///  * Linux: `constant_charge_current_max` sysfs attribute multiplied by `constant_charge_voltage_max` or by the
+    ///    design voltage, if the charge voltage is not reported
+    ///  * Other platforms: not available
+    pub fn max_charge_power(&self) -> Option<Power> {
+        self.device.max_charge_power()
+    }
+
+    /// Maximum power the connected external power supply (ex. AC adapter or USB-C charger) can deliver.
+    ///
+    ///  * Linux: `voltage_max` and `current_max` sysfs attributes of the online mains and USB supplies, as reported by
+    ///    the USB Power Delivery drivers; the most powerful supply is used
+    ///  * Other platforms: not available
+    pub fn adapter_power(&self) -> Option<Power> {
+        self.device.adapter_power()
+    }
+
+    /// Rough estimate of which share of the [adapter power](#method.adapter_power)
+    /// goes into the battery while it is charging, in the `0.0..=1.0` range usually.
+    ///
+    /// This is an estimate only: the system load, which is powered by the adapter too,
+    /// and the charging circuit losses are not taken into account.
+    /// A high value while charging slowly means that the adapter is the bottleneck.
+    ///
+    /// Returns `None` if the battery is not charging or the adapter power is unknown.
+    pub fn charge_efficiency_estimate(&self) -> Option<f32> {
+        match (self.state(), self.adapter_power()) {
+            (State::Charging, Some(adapter)) if adapter.get::<watt>() > 0.0 => {
+                Some(self.energy_rate().get::<watt>() / adapter.get::<watt>())
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the fast (rapid) charge mode is active.
+    ///
+    /// Fast charge explains the unusually high charging power and is usually toggled by the vendor tools.
+    ///
+    ///  * Linux: `charge_type` sysfs attribute is `Fast`, as set by the vendor drivers (ex. Dell "Express" charge);
+    ///    Lenovo `ideapad_acpi` driver `rapid_charge` attribute is used if it is missing (enabled `conservation_mode`
+    ///    is reported as the inactive fast charge)
+    ///  * Other platforms: not available
+    ///
+    /// Returns `None` if the vendor driver is not reporting the charge mode.
+    pub fn fast_charge_active(&self) -> Option<bool> {
+        self.device.fast_charge_active()
+    }
+
+    /// Short human-facing battery name assigned by the operating system.
+    ///
+    ///  * Linux: sysfs entry name, ex. `BAT0`
+    ///  * MacOS: IOKit registry entry name, ex. `AppleSmartBattery`
+    ///  * Windows: device friendly name or description, as displayed by the Device Manager
+    ///
+    /// Returns `None` if operating system does not provide it.
+    pub fn name(&self) -> Option<&str> {
+        self.device.name()
+    }
+
+    /// Physical location of the battery as reported by the firmware,
+    /// which helps to tell the internal battery from the dock or the slice one.
+    ///
+    ///  * Windows: device location information, as displayed by the Device Manager
+    ///  * Other platforms: not available
+    pub fn location(&self) -> Option<&str> {
+        self.device.location()
+    }
+
+    /// Operating system path of the battery device.
+    ///
+    ///  * Linux: sysfs device directory, ex. `/sys/class/power_supply/BAT0`
+    ///  * Windows: device interface path, ex. `\\?\ACPI#PNP0C0A#1#{72631e54-78a4-11d0-bcf7-00aa00b7b32a}`
+    ///  * MacOS: not available
+    ///
+    /// Returns `None` if operating system does not provide it.
+    pub fn device_path(&self) -> Option<&str> {
+        self.device.device_path()
+    }
+
+    /// Checks if the battery is rechargeable.
+    ///
+    /// Primary cells (ex. AA or coin cells in peripherals) are not rechargeable,
+    /// so charging-related values are meaningless for them.
+    ///
+    ///  * Linux: batteries with any known [technology](#method.technology) or with the `System` scope are rechargeable;
+    ///    `Device`-scoped batteries of unknown technology, which are reporting `capacity_level` only, are not;
+    ///    otherwise it is unknown
+    ///  * Windows: `BATTERY_INFORMATION.Technology` value
+    ///  * Other platforms: system batteries are always considered to be rechargeable
+    pub fn is_rechargeable(&self) -> Option<bool> {
+        self.device.is_rechargeable()
     }
 
     /// Battery vendor.
     pub fn vendor(&self) -> Option<&str> {
-        self.0.vendor()
+        self.device.vendor()
     }
 
     /// Battery model.
     pub fn model(&self) -> Option<&str> {
-        self.0.model()
+        self.device.model()
     }
 
     /// Battery serial number.
+    ///
+    /// Serial numbers which are not a valid UTF-8 are converted lossily, with the invalid sequences
+    /// replaced by the `U+FFFD REPLACEMENT CHARACTER`; use the
+    /// [serial_number_raw](#method.serial_number_raw) method for the exact value.
     pub fn serial_number(&self) -> Option<&str> {
-        self.0.serial_number()
+        self.device.serial_number()
+    }
+
+    /// Battery serial number as it was reported by the battery, byte for byte.
+    ///
+    /// For the textual serial numbers these are the UTF-8 bytes of the
+    /// [serial_number](#method.serial_number) value, but some batteries are reporting binary ones,
+    /// which can't be matched exactly after the lossy conversion into a string.
+    ///
+    ///  * Linux: `serial_number` sysfs attribute content, without the trailing newline
+    ///  * Other platforms: not available
+    pub fn serial_number_raw(&self) -> Option<Vec<u8>> {
+        self.device.serial_number_raw().map(<[u8]>::to_vec)
+    }
+
+    /// Raw manufacturer data block, as it was reported by the battery gauge, intended for the diagnostics tools.
+    ///
+    /// Contents are vendor-specific and depend on the gauge model and firmware;
+    /// decoding them is up to the caller and is out of scope for this crate.
+    ///
+    ///  * MacOS: `ManufacturerData` value of the `AppleSmartBattery` registry entry
+    ///  * Linux: not available, `power_supply` class does not expose the Smart Battery data blocks
+    ///  * Other platforms: not available
+    pub fn manufacturer_data(&self) -> Option<Vec<u8>> {
+        self.device.manufacturer_data().map(<[u8]>::to_vec)
+    }
+
+    /// Names of the `Battery` methods, which values are missing because the current user
+    /// is not allowed to read them (ex. `"serial_number"`).
+    ///
+    /// Some drivers are making the optional attributes readable by root only; such values are returned
+    /// as `None` instead of failing the whole battery, so the crate stays usable for the unprivileged processes.
+    /// Essential values (energy, voltage, state, etc.) are still failing the battery load or refresh.
+    ///
+    ///  * Linux: vendor, model, serial number and the optional sysfs attributes, as of the last refresh
+    ///  * Other platforms: always empty
+    pub fn restricted_fields(&self) -> Vec<&'static str> {
+        self.device.restricted_fields()
+    }
+
+    /// Identifier of the physical battery, which does not depend on the enumeration order.
+    ///
+    /// If the [serial number](#method.serial_number) is known, it is joined with the
+    /// [vendor](#method.vendor) and [model](#method.model) values available, separated by the `/`
+    /// (ex. `SMP/5B10W13930/1234`); the same battery gets the same identifier after being re-plugged,
+    /// even if the operating system assigns it another [name](#method.name).
+    ///
+    /// Otherwise there is nothing to tell the physical battery apart, so the operating system
+    /// assigned name is used instead, or an empty string if it is unknown too.
+    pub fn stable_id(&self) -> String {
+        match self.serial_number().map(str::trim) {
+            Some(serial) if !serial.is_empty() => [self.vendor(), self.model(), Some(serial)]
+                .iter()
+                .filter_map(|part| part.map(str::trim))
+                .filter(|part| !part.is_empty())
+                .collect::<Vec<_>>()
+                .join("/"),
+            _ => self.name().unwrap_or_default().to_string(),
+        }
+    }
+
+    /// Amount of energy left to be charged till full battery.
+    ///
+    /// This is an energy counterpart of the [Battery::time_to_full](#method.time_to_full) method.
+    ///
+    /// If battery is not charging at the moment, this method will return `None`.
+    pub fn energy_until_full(&self) -> Option<Energy> {
+        if self.is_treated_as_full() {
+            None
+        } else {
+            self.device.energy_until_full()
+        }
+    }
+
+    /// Amount of energy left to be drained till empty battery.
+    ///
+    /// This is an energy counterpart of the [Battery::time_to_empty](#method.time_to_empty) method.
+    ///
+    /// If battery is not discharging at the moment, this method will return `None`.
+    pub fn energy_until_empty(&self) -> Option<Energy> {
+        self.device.energy_until_empty()
     }
 
     /// Remaining time till full battery.
@@ -129,7 +1056,64 @@ impl Battery {
     ///
     /// If battery is not charging at the moment, this method will return `None`.
     pub fn time_to_full(&self) -> Option<Time> {
-        self.0.time_to_full()
+        if self.is_treated_as_full() {
+            None
+        } else {
+            self.device.time_to_full()
+        }
+    }
+
+    /// Remaining time till full battery, corrected for the charge taper.
+    ///
+    /// Li-ion chargers are switching from the constant current to the constant voltage phase
+    /// at roughly 80% of charge, after which charging slows down dramatically,
+    /// so the linear [time_to_full](#method.time_to_full) estimation is too optimistic
+    /// while battery is still in the constant current phase.
+    ///
+    /// This is a heuristic model and not a measurement: energy above the taper threshold
+    /// is assumed to be charged a fixed number of times slower than the energy below it.
+    /// Once the threshold is passed, measured energy rate is already reduced by the charger,
+    /// so the correction fades out linearly and reaches zero at the full battery,
+    /// which keeps the estimation continuous around the threshold.
+    /// Model parameters can be changed with [Config::charge_taper](struct.Config.html#method.charge_taper).
+    ///
+    /// If battery is not charging at the moment, this method will return `None`.
+    pub fn time_to_full_tapered(&self) -> Option<Time> {
+        let linear = self.time_to_full()?;
+        let (threshold, slowdown) = self.charge_taper();
+        let threshold = threshold.get::<percent>().min(100.0);
+        let charge = self.state_of_charge().get::<percent>().min(100.0);
+        if charge >= threshold {
+            if threshold >= 100.0 {
+                return Some(linear);
+            }
+            let fade = (100.0 - charge) / (100.0 - threshold);
+            return Some(linear * (1.0 + (slowdown - 1.0) * fade));
+        }
+
+        let constant_current = threshold - charge;
+        let constant_voltage = 100.0 - threshold;
+
+        Some(linear * ((constant_current + constant_voltage * slowdown) / (constant_current + constant_voltage)))
+    }
+
+    /// Charge taper model parameters, either configured or the default ones.
+    fn charge_taper(&self) -> (Ratio, f32) {
+        self.config.charge_taper.unwrap_or((Ratio::new::<percent>(80.0), 2.0))
+    }
+
+    /// Current phase of the charging, which explains why it slows down near full.
+    ///
+    /// This is a heuristic over the instant values, classified by these rules:
+    ///
+    ///  * Battery which is not [charging](enum.State.html#variant.Charging) or has no current flowing (zero [energy
+    ///    rate](#method.energy_rate)) is not in any phase, `None` is returned
+    ///  * If the [designed maximal voltage](#method.voltage_max_design) is known, battery is in the constant voltage
+    ///    phase once its [voltage](#method.voltage) is within 1% of it, and in the constant current phase otherwise
+    ///  * Otherwise the [charge taper](struct.Config.html#method.charge_taper) threshold is used, same as for the
+    ///    [time_to_full_tapered](#method.time_to_full_tapered): battery at or above it is in the constant voltage phase
+    pub fn charging_phase(&self) -> Option<ChargePhase> {
+        ChargePhase::detect(self, self.charge_taper().0)
     }
 
     /// Remaining time till empty battery.
@@ -139,25 +1123,151 @@ impl Battery {
     ///
     /// If battery is not discharging at the moment, this method will return `None`.
     pub fn time_to_empty(&self) -> Option<Time> {
-        self.0.time_to_empty()
+        self.device.time_to_empty()
+    }
+
+    /// Time to empty estimate reported by the operating system compared with the one calculated by this crate
+    /// from the instant energy rate, the same way as [time_to_empty](#method.time_to_empty) does it on Linux.
+    ///
+    ///  * MacOS: `TimeRemaining` value of the power source
+    ///  * Windows: `BatteryEstimatedTime` value of the battery
+    ///  * Other platforms: not available
+    ///
+    /// Returns `None` if any of the estimates is not available, ex. if battery is not discharging.
+    pub fn eta_comparison(&self) -> Option<EtaComparison> {
+        let reported = self.device.reported_time_to_empty()?;
+        let computed = self.device.computed_time_to_empty()?;
+
+        Some(EtaComparison::new(reported, computed))
+    }
+
+    /// Projected state of charge after the `duration`, if the current energy rate stays the same.
+    ///
+    /// Useful for the planning questions like "will the battery last for the next two hours?".
+    /// Same as the [time_to_full](#method.time_to_full) and [time_to_empty](#method.time_to_empty),
+    /// it is an instant value, which follows the load changes.
+    ///
+    /// Result is limited to the `0..=100%` range, and to the
+    /// [charge stop threshold](#method.charge_stop_threshold) while charging.
+    ///
+    /// Returns `None` if battery is neither charging nor discharging, or if the energy rate is unknown.
+    pub fn projected_soc_after(&self, duration: Duration) -> Option<Ratio> {
+        let energy_full = self.energy_full();
+        let energy_rate = self.energy_rate();
+        if energy_full.value <= 0.0 || energy_rate.value <= 0.0 || !energy_rate.is_finite() {
+            return None;
+        }
+
+        let delta: Ratio = energy_rate * duration_to_time(duration) / energy_full;
+        let soc = self.state_of_charge();
+        let projected = match self.state() {
+            State::Charging => {
+                let limit = match self.charge_stop_threshold() {
+                    Some(threshold) => threshold.min(Ratio::new::<percent>(100.0)),
+                    None => Ratio::new::<percent>(100.0),
+                };
+                // Battery already charged above the limit is not going to be discharged by the charger
+                (soc + delta).min(limit.max(soc))
+            }
+            State::Discharging => soc - delta,
+            _ => return None,
+        };
+
+        Some(
+            projected
+                .max(Ratio::new::<percent>(0.0))
+                .min(Ratio::new::<percent>(100.0)),
+        )
+    }
+
+    /// Checks if the battery charge lasts for at least `duration` with the current energy rate,
+    /// which answers the "will the laptop survive this meeting?" questions.
+    ///
+    /// Same as the [projected_soc_after](#method.projected_soc_after), it is based on the instant energy rate
+    /// and follows the load changes.
+    ///
+    /// Returns `None` if battery is not discharging or if the energy rate is unknown.
+    pub fn will_survive(&self, duration: Duration) -> Option<bool> {
+        let energy_rate = self.energy_rate();
+        if self.state() != State::Discharging || energy_rate.value <= 0.0 || !energy_rate.is_finite() {
+            return None;
+        }
+
+        Some(self.energy() >= energy_rate * duration_to_time(duration))
+    }
+
+    /// Quality of the [time_to_empty](#method.time_to_empty) estimate,
+    /// or `None` if there is no estimate.
+    ///
+    /// Quality is classified as following, first matching rule wins:
+    ///
+    ///  1. `High`, if the estimate is provided by the operating system (MacOS), which averages it over time
+    ///  2. `Low`, if the battery information is [stale](#method.is_stale), has any [anomalies](#method.validate) or the
+    ///     [measurement error](#method.measurement_error) is greater than 5%
+    ///  3. `High`, if the [energy rate](#method.rate_kind) is averaged by the driver or battery firmware
+    ///  4. `Medium` otherwise; the estimate is calculated from the instant energy rate, so it follows the load changes
+    ///
+    /// See [LearnedEstimator](struct.LearnedEstimator.html) for the batteries without estimates.
+    pub fn time_to_empty_quality(&self) -> Option<EstimateQuality> {
+        self.time_to_empty().map(|_| self.estimate_quality())
+    }
+
+    /// Quality of the [time_to_full](#method.time_to_full) estimate,
+    /// or `None` if there is no estimate.
+    ///
+    /// Same rules as for the [time_to_empty_quality](#method.time_to_empty_quality) are applied.
+    pub fn time_to_full_quality(&self) -> Option<EstimateQuality> {
+        self.time_to_full().map(|_| self.estimate_quality())
     }
+
+    fn estimate_quality(&self) -> EstimateQuality {
+        let imprecise = match self.measurement_error() {
+            Some(error) => error > Ratio::new::<percent>(5.0),
+            None => false,
+        };
+        if self.device.reports_time_estimates() {
+            EstimateQuality::High
+        } else if imprecise || self.is_stale() || !self.validate().is_empty() {
+            EstimateQuality::Low
+        } else if self.rate_kind() == Some(RateKind::Averaged) {
+            EstimateQuality::High
+        } else {
+            EstimateQuality::Medium
+        }
+    }
+}
+
+fn duration_to_time(duration: Duration) -> Time {
+    // `Duration::as_secs_f32` requires Rust 1.38
+    Time::new::<second>(duration.as_secs() as f32 + duration.subsec_nanos() as f32 / 1_000_000_000.0)
 }
 
 impl fmt::Debug for Battery {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         f.debug_struct("Battery")
-            .field("impl", &self.0)
+            .field("impl", &self.device)
             // static info
+            .field("name", &self.name())
             .field("vendor", &self.vendor())
             .field("model", &self.model())
             .field("serial_number", &self.serial_number())
             .field("technology", &self.technology())
+            .field("kind", &self.kind())
+            .field("is_rechargeable", &self.is_rechargeable())
             // common information
             .field("state", &self.state())
+            .field("state_raw", &self.state_raw())
+            .field("is_active_source", &self.is_active_source())
             .field("capacity", &self.state_of_health())
+            .field("measurement_error", &self.measurement_error())
             .field("temperature", &self.temperature())
             .field("percentage", &self.state_of_charge())
             .field("cycle_count", &self.cycle_count())
+            .field("capacity_alarm", &self.capacity_alarm())
+            .field("fast_charge_active", &self.fast_charge_active())
+            .field("charge_stop_threshold", &self.charge_stop_threshold())
+            .field("max_charge_power", &self.max_charge_power())
+            .field("adapter_power", &self.adapter_power())
             // energy stats
             .field("energy", &self.energy())
             .field("energy_full", &self.energy_full())
@@ -167,13 +1277,30 @@ impl fmt::Debug for Battery {
             // charge stats
             .field("time_to_full", &self.time_to_full())
             .field("time_to_empty", &self.time_to_empty())
+            .field("last_updated", &self.last_updated())
             .finish()
     }
 }
 
 impl From<Device> for Battery {
     fn from(device: Device) -> Battery {
-        Battery(device)
+        Battery {
+            state_since: Instant::now(),
+            // Platform devices are loading all the data during their creation
+            updated_at: Some(SystemTime::now()),
+            config: Config::default(),
+            field_errors: Vec::new(),
+            discharge_start: None,
+            last_cycle_draw: None,
+            idle_start: None,
+            self_discharge: None,
+            state_flips: VecDeque::new(),
+            observed_soc: device.state_of_charge(),
+            soc_resolution: None,
+            soc_changes: 0,
+            severity: Severity::detect(device.state_of_charge()),
+            device,
+        }
     }
 }
 
@@ -181,12 +1308,12 @@ impl Deref for Battery {
     type Target = Device;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.device
     }
 }
 
 impl DerefMut for Battery {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.0
+        &mut self.device
     }
 }
@@ -0,0 +1,88 @@
+use crate::units::{ElectricCharge, ElectricPotential, Energy};
+
+/// Tells if the [Capacity](struct.Capacity.html) value was reported by the platform or converted from the other one.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum CapacitySource {
+    /// Value is reported by the platform in these units.
+    Native,
+    /// Value is converted from the other one with the [conversion voltage](struct.Capacity.html#method.voltage).
+    Computed,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Battery capacity in both energy (Wh) and charge (Ah) forms.
+///
+/// Batteries are reporting their capacity either as an energy or as a charge, and the other form
+/// is calculated by multiplying or dividing it by a voltage, which is an approximation:
+/// the same charge holds less energy at the lower voltage. This struct tells which of the values
+/// are reported by the platform [natively](enum.CapacitySource.html#variant.Native), and which are
+/// [computed](enum.CapacitySource.html#variant.Computed), so the precision-sensitive consumers can pick the former.
+///
+/// Natively reported values, per platform:
+///
+///  * Linux: energy if the `energy_now` attribute is provided, charge if the `charge_now` one is, or both of them for
+///    some drivers
+///  * MacOS: charge only, as the Smart Battery is configured to report mAh
+///  * Windows: energy only
+///  * FreeBSD: either energy or charge, depending on the ACPI battery units
+///
+/// See [Battery::capacity](struct.Battery.html#method.capacity).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Capacity {
+    pub(crate) energy: Energy,
+    pub(crate) energy_full: Energy,
+    pub(crate) energy_source: CapacitySource,
+    pub(crate) charge: Option<ElectricCharge>,
+    pub(crate) charge_full: Option<ElectricCharge>,
+    pub(crate) charge_source: CapacitySource,
+    pub(crate) voltage: ElectricPotential,
+}
+
+impl Capacity {
+    /// Amount of energy currently stored in the battery, same as the
+    /// [Battery::energy](struct.Battery.html#method.energy).
+    pub fn energy(&self) -> Energy {
+        self.energy
+    }
+
+    /// Amount of energy in the battery when it's considered full, same as the
+    /// [Battery::energy_full](struct.Battery.html#method.energy_full).
+    pub fn energy_full(&self) -> Energy {
+        self.energy_full
+    }
+
+    /// Tells if the energy values are reported by the platform or converted from the charge ones.
+    pub fn energy_source(&self) -> CapacitySource {
+        self.energy_source
+    }
+
+    /// Electric charge currently stored in the battery.
+    ///
+    /// Returns `None` if it is not reported by the platform and can't be computed either,
+    /// because the [conversion voltage](#method.voltage) is unknown.
+    pub fn charge(&self) -> Option<ElectricCharge> {
+        self.charge
+    }
+
+    /// Electric charge in the battery when it's considered full.
+    ///
+    /// Returns `None` in the same cases as the [charge](#method.charge) does.
+    pub fn charge_full(&self) -> Option<ElectricCharge> {
+        self.charge_full
+    }
+
+    /// Tells if the charge values are reported by the platform or converted from the energy ones.
+    pub fn charge_source(&self) -> CapacitySource {
+        self.charge_source
+    }
+
+    /// Voltage used to convert between the energy and the charge values, which is the designed voltage
+    /// if the platform reports it, or the current battery voltage otherwise; zero if neither is known.
+    pub fn voltage(&self) -> ElectricPotential {
+        self.voltage
+    }
+}
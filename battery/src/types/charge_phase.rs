@@ -0,0 +1,64 @@
+use std::fmt;
+
+use num_traits::identities::Zero;
+
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, State};
+
+/// Phase of the Li-ion CC/CV charging, which explains why charging slows down near full.
+///
+/// Charger pushes the constant current first, while the battery voltage rises,
+/// and once the voltage reaches the battery maximum, it holds that voltage
+/// and lets the current taper off till the battery is full.
+///
+/// See [Battery::charging_phase](struct.Battery.html#method.charging_phase).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum ChargePhase {
+    /// Bulk charging with a constant current and a rising voltage.
+    ConstantCurrent,
+    /// Voltage is held at the maximum and the current is tapering off.
+    ConstantVoltage,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl ChargePhase {
+    /// Battery voltage, in percents of the designed maximal voltage, at or above which
+    /// the charger is considered to hold the constant voltage.
+    pub const CONSTANT_VOLTAGE_MIN_RATIO: f32 = 99.0;
+
+    pub(crate) fn detect(battery: &Battery, taper_threshold: Ratio) -> Option<ChargePhase> {
+        if battery.state() != State::Charging || battery.energy_rate().is_zero() {
+            return None;
+        }
+
+        match battery.voltage_max_design() {
+            Some(max) if !max.is_zero() && !battery.voltage().is_zero() => {
+                let ratio = (battery.voltage() / max).get::<percent>();
+                if ratio >= Self::CONSTANT_VOLTAGE_MIN_RATIO {
+                    Some(ChargePhase::ConstantVoltage)
+                } else {
+                    Some(ChargePhase::ConstantCurrent)
+                }
+            }
+            _ if battery.state_of_charge() >= taper_threshold => Some(ChargePhase::ConstantVoltage),
+            _ => Some(ChargePhase::ConstantCurrent),
+        }
+    }
+}
+
+impl fmt::Display for ChargePhase {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            ChargePhase::ConstantCurrent => "constant current",
+            ChargePhase::ConstantVoltage => "constant voltage",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
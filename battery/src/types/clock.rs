@@ -0,0 +1,100 @@
+//! Time source for the timestamps `Manager` and `Battery` are keeping.
+//!
+//! All the time-dependent methods are getting the current time from the clock
+//! carried by the [Config](../struct.Config.html), so tests can replace it with the `FakeClock`
+//! (available for the Linux backend tests only, which are the ones using it)
+//! and move the time forward explicitly instead of sleeping:
+//!
+//! ```ignore
+//! let clock = Arc::new(FakeClock::new());
+//! let manager = Manager::from(SysFsManager::with_root(root.path())).with_clock(clock.clone());
+//! let mut battery = manager.batteries()?.next().unwrap()?;
+//!
+//! clock.advance(Duration::from_secs(60));
+//! manager.refresh(&mut battery)?;
+//! ```
+
+use std::fmt;
+use std::sync::Arc;
+#[cfg(all(test, target_os = "linux"))]
+use std::sync::Mutex;
+#[cfg(all(test, target_os = "linux"))]
+use std::time::Duration;
+use std::time::{Instant, SystemTime};
+
+pub(crate) trait Clock: fmt::Debug + Send + Sync {
+    /// Monotonic time, used for the durations.
+    fn now(&self) -> Instant;
+
+    /// Wall clock time, used for the timestamps exposed to the caller.
+    fn system_now(&self) -> SystemTime;
+}
+
+/// Operating system clock.
+#[derive(Debug)]
+pub(crate) struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn system_now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Clock shared between the `Config` clones, `SystemClock` by default.
+#[derive(Debug, Clone)]
+pub(crate) struct SharedClock(Arc<dyn Clock>);
+
+impl SharedClock {
+    #[cfg(all(test, target_os = "linux"))]
+    pub fn new(clock: Arc<dyn Clock>) -> SharedClock {
+        SharedClock(clock)
+    }
+
+    pub fn now(&self) -> Instant {
+        self.0.now()
+    }
+
+    pub fn system_now(&self) -> SystemTime {
+        self.0.system_now()
+    }
+}
+
+impl Default for SharedClock {
+    fn default() -> SharedClock {
+        SharedClock(Arc::new(SystemClock))
+    }
+}
+
+/// Clock which stays still until moved forward with the `advance` method.
+#[cfg(all(test, target_os = "linux"))]
+#[derive(Debug)]
+pub(crate) struct FakeClock(Mutex<(Instant, SystemTime)>);
+
+#[cfg(all(test, target_os = "linux"))]
+impl FakeClock {
+    /// Creates clock starting at the current system time.
+    pub fn new() -> FakeClock {
+        FakeClock(Mutex::new((Instant::now(), SystemTime::now())))
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut now = self.0.lock().unwrap();
+        now.0 += duration;
+        now.1 += duration;
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+impl Clock for FakeClock {
+    fn now(&self) -> Instant {
+        self.0.lock().unwrap().0
+    }
+
+    fn system_now(&self) -> SystemTime {
+        self.0.lock().unwrap().1
+    }
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use num_traits::identities::Zero;
+
+use crate::units::ratio::percent;
+use crate::Battery;
+
+/// Friendly summary of the battery wear, combining its [cycle count](struct.Battery.html#method.cycle_count)
+/// and [state of health](struct.Battery.html#method.state_of_health) into a single descriptor.
+///
+/// Thresholds are available as the associated constants; the worst of the two values wins,
+/// ex. battery with 98% state of health and 600 cycles is considered `Aged`.
+///
+/// See [Battery::condition](struct.Battery.html#method.condition).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum Condition {
+    /// Designed capacity is not reported, so the state of health can't be calculated.
+    Unknown,
+    /// Barely used battery: less than `NEW_MAX_CYCLES` cycles and more than `NEW_MIN_HEALTH` state of health.
+    New,
+    /// Battery in a normal service.
+    Used,
+    /// Noticeably degraded battery: less than `USED_MIN_HEALTH` state of health
+    /// or at least `AGED_MIN_CYCLES` cycles.
+    Aged,
+    /// Battery due for replacement: less than `AGED_MIN_HEALTH` state of health
+    /// or at least `WORN_MIN_CYCLES` cycles.
+    Worn,
+    /// Battery controller reports a [permanent failure](struct.Battery.html#method.is_failed),
+    /// battery needs a service no matter how worn it is.
+    Failed,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Condition {
+    /// Cycle count below which the battery can be considered `New`.
+    pub const NEW_MAX_CYCLES: u32 = 50;
+    /// State of health, in percents, above which the battery can be considered `New`.
+    pub const NEW_MIN_HEALTH: f32 = 95.0;
+    /// State of health, in percents, below which the battery is `Aged`.
+    ///
+    /// Most of the vendors are considering 80% as the end of the battery service life.
+    pub const USED_MIN_HEALTH: f32 = 80.0;
+    /// State of health, in percents, below which the battery is `Worn`.
+    pub const AGED_MIN_HEALTH: f32 = 60.0;
+    /// Cycle count at which the battery is `Aged`, a typical rated cycle life of the laptop batteries.
+    pub const AGED_MIN_CYCLES: u32 = 500;
+    /// Cycle count at which the battery is `Worn`.
+    pub const WORN_MIN_CYCLES: u32 = 1000;
+
+    pub(crate) fn detect(battery: &Battery) -> Condition {
+        if battery.is_failed() {
+            return Condition::Failed;
+        }
+        if battery.energy_full_design().is_zero() {
+            return Condition::Unknown;
+        }
+
+        let health = battery.state_of_health().get::<percent>();
+        // Unknown cycle count does not make the battery worse, but it can't make it `New` either
+        let cycles = battery.cycle_count();
+        let cycles_at_least = |threshold: u32| cycles.map(|count| count >= threshold).unwrap_or(false);
+
+        if health < Self::AGED_MIN_HEALTH || cycles_at_least(Self::WORN_MIN_CYCLES) {
+            Condition::Worn
+        } else if health < Self::USED_MIN_HEALTH || cycles_at_least(Self::AGED_MIN_CYCLES) {
+            Condition::Aged
+        } else if health > Self::NEW_MIN_HEALTH && cycles.map(|count| count < Self::NEW_MAX_CYCLES).unwrap_or(false) {
+            Condition::New
+        } else {
+            Condition::Used
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            Condition::Unknown => "unknown",
+            Condition::New => "new",
+            Condition::Used => "used",
+            Condition::Aged => "aged",
+            Condition::Worn => "worn",
+            Condition::Failed => "failed",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
@@ -0,0 +1,410 @@
+use std::env;
+use std::io;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime};
+
+use num_traits::identities::Zero;
+
+use super::clock::SharedClock;
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, DisplayConfig, Error, Result, Technology};
+
+/// Configuration for the [Manager](struct.Manager.html).
+///
+/// Default configuration keeps all the values exactly as they are reported by the operating system.
+///
+/// # Example
+///
+/// ```edition2018
+/// # use battery::{Config, Manager, Result};
+/// use battery::units::ratio::percent;
+/// use battery::units::Ratio;
+///
+/// # fn main() -> Result<()> {
+/// let config = Config::new().full_threshold(Ratio::new::<percent>(97.0));
+/// let manager = Manager::with_config(config)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    pub(crate) full_threshold: Option<Ratio>,
+    pub(crate) display: DisplayConfig,
+    pub(crate) max_data_age: Option<Duration>,
+    pub(crate) charge_taper: Option<(Ratio, f32)>,
+    pub(crate) watch_interval: Option<Duration>,
+    pub(crate) partial_refresh: bool,
+    pub(crate) skip_empty: bool,
+    pub(crate) severity_hysteresis: Option<Ratio>,
+    pub(crate) technology_overrides: Vec<(String, Technology)>,
+    pub(crate) clock: SharedClock,
+}
+
+impl Config {
+    /// Creates default configuration.
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    /// Creates configuration from the environment variables, so it can be changed without recompiling,
+    /// ex. for the containerized deployments.
+    ///
+    /// Unset or empty variables are keeping the default values:
+    ///
+    ///  * `BATTERY_FULL_THRESHOLD` - [full threshold](#method.full_threshold), in percents from `0` to `100` (ex. `97`)
+    ///  * `BATTERY_MAX_DATA_AGE` - [max data age](#method.max_data_age), in seconds
+    ///  * `BATTERY_CHARGE_TAPER` - [charge taper](#method.charge_taper) threshold in percents and slowdown, separated
+    ///    by a comma (ex. `80,2.0`)
+    ///  * `BATTERY_WATCH_INTERVAL` - [watch interval](#method.watch_interval), in seconds, at least `1`
+    ///  * `BATTERY_PARTIAL_REFRESH` - enables the [partial refresh](#method.partial_refresh) with `1`, `true` or `yes`,
+    ///    disables with `0`, `false` or `no`
+    ///  * `BATTERY_SKIP_EMPTY` - enables the [phantom batteries skipping](#method.skip_empty), same values as for the
+    ///    `BATTERY_PARTIAL_REFRESH`
+    ///
+    /// Invalid values are failing with the `io::ErrorKind::InvalidInput` error, which names the variable.
+    ///
+    /// # Example
+    ///
+    /// ```edition2018,no_run
+    /// # use battery::{Config, Manager, Result};
+    /// # fn main() -> Result<()> {
+    /// let manager = Manager::with_config(Config::from_env()?)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_env() -> Result<Config> {
+        Config::from_vars(|name| env::var(name).ok())
+    }
+
+    pub(crate) fn from_vars<F>(var: F) -> Result<Config>
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let var = |name: &'static str| match var(name) {
+            Some(ref value) if !value.trim().is_empty() => Some((name, value.trim().to_string())),
+            _ => None,
+        };
+        let mut config = Config::new();
+
+        if let Some((name, value)) = var("BATTERY_FULL_THRESHOLD") {
+            let threshold = parse_var(name, &value)?;
+            if !is_valid_percent(threshold) {
+                return Err(invalid_var(name, &value));
+            }
+            config = config.full_threshold(Ratio::new::<percent>(threshold));
+        }
+        if let Some((name, value)) = var("BATTERY_MAX_DATA_AGE") {
+            config = config.max_data_age(Duration::from_secs(parse_var(name, &value)?));
+        }
+        if let Some((name, value)) = var("BATTERY_CHARGE_TAPER") {
+            let mut parts = value.splitn(2, ',');
+            match (parts.next(), parts.next()) {
+                (Some(threshold), Some(slowdown)) => {
+                    let threshold = parse_var(name, threshold.trim())?;
+                    let slowdown = parse_var(name, slowdown.trim())?;
+                    if !is_valid_taper(Ratio::new::<percent>(threshold), slowdown) {
+                        return Err(invalid_var(name, &value));
+                    }
+                    config = config.charge_taper(Ratio::new::<percent>(threshold), slowdown);
+                }
+                _ => return Err(invalid_var(name, &value)),
+            }
+        }
+        if let Some((name, value)) = var("BATTERY_WATCH_INTERVAL") {
+            // Zero interval would make the watcher spin
+            match parse_var(name, &value)? {
+                0 => return Err(invalid_var(name, &value)),
+                interval => config = config.watch_interval(Duration::from_secs(interval)),
+            }
+        }
+        if let Some((name, value)) = var("BATTERY_PARTIAL_REFRESH") {
+            config = config.partial_refresh(parse_flag(name, &value)?);
+        }
+        if let Some((name, value)) = var("BATTERY_SKIP_EMPTY") {
+            config = config.skip_empty(parse_flag(name, &value)?);
+        }
+
+        Ok(config)
+    }
+
+    /// Sets the state of charge, at or above which the battery on external power
+    /// is considered to be [full](enum.State.html#variant.Full).
+    ///
+    /// Batteries with charge limits or with a drifted gauge are rarely reaching 100%,
+    /// so it is up to the caller to decide what is "full enough".
+    ///
+    /// Threshold is applied only if battery reports [charging](enum.State.html#variant.Charging)
+    /// or [unknown](enum.State.html#variant.Unknown) state (which is how the "not charging" state
+    /// is reported by the most platforms) and the external power is connected;
+    /// [Battery::time_to_full](struct.Battery.html#method.time_to_full)
+    /// and [Battery::energy_until_full](struct.Battery.html#method.energy_until_full) are returning `None`
+    /// for such batteries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not in the `0%..=100%` range.
+    pub fn full_threshold(mut self, threshold: Ratio) -> Config {
+        assert!(is_valid_percent(threshold.get::<percent>()), "Invalid full threshold");
+        self.full_threshold = Some(threshold);
+        self
+    }
+
+    /// Sets the maximum age of the battery information, after which
+    /// [Battery::is_stale](struct.Battery.html#method.is_stale) starts returning `true`.
+    ///
+    /// Information age is counted from the [Battery::last_updated](struct.Battery.html#method.last_updated) time,
+    /// so the battery goes stale when the [Manager::refresh](struct.Manager.html#method.refresh) calls are failing
+    /// (or not made at all) for longer than `age`.
+    pub fn max_data_age(mut self, age: Duration) -> Config {
+        self.max_data_age = Some(age);
+        self
+    }
+
+    /// Sets the parameters of the heuristic charge taper model used by the
+    /// [Battery::time_to_full_tapered](struct.Battery.html#method.time_to_full_tapered) method:
+    ///
+    ///  * `threshold` - state of charge, where the charger switches from the constant current to the constant voltage
+    ///    phase (`80%` by default)
+    ///  * `slowdown` - how many times slower the energy above the `threshold` is charged (`2.0` by default)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `threshold` is not in the `0%..=100%` range or `slowdown` is less than `1.0`.
+    pub fn charge_taper(mut self, threshold: Ratio, slowdown: f32) -> Config {
+        assert!(is_valid_taper(threshold, slowdown), "Invalid charge taper parameters");
+        self.charge_taper = Some((threshold, slowdown));
+        self
+    }
+
+    /// Sets how often the watcher spawned with the [Manager::spawn_watcher](struct.Manager.html#method.spawn_watcher)
+    /// checks the batteries (`1s` by default).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `interval` is zero, which would make the watcher spin.
+    pub fn watch_interval(mut self, interval: Duration) -> Config {
+        assert!(interval > Duration::from_secs(0), "Invalid watch interval");
+        self.watch_interval = Some(interval);
+        self
+    }
+
+    /// Enables the partial [Manager::refresh](struct.Manager.html#method.refresh), disabled by default.
+    ///
+    /// By default any failed battery value read fails the whole refresh. With the partial refresh,
+    /// failed reads are collected into the [Battery::field_errors](struct.Battery.html#method.field_errors)
+    /// instead, and the battery is updated with the values which were read successfully.
+    /// Degraded values keep the value read by the previous refresh, optional ones are reported as missing
+    /// (`None`), and the refresh still fails if the battery itself is gone.
+    ///
+    /// Only Linux reads the battery values one by one, other platforms are failing the whole refresh as usual.
+    pub fn partial_refresh(mut self, enabled: bool) -> Config {
+        self.partial_refresh = enabled;
+        self
+    }
+
+    /// Enables skipping of the phantom batteries during the enumeration, disabled by default.
+    ///
+    /// Some firmwares are exposing an extra battery, which is always empty or absent.
+    /// With this option enabled, batteries which report zero for both
+    /// [Battery::energy_full_design](struct.Battery.html#method.energy_full_design) and
+    /// [Battery::energy_full](struct.Battery.html#method.energy_full) are not yielded
+    /// by the [Manager](struct.Manager.html) enumeration methods. Batteries with just one of these values
+    /// missing (ex. without design capacity reported) and drained batteries are kept.
+    pub fn skip_empty(mut self, enabled: bool) -> Config {
+        self.skip_empty = enabled;
+        self
+    }
+
+    /// Sets how far past a threshold the state of charge should go to change the
+    /// [Battery::severity_stable](struct.Battery.html#method.severity_stable) value, in the absolute terms
+    /// (`2%` by default).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `margin` is negative or NaN.
+    pub fn severity_hysteresis(mut self, margin: Ratio) -> Config {
+        assert!(margin.get::<percent>() >= 0.0, "Invalid severity hysteresis");
+        self.severity_hysteresis = Some(margin);
+        self
+    }
+
+    /// Forces the [Battery::technology](struct.Battery.html#method.technology) value for the batteries
+    /// which [vendor](struct.Battery.html#method.vendor) or [model](struct.Battery.html#method.model)
+    /// contains the `pattern`, which corrects the drivers reporting the wrong chemistry.
+    ///
+    /// Pattern is matched as a case-sensitive substring, so `"5B10"` matches the `5B10W13930` model,
+    /// and an exact value is matched too. If multiple overrides are matching the battery,
+    /// the one added first wins. Empty pattern matches any battery with either value reported.
+    ///
+    /// # Example
+    ///
+    /// ```edition2018
+    /// # use battery::{Config, Technology};
+    /// let config = Config::new().technology_override("DELL 7FHHV", Technology::LithiumPolymer);
+    /// ```
+    pub fn technology_override(mut self, pattern: &str, technology: Technology) -> Config {
+        self.technology_overrides.push((pattern.to_string(), technology));
+        self
+    }
+
+    /// Sets the formatting preferences used by the [Battery::summary_line](struct.Battery.html#method.summary_line).
+    pub fn display(mut self, display: DisplayConfig) -> Config {
+        self.display = display;
+        self
+    }
+
+    /// Finds the technology override matching the `vendor` or `model`.
+    pub(crate) fn technology_for(&self, vendor: Option<&str>, model: Option<&str>) -> Option<Technology> {
+        let matches = |pattern: &str| {
+            vendor.map(|vendor| vendor.contains(pattern)).unwrap_or(false)
+                || model.map(|model| model.contains(pattern)).unwrap_or(false)
+        };
+
+        self.technology_overrides
+            .iter()
+            .find(|(pattern, _)| matches(pattern))
+            .map(|(_, technology)| *technology)
+    }
+
+    /// Checks if the data updated at `updated_at` is older than the maximum data age at `now`.
+    pub(crate) fn is_stale(&self, updated_at: Option<SystemTime>, now: SystemTime) -> bool {
+        match (self.max_data_age, updated_at) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            // `SystemTime` is not monotonic, data from the "future" is considered to be fresh
+            (Some(max_age), Some(updated_at)) => match now.duration_since(updated_at) {
+                Ok(age) => age > max_age,
+                Err(_) => false,
+            },
+        }
+    }
+
+    /// Checks if `battery` should not be yielded by the enumeration.
+    pub(crate) fn skips(&self, battery: &Battery) -> bool {
+        self.skip_empty && battery.energy_full_design().is_zero() && battery.energy_full().is_zero()
+    }
+}
+
+fn is_valid_taper(threshold: Ratio, slowdown: f32) -> bool {
+    is_valid_percent(threshold.get::<percent>()) && slowdown >= 1.0
+}
+
+/// Checks that the value is in the `0..=100` range, which also rules out NaN.
+// `RangeInclusive::contains` is not available for the MSRV
+#[allow(clippy::manual_range_contains)]
+fn is_valid_percent(value: f32) -> bool {
+    value >= 0.0 && value <= 100.0
+}
+
+fn parse_var<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| invalid_var(name, value))
+}
+
+fn parse_flag(name: &str, value: &str) -> Result<bool> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" => Ok(true),
+        "0" | "false" | "no" => Ok(false),
+        _ => Err(invalid_var(name, value)),
+    }
+}
+
+fn invalid_var(name: &str, value: &str) -> Error {
+    Error::new(
+        io::Error::from(io::ErrorKind::InvalidInput),
+        format!("Invalid `{}` environment variable value `{}`", name, value),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::io;
+    use std::time::Duration;
+
+    use super::Config;
+    use crate::units::ratio::percent;
+    use crate::units::Ratio;
+
+    fn from_vars(vars: &[(&str, &str)]) -> crate::Result<Config> {
+        let vars = vars.iter().cloned().collect::<HashMap<_, _>>();
+        Config::from_vars(|name| vars.get(name).map(|value| value.to_string()))
+    }
+
+    #[test]
+    fn test_from_vars() {
+        let config = from_vars(&[
+            ("BATTERY_FULL_THRESHOLD", "97"),
+            ("BATTERY_MAX_DATA_AGE", "30"),
+            ("BATTERY_CHARGE_TAPER", "75, 3.5"),
+            ("BATTERY_WATCH_INTERVAL", " 5 "),
+            ("BATTERY_PARTIAL_REFRESH", "Yes"),
+            ("BATTERY_SKIP_EMPTY", "0"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.full_threshold, Some(Ratio::new::<percent>(97.0)));
+        assert_eq!(config.max_data_age, Some(Duration::from_secs(30)));
+        assert_eq!(config.charge_taper, Some((Ratio::new::<percent>(75.0), 3.5)));
+        assert_eq!(config.watch_interval, Some(Duration::from_secs(5)));
+        assert!(config.partial_refresh);
+        assert!(!config.skip_empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_charge_taper_invalid() {
+        let _ = Config::new().charge_taper(Ratio::new::<percent>(80.0), 0.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_full_threshold_invalid() {
+        let _ = Config::new().full_threshold(Ratio::new::<percent>(150.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_watch_interval_zero() {
+        let _ = Config::new().watch_interval(Duration::from_secs(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_severity_hysteresis_negative() {
+        let _ = Config::new().severity_hysteresis(Ratio::new::<percent>(-2.0));
+    }
+
+    #[test]
+    fn test_from_vars_defaults() {
+        let config = from_vars(&[("BATTERY_FULL_THRESHOLD", ""), ("BATTERY_SKIP_EMPTY", " ")]).unwrap();
+
+        assert_eq!(config.full_threshold, None);
+        assert_eq!(config.max_data_age, None);
+        assert_eq!(config.charge_taper, None);
+        assert!(!config.skip_empty);
+    }
+
+    #[test]
+    fn test_from_vars_invalid() {
+        for vars in [
+            ("BATTERY_FULL_THRESHOLD", "97%"),
+            ("BATTERY_FULL_THRESHOLD", "NaN"),
+            ("BATTERY_FULL_THRESHOLD", "101"),
+            ("BATTERY_FULL_THRESHOLD", "-5"),
+            ("BATTERY_MAX_DATA_AGE", "-1"),
+            ("BATTERY_CHARGE_TAPER", "80"),
+            ("BATTERY_CHARGE_TAPER", "80,0.5"),
+            ("BATTERY_CHARGE_TAPER", "80,NaN"),
+            ("BATTERY_CHARGE_TAPER", "120,2"),
+            ("BATTERY_WATCH_INTERVAL", "0"),
+            ("BATTERY_SKIP_EMPTY", "on"),
+        ]
+        .iter()
+        {
+            let err = from_vars(&[*vars]).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+            assert!(err.to_string().contains(vars.0));
+        }
+    }
+}
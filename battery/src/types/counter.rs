@@ -0,0 +1,32 @@
+use num_traits::identities::Zero;
+
+use crate::units::Energy;
+use crate::{Battery, State};
+
+/// Sums the energy drained from the batteries between their refreshes.
+#[derive(Debug)]
+pub(crate) struct EnergyCounter {
+    total: Energy,
+}
+
+impl EnergyCounter {
+    pub fn new() -> EnergyCounter {
+        EnergyCounter {
+            total: Energy::zero(),
+        }
+    }
+
+    /// Adds the energy drained since the `previous` energy of the just refreshed `battery`, if it is discharging.
+    ///
+    /// Energy gained while charging is not subtracted.
+    pub fn observe(&mut self, previous: Energy, battery: &Battery) {
+        let energy = battery.energy();
+        if battery.state() == State::Discharging && previous > energy {
+            self.total += previous - energy;
+        }
+    }
+
+    pub fn total(&self) -> Energy {
+        self.total
+    }
+}
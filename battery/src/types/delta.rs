@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+
+/// Batteries added and removed since the previous enumeration.
+///
+/// This struct is created by the [Manager::diff_enumeration](struct.Manager.html#method.diff_enumeration) method.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct EnumerationDelta {
+    added: Vec<String>,
+    removed: Vec<String>,
+    current: Vec<String>,
+}
+
+impl EnumerationDelta {
+    pub(crate) fn new(previous: &[String], current: Vec<String>) -> EnumerationDelta {
+        let known = previous.iter().map(String::as_str).collect::<HashSet<_>>();
+        let seen = current.iter().map(String::as_str).collect::<HashSet<_>>();
+
+        let added = current
+            .iter()
+            .filter(|id| !known.contains(id.as_str()))
+            .cloned()
+            .collect();
+        let removed = previous
+            .iter()
+            .filter(|id| !seen.contains(id.as_str()))
+            .cloned()
+            .collect();
+
+        EnumerationDelta {
+            added,
+            removed,
+            current,
+        }
+    }
+
+    /// Stable identifiers of the batteries which were not in the previous list,
+    /// in the enumeration order.
+    pub fn added(&self) -> &[String] {
+        &self.added
+    }
+
+    /// Stable identifiers from the previous list, which batteries are gone,
+    /// in the previous list order.
+    pub fn removed(&self) -> &[String] {
+        &self.removed
+    }
+
+    /// Stable identifiers of all the currently available batteries, in the enumeration order.
+    ///
+    /// Pass them as the `previous` list to the next `diff_enumeration` call.
+    pub fn current(&self) -> &[String] {
+        &self.current
+    }
+
+    /// Consumes the delta, returning the current stable identifiers.
+    pub fn into_current(self) -> Vec<String> {
+        self.current
+    }
+
+    /// Checks if no batteries were added or removed.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Write;
+
+use crate::units::human::{HumanPercent, HumanTemperature, HumanTime};
+use crate::units::ratio::percent;
+use crate::units::time::minute;
+use crate::units::Time;
+use crate::Battery;
+
+/// Temperature measurement unit used by the [DisplayConfig](struct.DisplayConfig.html).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum TemperatureUnit {
+    Celsius,
+    Fahrenheit,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` enum variants require Rust 1.62
+impl Default for TemperatureUnit {
+    fn default() -> Self {
+        TemperatureUnit::Celsius
+    }
+}
+
+/// Time formatting style used by the [DisplayConfig](struct.DisplayConfig.html).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum TimeFormat {
+    /// Hours and minutes, ex. `1h 05m` or `45m`, see [HumanTime](units/human/struct.HumanTime.html)
+    HoursMinutes,
+    /// Clock-like format, ex. `1:05`
+    Clock,
+    /// Total amount of minutes, ex. `65 min`
+    Minutes,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` enum variants require Rust 1.62
+impl Default for TimeFormat {
+    fn default() -> Self {
+        TimeFormat::HoursMinutes
+    }
+}
+
+/// Rounding of the fractional percentage into an integer one.
+///
+/// Operating systems are not agreeing on how to display the battery percentage,
+/// so the mode can be picked to match the reference tool; for example, with `49.5%` charge
+/// `Floor` and `Round` are giving `49%` and `50%` respectively.
+///
+/// Values are rounded to a thousandth of percent first, so the floating point noise
+/// (ex. `28.999998%` instead of `29%`) does not shift the result.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum RoundingMode {
+    /// Rounds down, ex. `49.5%` becomes `49%` and `49.9%` becomes `49%`,
+    /// as in the Linux `capacity` sysfs attribute.
+    Floor,
+    /// Rounds to the nearest integer, half away from zero,
+    /// ex. `49.4%` becomes `49%` and `49.5%` becomes `50%`.
+    Round,
+    /// Rounds up, ex. `49.1%` becomes `50%` and `49.5%` becomes `50%`.
+    Ceil,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl RoundingMode {
+    /// Rounds `value` (in percents) according to the mode, clamping it into the `0..=100` range.
+    pub fn apply(self, value: f32) -> u8 {
+        let value = (value * 1_000.0).round() / 1_000.0;
+        let rounded = match self {
+            RoundingMode::Floor => value.floor(),
+            RoundingMode::Ceil => value.ceil(),
+            _ => value.round(),
+        };
+
+        // `f32::clamp` requires Rust 1.50
+        if rounded <= 0.0 {
+            0
+        } else if rounded >= 100.0 {
+            100
+        } else {
+            rounded as u8
+        }
+    }
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` enum variants require Rust 1.62
+impl Default for RoundingMode {
+    fn default() -> Self {
+        RoundingMode::Round
+    }
+}
+
+/// Characters of the [Battery::as_percentage_bar](struct.Battery.html#method.as_percentage_bar) filled
+/// and empty cells.
+const DEFAULT_BAR_CHARS: (char, char) = ('#', '-');
+
+/// Formatting preferences for the [Battery::summary_line_with](struct.Battery.html#method.summary_line_with) method.
+///
+/// Default configuration uses Celsius degrees, `1h 05m` time format and hides the serial number.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct DisplayConfig {
+    temperature_unit: TemperatureUnit,
+    time_format: TimeFormat,
+    show_serial: bool,
+    rounding_mode: RoundingMode,
+    bar_chars: Option<(char, char)>,
+}
+
+impl DisplayConfig {
+    /// Creates default configuration.
+    pub fn new() -> DisplayConfig {
+        DisplayConfig::default()
+    }
+
+    /// Sets the temperature measurement unit.
+    pub fn temperature_unit(mut self, unit: TemperatureUnit) -> DisplayConfig {
+        self.temperature_unit = unit;
+        self
+    }
+
+    /// Sets the remaining time formatting style.
+    pub fn time_format(mut self, format: TimeFormat) -> DisplayConfig {
+        self.time_format = format;
+        self
+    }
+
+    /// Sets whether the battery serial number should be displayed.
+    pub fn show_serial(mut self, show: bool) -> DisplayConfig {
+        self.show_serial = show;
+        self
+    }
+
+    /// Sets the rounding of the state of charge percentage (`RoundingMode::Round` by default).
+    ///
+    /// It is used by the summary line and by the [PromptSegment](struct.PromptSegment.html).
+    pub fn rounding_mode(mut self, mode: RoundingMode) -> DisplayConfig {
+        self.rounding_mode = mode;
+        self
+    }
+
+    /// Sets the characters of the [percentage bar](struct.Battery.html#method.as_percentage_bar)
+    /// filled and empty cells (`#` and `-` by default).
+    pub fn bar_chars(mut self, fill: char, empty: char) -> DisplayConfig {
+        self.bar_chars = Some((fill, empty));
+        self
+    }
+
+    pub(crate) fn percent(&self, battery: &Battery) -> u8 {
+        self.rounding_mode.apply(battery.state_of_charge().get::<percent>())
+    }
+
+    pub(crate) fn summary_line(&self, battery: &Battery) -> String {
+        let name = battery.name().or_else(|| battery.model()).unwrap_or("battery");
+        let mut line = format!(
+            "{}: {}, {}",
+            name,
+            HumanPercent(battery.state_of_charge(), self.rounding_mode),
+            battery.state()
+        );
+
+        // Writing into `String` never fails
+        if let Some(time) = battery.time_to_full() {
+            let _ = write!(line, ", full in {}", self.format_time(time));
+        } else if let Some(time) = battery.time_to_empty() {
+            let _ = write!(line, ", empty in {}", self.format_time(time));
+        }
+        if let Some(temperature) = battery.temperature() {
+            let _ = write!(line, ", {}", HumanTemperature(temperature, self.temperature_unit));
+        }
+        if self.show_serial {
+            if let Some(serial) = battery.serial_number() {
+                let _ = write!(line, ", serial {}", serial);
+            }
+        }
+
+        line
+    }
+
+    pub(crate) fn percentage_bar(&self, battery: &Battery, width: usize) -> String {
+        let (fill, empty) = self.bar_chars.unwrap_or(DEFAULT_BAR_CHARS);
+        let soc = battery.state_of_charge().get::<percent>();
+        // Same as `RoundingMode::Round`, but for the cells count
+        let filled = (soc / 100.0 * width as f32).round().max(0.0).min(width as f32) as usize;
+
+        let mut bar = String::with_capacity(width + 2);
+        bar.push('[');
+        for idx in 0..width {
+            bar.push(if idx < filled { fill } else { empty });
+        }
+        bar.push(']');
+
+        bar
+    }
+
+    fn format_time(&self, time: Time) -> String {
+        let minutes = time.get::<minute>().round() as u64;
+        match self.time_format {
+            TimeFormat::Clock => format!("{}:{:02}", minutes / 60, minutes % 60),
+            TimeFormat::Minutes => format!("{} min", minutes),
+            _ => HumanTime(time).to_string(),
+        }
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::units::ratio::percent;
+use crate::units::time::second;
+use crate::units::{Ratio, Time};
+use crate::{Battery, State};
+
+/// Origin of the [Estimate](struct.Estimate.html) value.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum EstimateSource {
+    /// Calculated from the state of charge changes observed over time,
+    /// see [LearnedEstimator](struct.LearnedEstimator.html).
+    Learned,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// How much the time estimate can be trusted, from the least to the most trustworthy.
+///
+/// See [Battery::time_to_empty_quality](struct.Battery.html#method.time_to_empty_quality)
+/// and [Estimate::quality](struct.Estimate.html#method.quality) for the classification rules.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum EstimateQuality {
+    /// Estimate is based on the questionable data and might be way off.
+    Low,
+    /// Estimate is based on the consistent data, but follows the load changes.
+    Medium,
+    /// Estimate is averaged by the operating system or battery firmware.
+    High,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Time estimate tagged with its [source](enum.EstimateSource.html) and [quality](enum.EstimateQuality.html).
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct Estimate {
+    time: Time,
+    source: EstimateSource,
+    quality: EstimateQuality,
+}
+
+impl Estimate {
+    /// Estimated time.
+    pub fn time(&self) -> Time {
+        self.time
+    }
+
+    /// How the time was estimated.
+    pub fn source(&self) -> EstimateSource {
+        self.source
+    }
+
+    /// How much the estimate can be trusted.
+    ///
+    /// Learned estimates are of a `Medium` quality, unless samples deviation from the fitted line
+    /// is more than a half of the [allowed one](struct.LearnedEstimator.html#method.max_deviation),
+    /// in which case they are of a `Low` quality.
+    pub fn quality(&self) -> EstimateQuality {
+        self.quality
+    }
+}
+
+/// Opt-in time estimator for batteries, which are reporting the state of charge only.
+///
+/// Peripheral batteries (ex. wireless mice) are usually not providing any energy or rate values,
+/// so [Battery::time_to_empty](struct.Battery.html#method.time_to_empty) and
+/// [Battery::time_to_full](struct.Battery.html#method.time_to_full) are always `None` for them.
+/// This estimator collects the state of charge samples and fits a line through them with
+/// the least squares method, extrapolating it to the empty or full battery.
+///
+/// Estimates are available only when:
+///
+///  * samples are covering at least the [minimum window](#method.min_window) (`10 min` by default) and there are at
+///    least three of them,
+///  * state of charge changes in the direction matching the battery state,
+///  * samples deviate from the fitted line by no more than the [maximum deviation](#method.max_deviation) (`2%` by
+///    default) on average.
+///
+/// Collected samples are dropped when the battery state changes.
+///
+/// # Example
+///
+/// ```edition2018,no_run
+/// # use battery::{LearnedEstimator, Manager, Result};
+/// # use std::{thread, time::Duration};
+/// # fn main() -> Result<()> {
+/// let manager = Manager::new()?;
+/// let mut battery = manager.batteries()?.next().unwrap()?;
+/// let mut estimator = LearnedEstimator::new();
+/// loop {
+///     estimator.push(&battery);
+///     if let Some(estimate) = estimator.time_to_empty() {
+///         println!("{:?} left", estimate.time());
+///     }
+///     thread::sleep(Duration::from_secs(60));
+///     manager.refresh(&mut battery)?;
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LearnedEstimator {
+    min_window: Duration,
+    max_deviation: f32,
+    state: State,
+    // Sample time and state of charge in percents
+    samples: VecDeque<(Instant, f32)>,
+}
+
+impl Default for LearnedEstimator {
+    fn default() -> Self {
+        LearnedEstimator {
+            min_window: Duration::from_secs(10 * 60),
+            max_deviation: 2.0,
+            state: State::Unknown,
+            samples: VecDeque::new(),
+        }
+    }
+}
+
+impl LearnedEstimator {
+    /// Creates estimator with the default parameters.
+    pub fn new() -> LearnedEstimator {
+        LearnedEstimator::default()
+    }
+
+    /// Sets the minimum time span the samples should cover before the estimates are available.
+    ///
+    /// Samples older than two windows are dropped.
+    pub fn min_window(mut self, window: Duration) -> LearnedEstimator {
+        self.min_window = window;
+        self
+    }
+
+    /// Sets the maximum root-mean-square deviation of the samples from the fitted line,
+    /// above which the estimates are suppressed.
+    pub fn max_deviation(mut self, deviation: Ratio) -> LearnedEstimator {
+        self.max_deviation = deviation.get::<percent>();
+        self
+    }
+
+    /// Adds the current battery state of charge as a sample.
+    pub fn push(&mut self, battery: &Battery) {
+        self.push_sample(battery.now(), battery.state(), battery.state_of_charge());
+    }
+
+    /// Adds the state of charge sample taken at the given time.
+    ///
+    /// Samples are expected to be pushed in a chronological order.
+    pub fn push_sample(&mut self, at: Instant, state: State, charge: Ratio) {
+        if state != self.state {
+            self.state = state;
+            self.samples.clear();
+        }
+
+        self.samples.push_back((at, charge.get::<percent>()));
+        while let Some(&(oldest, _)) = self.samples.front() {
+            if at.duration_since(oldest) > self.min_window * 2 {
+                self.samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Estimated time until the battery is discharged.
+    pub fn time_to_empty(&self) -> Option<Estimate> {
+        match (self.state, self.fit()) {
+            (State::Discharging, Some((slope, current, deviation))) if slope < 0.0 => {
+                Some(self.estimate(current / -slope, deviation))
+            }
+            _ => None,
+        }
+    }
+
+    /// Estimated time until the battery is fully charged.
+    pub fn time_to_full(&self) -> Option<Estimate> {
+        match (self.state, self.fit()) {
+            (State::Charging, Some((slope, current, deviation))) if slope > 0.0 => {
+                Some(self.estimate((100.0 - current).max(0.0) / slope, deviation))
+            }
+            _ => None,
+        }
+    }
+
+    fn estimate(&self, seconds: f32, deviation: f32) -> Estimate {
+        let quality = if deviation > self.max_deviation / 2.0 {
+            EstimateQuality::Low
+        } else {
+            EstimateQuality::Medium
+        };
+
+        Estimate {
+            time: Time::new::<second>(seconds),
+            source: EstimateSource::Learned,
+            quality,
+        }
+    }
+
+    /// Fits the samples with a line, returning its slope (in percents per second),
+    /// the fitted state of charge at the last sample time and the samples deviation from the line.
+    fn fit(&self) -> Option<(f32, f32, f32)> {
+        let (first, _) = *self.samples.front()?;
+        let (last, _) = *self.samples.back()?;
+        if self.samples.len() < 3 || last.duration_since(first) < self.min_window {
+            return None;
+        }
+
+        let points = self
+            .samples
+            .iter()
+            .map(|&(at, charge)| (seconds(at.duration_since(first)), f64::from(charge)))
+            .collect::<Vec<_>>();
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / count;
+        let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / count;
+        let covariance = points.iter().map(|&(x, y)| (x - mean_x) * (y - mean_y)).sum::<f64>();
+        let variance = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum::<f64>();
+        let slope = covariance / variance;
+        let intercept = mean_y - slope * mean_x;
+
+        let squared_error = points
+            .iter()
+            .map(|&(x, y)| (y - (intercept + slope * x)).powi(2))
+            .sum::<f64>();
+        let deviation = (squared_error / count).sqrt();
+        if deviation > f64::from(self.max_deviation) {
+            return None;
+        }
+
+        let (last_x, _) = points[points.len() - 1];
+        Some((slope as f32, (intercept + slope * last_x) as f32, deviation as f32))
+    }
+}
+
+// `Duration::as_secs_f64` requires Rust 1.38
+fn seconds(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1_000_000_000.0
+}
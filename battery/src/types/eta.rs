@@ -0,0 +1,36 @@
+use crate::units::Time;
+
+/// Time to empty estimate reported by the operating system side by side with the one calculated by this crate.
+///
+/// Useful to find out which one of the estimates is off, and to pick the one to show.
+///
+/// See [Battery::eta_comparison](struct.Battery.html#method.eta_comparison).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct EtaComparison {
+    reported: Time,
+    computed: Time,
+}
+
+impl EtaComparison {
+    pub(crate) fn new(reported: Time, computed: Time) -> EtaComparison {
+        EtaComparison {
+            reported,
+            computed,
+        }
+    }
+
+    /// Estimate provided by the operating system or battery firmware, which is usually averaged over time.
+    pub fn reported(&self) -> Time {
+        self.reported
+    }
+
+    /// Estimate calculated by this crate from the current energy and the instant energy rate.
+    pub fn computed(&self) -> Time {
+        self.computed
+    }
+
+    /// Reported estimate minus the computed one: positive if the operating system is more optimistic.
+    pub fn difference(&self) -> Time {
+        self.reported - self.computed
+    }
+}
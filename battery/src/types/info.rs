@@ -0,0 +1,167 @@
+use crate::units::electric_potential::volt;
+use crate::units::energy::watt_hour;
+use crate::units::power::watt;
+use crate::units::ratio::percent;
+use crate::units::thermodynamic_temperature::degree_celsius;
+use crate::units::time::second;
+use crate::units::{ElectricPotential, Energy, Power, Ratio, ThermodynamicTemperature, Time};
+use crate::{Battery, State, Technology};
+
+/// Plain data snapshot of the [Battery](struct.Battery.html) values.
+///
+/// Unlike the `Battery`, snapshot is not bound to the platform device, so it can be stored,
+/// sent between threads or, with the `serde` feature enabled, serialized and read back later.
+///
+/// Serialized field names are carrying the measurement units of the values
+/// (ex. `energy_wh` or `temperature_celsius`); missing optional values are serialized as `null`
+/// and can be omitted. Fields added in the later crate versions are optional,
+/// so the snapshots written by the older versions stay loadable.
+///
+/// With the `schemars` feature enabled, `JsonSchema` is implemented for the serialized representation.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct BatteryInfo {
+    id: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    vendor: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    model: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    serial_number: Option<String>,
+    state: State,
+    technology: Technology,
+    #[cfg_attr(feature = "serde", serde(rename = "state_of_charge_percent"))]
+    state_of_charge: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "state_of_health_percent"))]
+    state_of_health: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "energy_wh"))]
+    energy: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "energy_full_wh"))]
+    energy_full: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "energy_full_design_wh"))]
+    energy_full_design: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "energy_rate_w"))]
+    energy_rate: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "voltage_v"))]
+    voltage: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "temperature_celsius", default))]
+    temperature: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    cycle_count: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(rename = "time_to_full_secs", default))]
+    time_to_full: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(rename = "time_to_empty_secs", default))]
+    time_to_empty: Option<f32>,
+}
+
+impl BatteryInfo {
+    /// Battery identifier, see [Battery::stable_id](struct.Battery.html#method.stable_id).
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Battery manufacturer name.
+    pub fn vendor(&self) -> Option<&str> {
+        self.vendor.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Battery model name.
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Battery serial number.
+    pub fn serial_number(&self) -> Option<&str> {
+        self.serial_number.as_ref().map(AsRef::as_ref)
+    }
+
+    /// Battery state at the snapshot time.
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Battery technology.
+    pub fn technology(&self) -> Technology {
+        self.technology
+    }
+
+    /// State of charge at the snapshot time.
+    pub fn state_of_charge(&self) -> Ratio {
+        Ratio::new::<percent>(self.state_of_charge)
+    }
+
+    /// State of health at the snapshot time.
+    pub fn state_of_health(&self) -> Ratio {
+        Ratio::new::<percent>(self.state_of_health)
+    }
+
+    /// Amount of energy in the battery at the snapshot time.
+    pub fn energy(&self) -> Energy {
+        Energy::new::<watt_hour>(self.energy)
+    }
+
+    /// Amount of energy in the battery when it is considered full.
+    pub fn energy_full(&self) -> Energy {
+        Energy::new::<watt_hour>(self.energy_full)
+    }
+
+    /// Amount of energy the battery is designed to hold when it is considered full.
+    pub fn energy_full_design(&self) -> Energy {
+        Energy::new::<watt_hour>(self.energy_full_design)
+    }
+
+    /// Amount of energy being drained from the battery at the snapshot time.
+    pub fn energy_rate(&self) -> Power {
+        Power::new::<watt>(self.energy_rate)
+    }
+
+    /// Battery voltage at the snapshot time.
+    pub fn voltage(&self) -> ElectricPotential {
+        ElectricPotential::new::<volt>(self.voltage)
+    }
+
+    /// Battery temperature, if reported.
+    pub fn temperature(&self) -> Option<ThermodynamicTemperature> {
+        self.temperature.map(ThermodynamicTemperature::new::<degree_celsius>)
+    }
+
+    /// Number of charge/discharge cycles, if reported.
+    pub fn cycle_count(&self) -> Option<u32> {
+        self.cycle_count
+    }
+
+    /// Remaining time till full battery at the snapshot time.
+    pub fn time_to_full(&self) -> Option<Time> {
+        self.time_to_full.map(Time::new::<second>)
+    }
+
+    /// Remaining time till empty battery at the snapshot time.
+    pub fn time_to_empty(&self) -> Option<Time> {
+        self.time_to_empty.map(Time::new::<second>)
+    }
+}
+
+impl<'a> From<&'a Battery> for BatteryInfo {
+    fn from(battery: &'a Battery) -> BatteryInfo {
+        BatteryInfo {
+            id: battery.stable_id(),
+            vendor: battery.vendor().map(ToString::to_string),
+            model: battery.model().map(ToString::to_string),
+            serial_number: battery.serial_number().map(ToString::to_string),
+            state: battery.state(),
+            technology: battery.technology(),
+            state_of_charge: battery.state_of_charge().get::<percent>(),
+            state_of_health: battery.state_of_health().get::<percent>(),
+            energy: battery.energy().get::<watt_hour>(),
+            energy_full: battery.energy_full().get::<watt_hour>(),
+            energy_full_design: battery.energy_full_design().get::<watt_hour>(),
+            energy_rate: battery.energy_rate().get::<watt>(),
+            voltage: battery.voltage().get::<volt>(),
+            temperature: battery.temperature().map(|value| value.get::<degree_celsius>()),
+            cycle_count: battery.cycle_count(),
+            time_to_full: battery.time_to_full().map(|value| value.get::<second>()),
+            time_to_empty: battery.time_to_empty().map(|value| value.get::<second>()),
+        }
+    }
+}
@@ -1,39 +1,87 @@
 use std::fmt;
 
+use crate::instrument::Span;
 use crate::platform::Iterator as PlatformIterator;
-use crate::{Battery, Result};
+use crate::{Battery, Config, Error, Result};
 
 /// An iterator that yields batteries available in system.
 ///
-/// This struct is created by the [Manager::batteries](struct.Manager.html#method.batteries) method.
+/// This struct is created by the [Manager::batteries](struct.Manager.html#method.batteries) method
+/// or by iterating over the `&Manager`.
 /// See its documentation for more.
 #[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
-pub struct Batteries(PlatformIterator);
+pub struct Batteries {
+    inner: Inner,
+    config: Config,
+    // Single span covers all the batteries yielded
+    span: Span,
+}
+
+enum Inner {
+    Platform(PlatformIterator),
+    // Platform iterator construction had failed, error is yielded once
+    Failed(Option<Error>),
+}
+
+impl Batteries {
+    pub(crate) fn new(inner: PlatformIterator, config: Config) -> Batteries {
+        Batteries {
+            inner: Inner::Platform(inner),
+            config,
+            span: trace_new_span!("enumerate"),
+        }
+    }
+
+    pub(crate) fn failed(e: Error) -> Batteries {
+        Batteries {
+            inner: Inner::Failed(Some(e)),
+            config: Config::default(),
+            span: trace_new_span!("enumerate"),
+        }
+    }
+}
 
 impl Iterator for Batteries {
     type Item = Result<Battery>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        match self.0.next() {
-            Some(Ok(device)) => Some(Ok(device.into())),
-            Some(Err(e)) => Some(Err(e)),
-            None => None,
+        let _span = trace_enter!(self.span);
+        let inner = match self.inner {
+            Inner::Platform(ref mut inner) => inner,
+            Inner::Failed(ref mut e) => return e.take().map(Err),
+        };
+
+        loop {
+            return match inner.next() {
+                Some(Ok(device)) => {
+                    let battery = Battery::from(device).with_config(&self.config);
+                    if self.config.skips(&battery) {
+                        continue;
+                    }
+                    Some(Ok(battery))
+                }
+                Some(Err(e)) => Some(Err(e)),
+                None => None,
+            };
         }
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.0.size_hint()
-    }
-}
-
-impl From<PlatformIterator> for Batteries {
-    fn from(inner: PlatformIterator) -> Batteries {
-        Batteries(inner)
+        match self.inner {
+            // Skipped batteries are not known in advance
+            Inner::Platform(ref inner) if self.config.skip_empty => (0, inner.size_hint().1),
+            Inner::Platform(ref inner) => inner.size_hint(),
+            Inner::Failed(Some(_)) => (1, Some(1)),
+            Inner::Failed(None) => (0, Some(0)),
+        }
     }
 }
 
 impl fmt::Debug for Batteries {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Batteries").field("impl", &self.0).finish()
+        match self.inner {
+            Inner::Platform(ref inner) => f.debug_struct("Batteries").field("impl", inner).finish(),
+            Inner::Failed(ref e) => f.debug_struct("Batteries").field("error", e).finish(),
+        }
     }
 }
@@ -0,0 +1,118 @@
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+/// Kind of the battery, a portable classification of the device it is powering.
+///
+/// Derived from the same information as the [KindFilter](struct.KindFilter.html) used for the enumeration:
+///
+///  * Linux: `type` and `scope` sysfs attributes; batteries with the `Unknown` scope are of unknown kind
+///  * MacOS: `IOPMPowerSource` services are always the system batteries
+///  * Windows: battery capabilities, `BATTERY_IS_SHORT_TERM` ones are UPS
+///  * FreeBSD and DragonFlyBSD: ACPI batteries are always the system batteries
+///
+/// See [Battery::kind](struct.Battery.html#method.kind).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum Kind {
+    Unknown,
+    /// Battery powering the whole system, ex. laptop battery.
+    System,
+    /// Battery powering a specific device, ex. wireless mouse or keyboard.
+    Peripheral,
+    /// Uninterruptible power supply.
+    Ups,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Kind {
+    /// Filter selecting the batteries of this kind, if there is one.
+    #[cfg(any(target_os = "linux", target_os = "windows"))]
+    pub(crate) fn filter(self) -> Option<KindFilter> {
+        match self {
+            Kind::System => Some(KindFilter::SYSTEM),
+            Kind::Peripheral => Some(KindFilter::PERIPHERAL),
+            Kind::Ups => Some(KindFilter::UPS),
+            _ => None,
+        }
+    }
+}
+
+#[allow(clippy::derivable_impls)] // `#[default]` enum variants require Rust 1.62
+impl Default for Kind {
+    fn default() -> Self {
+        Kind::Unknown
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            Kind::System => "system",
+            Kind::Peripheral => "peripheral",
+            Kind::Ups => "ups",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
+
+/// Set of the battery kinds to enumerate with the
+/// [Manager::batteries_filtered](struct.Manager.html#method.batteries_filtered) method.
+///
+/// Kinds can be combined with the `|` operator.
+///
+/// # Example
+///
+/// ```edition2018
+/// # use battery::{KindFilter, Manager, Result};
+/// # fn main() -> Result<()> {
+/// let manager = Manager::new()?;
+/// for battery in manager.batteries_filtered(KindFilter::SYSTEM | KindFilter::UPS)? {
+///     println!("{:?}", battery?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct KindFilter(u8);
+
+impl KindFilter {
+    /// Batteries powering the whole system, ex. laptop batteries.
+    pub const SYSTEM: KindFilter = KindFilter(0b001);
+
+    /// Batteries powering a specific device, ex. wireless mouse or keyboard.
+    ///
+    /// Available for Linux and Windows only.
+    pub const PERIPHERAL: KindFilter = KindFilter(0b010);
+
+    /// Uninterruptible power supplies.
+    ///
+    /// Available for Linux and Windows only.
+    pub const UPS: KindFilter = KindFilter(0b100);
+
+    /// All the battery kinds.
+    pub const ALL: KindFilter = KindFilter(0b111);
+
+    /// Checks if all the kinds from `other` are selected by this filter.
+    pub fn contains(self, other: KindFilter) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for KindFilter {
+    type Output = KindFilter;
+
+    fn bitor(self, rhs: KindFilter) -> KindFilter {
+        KindFilter(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for KindFilter {
+    fn bitor_assign(&mut self, rhs: KindFilter) {
+        self.0 |= rhs.0;
+    }
+}
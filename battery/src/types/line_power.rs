@@ -0,0 +1,38 @@
+/// External line power supply (ex. AC adapter) information.
+///
+/// This struct is created by the [Manager::line_power](struct.Manager.html#method.line_power) method.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct LinePower {
+    name: String,
+    online: Option<bool>,
+    charge_type: Option<String>,
+}
+
+impl LinePower {
+    #[cfg(target_os = "linux")]
+    pub(crate) fn new(name: String, online: Option<bool>, charge_type: Option<String>) -> LinePower {
+        LinePower {
+            name,
+            online,
+            charge_type,
+        }
+    }
+
+    /// Power supply name, as provided by the operating system (ex. `AC` or `ADP1`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Checks if the power supply is connected, `None` if it is not reported.
+    pub fn is_online(&self) -> Option<bool> {
+        self.online
+    }
+
+    /// Charging algorithm the power supply negotiated, as reported by the operating system
+    /// (ex. `Fast`, `Standard` or `Trickle`).
+    ///
+    /// Returns `None` if it is not reported or reported as unknown.
+    pub fn charge_type(&self) -> Option<&str> {
+        self.charge_type.as_ref().map(AsRef::as_ref)
+    }
+}
@@ -1,10 +1,21 @@
+use std::cell::RefCell;
 use std::fmt;
+#[cfg(target_os = "linux")]
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::mpsc;
+use std::time::Duration;
 
+use super::counter::EnergyCounter;
+use super::watcher::flatten;
 use crate::platform::traits::*;
 use crate::platform::Iterator as PlatformIterator;
 use crate::platform::Manager as PlatformManager;
-use crate::{Batteries, Battery, Result};
+use crate::units::{Energy, Power};
+use crate::{
+    Batteries, Battery, BatteryInfo, Config, EnumerationDelta, Event, KindFilter, LinePower, Result,
+    SystemBatteryReport, ThresholdEvent, ThresholdWatcher, WatcherHandle,
+};
 
 /// Manager for batteries available in system.
 ///
@@ -21,35 +32,479 @@ use crate::{Batteries, Battery, Result};
 /// # Ok(())
 /// # }
 /// ```
+///
+/// Reference to the `Manager` can be iterated directly too,
+/// see the [IntoIterator](#impl-IntoIterator) implementation for details:
+///
+/// ```edition2018
+/// # use battery::{Result, Manager};
+/// # fn main() -> Result<()> {
+/// let manager = Manager::new()?;
+/// for battery in &manager {
+///     println!("{:#?}", battery?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
 pub struct Manager {
     inner: Rc<PlatformManager>,
+    config: Config,
+    // Energy drained from the batteries since the manager was created
+    counter: RefCell<EnergyCounter>,
 }
 
 impl Manager {
     /// Creates new manager instance.
     pub fn new() -> Result<Manager> {
+        Manager::with_config(Config::default())
+    }
+
+    /// Creates new manager instance with the given [configuration](struct.Config.html).
+    pub fn with_config(config: Config) -> Result<Manager> {
         let inner = PlatformManager::new()?;
 
         Ok(Manager {
             inner: Rc::new(inner),
+            config,
+            counter: RefCell::new(EnergyCounter::new()),
         })
     }
 
+    /// Creates new manager instance, which reads the power supplies from the `root` directory
+    /// instead of the `/sys/class/power_supply`.
+    ///
+    /// Not a part of the public API, used by the integration tests with the fake power supplies.
+    #[cfg(target_os = "linux")]
+    #[doc(hidden)]
+    pub fn with_sysfs_root<T: Into<PathBuf>>(root: T, config: Config) -> Manager {
+        Manager {
+            inner: Rc::new(PlatformManager::with_root(root)),
+            config,
+            counter: RefCell::new(EnergyCounter::new()),
+        }
+    }
+
     /// Gets an iterator over available [batteries](struct.Battery.html).
+    ///
+    /// Batteries are yielded in a deterministic order, which does not change
+    /// between calls and system reboots as long as the hardware stays the same:
+    ///
+    ///  * Linux: by the `/sys/class/power_supply/` entry name, in a natural order (`BAT2` goes before `BAT10`)
+    ///  * MacOS: by the IOKit registry path, in a natural order, then by the registry entry ID
+    ///  * Windows: by the device instance path, in a natural order
+    ///  * FreeBSD and DragonFlyBSD: by the ACPI battery unit number
     pub fn batteries(&self) -> Result<Batteries> {
         let inner = PlatformIterator::new(self.inner.clone())?;
 
-        Ok(Batteries::from(inner))
+        Ok(Batteries::new(inner, self.config.clone()))
+    }
+
+    /// Gets an iterator over available [batteries](struct.Battery.html) of the given kinds.
+    ///
+    /// Filtering is done by the platform implementation before loading the battery information,
+    /// so excluded devices are not slowing down the enumeration.
+    /// Batteries are yielded in the same order as with [batteries](#method.batteries).
+    ///
+    /// Unlike [batteries](#method.batteries), which yields system batteries only
+    /// (and all the batteries for Windows), any combination of kinds can be requested here.
+    pub fn batteries_filtered(&self, filter: KindFilter) -> Result<Batteries> {
+        let inner = PlatformIterator::with_filter(self.inner.clone(), filter)?;
+
+        Ok(Batteries::new(inner, self.config.clone()))
+    }
+
+    /// Gets all available batteries, sorted by the key extracted with `key` function.
+    ///
+    /// Batteries information is loaded during the enumeration,
+    /// so all returned batteries are holding the up-to-date data.
+    /// Sort is stable, so batteries with equal keys are keeping the
+    /// [enumeration order](#method.batteries).
+    ///
+    /// # Example
+    ///
+    /// Weakest batteries go first:
+    ///
+    /// ```edition2018
+    /// # use battery::{Result, Manager};
+    /// use battery::units::ratio::percent;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let manager = Manager::new()?;
+    /// let batteries = manager.batteries_sorted_by(|battery| battery.state_of_charge().get::<percent>() as u32)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn batteries_sorted_by<K, F>(&self, key: F) -> Result<Vec<Battery>>
+    where
+        K: Ord,
+        F: Fn(&Battery) -> K,
+    {
+        let mut batteries = self.batteries()?.collect::<Result<Vec<_>>>()?;
+        batteries.sort_by_key(key);
+
+        Ok(batteries)
+    }
+
+    /// Maps the current batteries indices, as yielded by [batteries](#method.batteries),
+    /// to their [stable identifiers](struct.Battery.html#method.stable_id).
+    ///
+    /// Batteries might be enumerated in a different order after hotplug (ex. `BAT0` and `BAT1` are swapped),
+    /// so consumers keeping the per-index state can compare this map with the previous one
+    /// to detect the reordering and remap their data.
+    ///
+    /// Enumeration stops at the first error.
+    pub fn index_map(&self) -> Result<Vec<(usize, String)>> {
+        self.batteries()?
+            .enumerate()
+            .map(|(idx, battery)| battery.map(|battery| (idx, battery.stable_id())))
+            .collect()
+    }
+
+    /// Gets the battery with the given [stable identifier](struct.Battery.html#method.stable_id),
+    /// so the battery which identifier was persisted can be fetched again even if the batteries were reordered.
+    ///
+    /// Returns `None` if there is no such battery at the moment. On Linux the identifier
+    /// of the battery without a serial number is its `/sys/class/power_supply/` entry name (ex. `BAT0`).
+    ///
+    /// Enumeration stops at the first error or at the battery found.
+    pub fn get_by_id(&self, id: &str) -> Result<Option<Battery>> {
+        for battery in self.batteries()? {
+            let battery = battery?;
+            if battery.stable_id() == id {
+                return Ok(Some(battery));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loads the battery by its [name](struct.Battery.html#method.name), ex. for the scripts targeting a fixed `BAT0`.
+    ///
+    /// Names are following the platform conventions:
+    ///
+    ///  * Linux: `/sys/class/power_supply/` entry name (ex. `BAT0`), which is read directly instead of enumerating all
+    ///    the power supplies
+    ///  * MacOS: IOKit registry entry name, ex. `AppleSmartBattery`
+    ///  * Windows: device friendly name or description, as displayed by the Device Manager
+    ///
+    /// Returns `None` if there is no such entry or it is not a battery which [batteries](#method.batteries)
+    /// would yield (ex. it is an AC adapter or the battery slot is empty).
+    pub fn battery_by_name(&self, name: &str) -> Result<Option<Battery>> {
+        let device = PlatformManager::find_by_name(&self.inner, name)?;
+
+        Ok(device
+            .map(|device| Battery::from(device).with_config(&self.config))
+            .filter(|battery| !self.config.skips(battery)))
+    }
+
+    /// Compares the currently available batteries with the `previous` list of their
+    /// [stable identifiers](struct.Battery.html#method.stable_id), returning the added and removed ones.
+    ///
+    /// This is a polling alternative to the [spawn_watcher](#method.spawn_watcher) added and removed events,
+    /// for the consumers which are managing their own polling loop.
+    /// Pass [EnumerationDelta::current](struct.EnumerationDelta.html#method.current)
+    /// as the `previous` list to the next call; empty list means that all batteries are added.
+    ///
+    /// Each call is a full batteries enumeration, O(n) of the batteries count.
+    /// Enumeration stops at the first error.
+    ///
+    /// # Example
+    ///
+    /// ```edition2018,no_run
+    /// # use battery::{Manager, Result};
+    /// # fn main() -> Result<()> {
+    /// let manager = Manager::new()?;
+    /// let mut known = vec![];
+    /// loop {
+    ///     let delta = manager.diff_enumeration(&known)?;
+    ///     for id in delta.added() {
+    ///         println!("Added: {}", id);
+    ///     }
+    ///     for id in delta.removed() {
+    ///         println!("Removed: {}", id);
+    ///     }
+    ///     known = delta.into_current();
+    /// #   break;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn diff_enumeration(&self, previous: &[String]) -> Result<EnumerationDelta> {
+        let current = self
+            .batteries()?
+            .map(|battery| battery.map(|battery| battery.stable_id()))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(EnumerationDelta::new(previous, current))
+    }
+
+    /// Loads all available batteries and the external power supply status in one pass.
+    ///
+    /// Batteries are the same as [batteries](#method.batteries) would yield, but the enumeration
+    /// stops at the first error.
+    ///
+    /// On Linux this is cheaper than iterating over the batteries, because power supply directories
+    /// are opened only once and their attributes are read relatively to them;
+    /// other platforms fall back to the plain enumeration.
+    pub fn full_scan(&self) -> Result<SystemBatteryReport> {
+        let _span = trace_span!("full_scan");
+        let (devices, external_power) = PlatformManager::scan(&self.inner)?;
+        let batteries = devices
+            .into_iter()
+            .map(|device| Battery::from(device).with_config(&self.config))
+            .filter(|battery| !self.config.skips(battery))
+            .collect();
+
+        Ok(SystemBatteryReport::new(batteries, external_power))
+    }
+
+    /// Loads the batteries and gets the power the whole system is drawing at the moment, when it runs on them.
+    ///
+    /// Returns `None` on the external power, see
+    /// [SystemBatteryReport::system_power_draw](struct.SystemBatteryReport.html#method.system_power_draw)
+    /// for details.
+    pub fn system_power_draw(&self) -> Result<Option<Power>> {
+        Ok(self.full_scan()?.system_power_draw())
+    }
+
+    /// Loads all available batteries using up to `threads` threads,
+    /// which speeds up the systems with many batteries (ex. UPS banks),
+    /// and returns their [snapshots](struct.BatteryInfo.html).
+    ///
+    /// Returned snapshots are ordered by their index, same as [batteries](#method.batteries) would yield them,
+    /// no matter which thread loaded them first. Error for the battery with the lowest index is returned
+    /// if any of them fails to load.
+    ///
+    /// Zero or one `threads` means that batteries are loaded in the calling thread.
+    /// Only Linux loads batteries in parallel for now, other platforms fall back to the plain enumeration.
+    pub fn snapshot_parallel(&self, threads: usize) -> Result<Vec<BatteryInfo>> {
+        let _span = trace_span!("snapshot_parallel", threads);
+        let devices = PlatformManager::scan_parallel(&self.inner, threads)?;
+
+        Ok(devices
+            .into_iter()
+            .map(|device| Battery::from(device).with_config(&self.config))
+            .filter(|battery| !self.config.skips(battery))
+            .map(|battery| BatteryInfo::from(&battery))
+            .collect())
+    }
+
+    /// Spawns a background thread, which watches the batteries and sends the change [events](enum.Event.html)
+    /// into the `tx` channel.
+    ///
+    /// Batteries are enumerated every [watch interval](struct.Config.html#method.watch_interval);
+    /// all batteries found on the first run are reported as added. Enumeration errors are sent into
+    /// the channel too, and the watcher keeps going after them.
+    /// If the enumeration keeps failing for longer than the [maximum data age](struct.Config.html#method.max_data_age),
+    /// a [stale](enum.Event.html#variant.Stale) event is sent for each of the previously seen batteries.
+    ///
+    /// Watcher thread uses its own `Manager` with the same configuration, since managers can't be
+    /// shared between threads; an error is returned if it can't be created.
+    ///
+    /// Watcher stops when the returned [handle](struct.WatcherHandle.html) is dropped
+    /// or when the `tx` receiver is gone.
+    ///
+    /// # Example
+    ///
+    /// ```edition2018,no_run
+    /// # use battery::{Event, Manager, Result};
+    /// # use std::sync::mpsc;
+    /// # fn main() -> Result<()> {
+    /// let (tx, rx) = mpsc::channel();
+    /// let _watcher = Manager::new()?.spawn_watcher(tx)?;
+    /// for event in rx {
+    ///     match event? {
+    ///         Event::Added(battery) | Event::Changed(battery) => println!("{:?}", battery.state_of_charge()),
+    ///         Event::Removed(id) => println!("{} is gone", id),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_watcher(&self, tx: mpsc::Sender<Result<Event>>) -> Result<WatcherHandle> {
+        let config = self.config.clone();
+        let interval = config.watch_interval.unwrap_or_else(|| Duration::from_secs(1));
+
+        WatcherHandle::spawn(move || Manager::with_config(config), interval, tx)
+    }
+
+    /// Spawns a background thread, which checks the batteries against the `watcher` levels
+    /// every [watch interval](struct.Config.html#method.watch_interval) and sends the
+    /// [threshold events](enum.ThresholdEvent.html) into the `tx` channel.
+    ///
+    /// Same as with [spawn_watcher](#method.spawn_watcher), thread uses its own `Manager`,
+    /// enumeration errors are sent into the channel, and thread stops
+    /// when the returned handle is dropped or when the `tx` receiver is gone.
+    ///
+    /// # Example
+    ///
+    /// ```edition2018,no_run
+    /// # use battery::{Manager, Result, ThresholdEvent, ThresholdWatcher};
+    /// # use std::sync::mpsc;
+    /// use battery::units::ratio::percent;
+    /// use battery::units::Ratio;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let (tx, rx) = mpsc::channel();
+    /// let watcher = ThresholdWatcher::new(&[Ratio::new::<percent>(20.0)]);
+    /// let _handle = Manager::new()?.spawn_threshold_watcher(watcher, tx)?;
+    /// for event in rx {
+    ///     match event? {
+    ///         ThresholdEvent::CrossedBelow(id, _) => println!("{} is low", id),
+    ///         ThresholdEvent::RecoveredAbove(id, _) => println!("{} is fine", id),
+    ///         _ => {}
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn spawn_threshold_watcher(
+        &self,
+        mut watcher: ThresholdWatcher,
+        tx: mpsc::Sender<Result<ThresholdEvent>>,
+    ) -> Result<WatcherHandle> {
+        let config = self.config.clone();
+        let interval = config.watch_interval.unwrap_or_else(|| Duration::from_secs(1));
+
+        WatcherHandle::spawn_with(
+            move || Manager::with_config(config),
+            interval,
+            tx,
+            move |manager| flatten(watcher.poll(manager)),
+        )
+    }
+
+    /// Checks if the operating system low-power mode (battery saver) is on.
+    ///
+    ///  * Linux: `power-profiles-daemon` profile (used by GNOME and KDE) is `power-saver`, or, if it is not available,
+    ///    the ACPI `platform_profile` is `low-power`
+    ///  * Windows: `SYSTEM_POWER_STATUS.SystemStatusFlag` is set, Windows 10 and newer
+    ///  * Other platforms: not available
+    ///
+    /// Returns `None` if the mode can't be determined.
+    pub fn battery_saver_active(&self) -> Result<Option<bool>> {
+        self.inner.battery_saver_active()
+    }
+
+    /// Loads the external line power supplies (ex. AC adapters) information,
+    /// which describes them in more details than the system report
+    /// [is_external_power_connected](struct.SystemBatteryReport.html#method.is_external_power_connected)
+    /// value.
+    ///
+    /// Available only for Linux, where the `Mains` type power supplies are returned in the
+    /// [enumeration order](#method.batteries); returns an empty list for other platforms.
+    pub fn line_power(&self) -> Result<Vec<LinePower>> {
+        self.inner.line_power()
+    }
+
+    /// Energy drained from the batteries since this manager was created,
+    /// ex. for the "this session used X Wh" reports.
+    ///
+    /// There is no cumulative energy counter reported by the platforms, so each [refresh](#method.refresh)
+    /// adds up the refreshed battery energy decline since it was loaded or refreshed previously;
+    /// energy drained before the battery was loaded is not counted.
+    /// Only the discharging batteries are counted, energy gained while charging is not subtracted,
+    /// and all the batteries are summed up, so the systems discharging the packs sequentially
+    /// are counted correctly.
+    ///
+    /// Accuracy depends on the refresh frequency: energy drained from the battery
+    /// which was charged between two refreshes is missed. Counter starts from zero
+    /// for every new `Manager`.
+    pub fn energy_since_start(&self) -> Result<Energy> {
+        Ok(self.counter.borrow().total())
+    }
+
+    /// Re-opens the platform handles this manager keeps for its lifetime:
+    /// IOKit master port on MacOS and the `/dev/acpi` device on FreeBSD.
+    /// Linux and Windows managers are not keeping anything open, so it does nothing for them.
+    ///
+    /// It is not needed to see the hot-added batteries: each enumeration (ex. [batteries](#method.batteries)
+    /// or [full_scan](#method.full_scan)) scans the system from scratch, and the attribute files opened
+    /// with the `fast-refresh` feature are kept by the `Battery` instances, not by the manager.
+    /// Use it if the handles went bad, ex. the manager calls keep failing after the system resume,
+    /// to avoid creating a new manager with the same configuration.
+    ///
+    /// Iterators created before the reload keep using the old handles.
+    pub fn reload(&mut self) -> Result<()> {
+        self.inner = Rc::new(self.inner.reloaded()?);
+
+        Ok(())
     }
 
     /// Refresh battery information in-place.
     pub fn refresh(&self, battery: &mut Battery) -> Result<()> {
-        self.inner.refresh(battery)
+        let _span = trace_span!("refresh", device = ?battery.name());
+        let state = battery.state();
+        let energy = battery.energy();
+        if self.config.partial_refresh {
+            let errors = self.inner.refresh_partial(battery)?;
+            battery.set_field_errors(errors);
+        } else {
+            self.inner.refresh(battery)?;
+            battery.set_field_errors(Vec::new());
+        }
+        battery.track_state(state, self.config.clock.now());
+        battery.mark_updated(self.config.clock.system_now());
+        self.counter.borrow_mut().observe(energy, battery);
+
+        Ok(())
+    }
+
+    pub(crate) fn config(&self) -> &Config {
+        &self.config
+    }
+}
+
+/// Iterates over the same batteries as the [Manager::batteries](struct.Manager.html#method.batteries) method.
+///
+/// Since `into_iter` can't fail, an error from the batteries enumeration start
+/// is yielded as a single `Err` item, after which iterator ends.
+/// Use the [Manager::batteries](struct.Manager.html#method.batteries) method instead
+/// if that error should be told apart from the errors of the individual batteries.
+impl IntoIterator for &Manager {
+    type Item = Result<Battery>;
+    type IntoIter = Batteries;
+
+    fn into_iter(self) -> Batteries {
+        match self.batteries() {
+            Ok(batteries) => batteries,
+            Err(e) => Batteries::failed(e),
+        }
+    }
+}
+
+#[cfg(test)]
+impl From<PlatformManager> for Manager {
+    fn from(inner: PlatformManager) -> Manager {
+        Manager {
+            inner: Rc::new(inner),
+            config: Config::default(),
+            counter: RefCell::new(EnergyCounter::new()),
+        }
+    }
+}
+
+#[cfg(all(test, target_os = "linux"))]
+impl Manager {
+    /// Replaces the clock used for the batteries timestamps, see the `clock` module.
+    pub(crate) fn with_clock(mut self, clock: std::sync::Arc<dyn super::clock::Clock>) -> Manager {
+        self.config.clock = super::clock::SharedClock::new(clock);
+        self
+    }
+
+    /// Replaces the configuration, same as the `Manager::with_config` does for the real manager.
+    pub(crate) fn configured(mut self, config: Config) -> Manager {
+        self.config = config;
+        self
     }
 }
 
 impl fmt::Debug for Manager {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("Manager").field("impl", &self.inner).finish()
+        f.debug_struct("Manager")
+            .field("impl", &self.inner)
+            .field("config", &self.config)
+            .finish()
     }
 }
@@ -1,11 +1,57 @@
+mod active;
+mod anomaly;
 mod battery;
+mod capacity;
+mod charge_phase;
+pub(crate) mod clock;
+mod condition;
+mod config;
+mod counter;
+mod delta;
+mod display;
+mod estimator;
+mod eta;
+mod info;
 mod iterator;
+mod kind;
+mod line_power;
 mod manager;
+mod prompt;
+mod rate_kind;
+mod report;
+mod risk;
+#[cfg(feature = "schemars")]
+mod schema;
+mod severity;
+mod shared;
 mod state;
 mod technology;
+mod threshold;
+pub(crate) mod watcher;
 
+pub use self::active::active_battery;
+pub use self::anomaly::Anomaly;
 pub use self::battery::Battery;
+pub use self::capacity::{Capacity, CapacitySource};
+pub use self::charge_phase::ChargePhase;
+pub use self::condition::Condition;
+pub use self::config::Config;
+pub use self::delta::EnumerationDelta;
+pub use self::display::{DisplayConfig, RoundingMode, TemperatureUnit, TimeFormat};
+pub use self::estimator::{Estimate, EstimateQuality, EstimateSource, LearnedEstimator};
+pub use self::eta::EtaComparison;
+pub use self::info::BatteryInfo;
 pub use self::iterator::Batteries;
+pub use self::kind::{Kind, KindFilter};
+pub use self::line_power::LinePower;
 pub use self::manager::Manager;
+pub use self::prompt::PromptSegment;
+pub use self::rate_kind::RateKind;
+pub use self::report::SystemBatteryReport;
+pub use self::risk::RiskLevel;
+pub use self::severity::Severity;
+pub use self::shared::{batteries, status};
 pub use self::state::State;
 pub use self::technology::Technology;
+pub use self::threshold::{ThresholdEvent, ThresholdWatcher};
+pub use self::watcher::{Event, WatcherHandle};
@@ -0,0 +1,55 @@
+use crate::units::ratio::percent;
+use crate::{Battery, DisplayConfig, State};
+
+/// Minimal battery information required to render a shell prompt segment,
+/// as in [starship](https://github.com/starship/starship) or powerline.
+///
+/// See [Battery::prompt_segment](struct.Battery.html#method.prompt_segment).
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct PromptSegment {
+    icon_level: u8,
+    percent: u8,
+    charging: bool,
+}
+
+impl PromptSegment {
+    /// Lower bounds (in percents, inclusive) of the icon levels `1..=4`;
+    /// charge below the first one falls into the level `0`.
+    ///
+    /// Each level is centered around the 0%, 25%, 50%, 75% and 100% marks,
+    /// which matches the usual five-icon battery glyph sets.
+    pub const LEVEL_THRESHOLDS: [f32; 4] = [12.5, 37.5, 62.5, 87.5];
+
+    pub(crate) fn new(battery: &Battery, display: &DisplayConfig) -> PromptSegment {
+        let charge = battery.state_of_charge().get::<percent>();
+        let icon_level = Self::LEVEL_THRESHOLDS
+            .iter()
+            .filter(|threshold| charge >= **threshold)
+            .count() as u8;
+
+        PromptSegment {
+            icon_level,
+            percent: display.percent(battery),
+            charging: battery.state() == State::Charging,
+        }
+    }
+
+    /// Discrete charge level in the `0..=4` range, intended to pick the battery icon.
+    ///
+    /// See [LEVEL_THRESHOLDS](#associatedconstant.LEVEL_THRESHOLDS) for the levels boundaries.
+    pub fn icon_level(&self) -> u8 {
+        self.icon_level
+    }
+
+    /// Battery state of charge in percents, rounded according to the
+    /// [DisplayConfig::rounding_mode](struct.DisplayConfig.html#method.rounding_mode) of the battery manager
+    /// (to the nearest integer by default).
+    pub fn percent(&self) -> u8 {
+        self.percent
+    }
+
+    /// Returns `true` if battery is charging.
+    pub fn is_charging(&self) -> bool {
+        self.charging
+    }
+}
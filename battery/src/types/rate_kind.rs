@@ -0,0 +1,33 @@
+use std::fmt;
+
+/// Tells how the [energy rate](struct.Battery.html#method.energy_rate) is measured.
+///
+/// Instantaneous rate follows the load changes immediately, so the time estimates calculated
+/// from it are jumping around and should be smoothed by the consumer,
+/// while the averaged one is already smoothed by the driver or battery firmware.
+///
+/// See [Battery::rate_kind](struct.Battery.html#method.rate_kind).
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum RateKind {
+    /// Rate is measured at the moment of the refresh.
+    Instantaneous,
+    /// Rate is a running average over the recent period, usually about a minute.
+    Averaged,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl fmt::Display for RateKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            RateKind::Instantaneous => "instantaneous",
+            RateKind::Averaged => "averaged",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
@@ -0,0 +1,103 @@
+use num_traits::identities::Zero;
+
+use crate::units::{Bound, Energy, Power, Ratio};
+use crate::{active_battery, Battery, State};
+
+/// All batteries available in system together with the external power supply status.
+///
+/// This struct is created by the [Manager::full_scan](struct.Manager.html#method.full_scan) method
+/// and the [status](fn.status.html) function.
+#[derive(Debug)]
+pub struct SystemBatteryReport {
+    batteries: Vec<Battery>,
+    external_power: Option<bool>,
+}
+
+impl SystemBatteryReport {
+    pub(crate) fn new(batteries: Vec<Battery>, external_power: Option<bool>) -> SystemBatteryReport {
+        SystemBatteryReport {
+            batteries,
+            external_power,
+        }
+    }
+
+    /// Batteries in the same order as they are yielded by the
+    /// [Manager::batteries](struct.Manager.html#method.batteries).
+    pub fn batteries(&self) -> &[Battery] {
+        &self.batteries
+    }
+
+    /// Consumes the report, returning the batteries.
+    pub fn into_batteries(self) -> Vec<Battery> {
+        self.batteries
+    }
+
+    /// Checks if any external power supply (ex. AC adapter or USB charger) is connected.
+    ///
+    /// Available only for Linux, returns `None` for other platforms
+    /// and for systems without any external power supplies known to the kernel.
+    pub fn is_external_power_connected(&self) -> Option<bool> {
+        self.external_power
+    }
+
+    /// Battery to look at for the single battery displays: the [active](fn.active_battery.html) one
+    /// or the first battery if none of them are charging or discharging.
+    ///
+    /// Returns `None` only if there are no batteries at all.
+    pub fn primary_battery(&self) -> Option<&Battery> {
+        active_battery(&self.batteries)
+            .map(|idx| &self.batteries[idx])
+            .or_else(|| self.batteries.first())
+    }
+
+    /// Combined state of charge of all batteries, weighted by their full energy,
+    /// same as if they were a single large battery.
+    ///
+    /// Returns zero if there are no batteries or their full energy is unknown.
+    pub fn state_of_charge(&self) -> Ratio {
+        let energy = self
+            .batteries
+            .iter()
+            .fold(Energy::zero(), |acc, battery| acc + battery.energy());
+        let energy_full = self
+            .batteries
+            .iter()
+            .fold(Energy::zero(), |acc, battery| acc + battery.energy_full());
+
+        if energy_full.is_zero() {
+            Ratio::zero()
+        } else {
+            // Same as for the single battery, forcing the value to be in `0.0..=1.0` range
+            (energy / energy_full).into_bounded()
+        }
+    }
+
+    /// Power the whole system is drawing at the moment, when it runs on the batteries.
+    ///
+    /// Without the external power, the system is powered by the batteries only, so their
+    /// combined discharge [energy rate](struct.Battery.html#method.energy_rate) is the system draw.
+    /// On the external power the batteries rate tells how fast they are charging instead,
+    /// and neither the adapter draw nor the processor power (ex. Intel RAPL) is read by this crate,
+    /// so `None` is returned.
+    ///
+    /// External power is considered connected if [reported](#method.is_external_power_connected) so,
+    /// or, when it is unknown, if any battery is charging. `None` is returned too if no battery
+    /// is discharging or the rate is not reported.
+    pub fn system_power_draw(&self) -> Option<Power> {
+        let charging = self.batteries.iter().any(|battery| battery.state() == State::Charging);
+        if self.external_power.unwrap_or(charging) {
+            return None;
+        }
+
+        let draw = self
+            .batteries
+            .iter()
+            .filter(|battery| battery.state() == State::Discharging)
+            .fold(Power::zero(), |acc, battery| acc + battery.energy_rate());
+        if draw.is_zero() {
+            None
+        } else {
+            Some(draw)
+        }
+    }
+}
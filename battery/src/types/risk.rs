@@ -0,0 +1,129 @@
+use std::fmt;
+
+use num_traits::identities::Zero;
+
+use crate::units::ratio::percent;
+use crate::units::thermodynamic_temperature::degree_celsius;
+use crate::units::time::hour;
+use crate::units::{Ratio, Time};
+use crate::Battery;
+
+/// Likelihood that the battery pack is failing, ex. swollen, and should be replaced.
+///
+/// Swelling can't be sensed directly, so the risk is aggregated from the indirect signals,
+/// with the thresholds available as the associated constants; the worst of the signals wins:
+///
+///  * [Permanent failure](struct.Battery.html#method.is_failed) reported by the battery controller: `High`
+///  * [State of health](struct.Battery.html#method.state_of_health) below `ELEVATED_RISK_HEALTH`: `Elevated`, below
+///    `HIGH_RISK_HEALTH`: `High`; not checked if the full energy is missing or
+///    [estimated](struct.Battery.html#method.energy_full_is_estimated)
+///  * Full energy drifted above the designed one by more than `MAX_FULL_DRIFT`, which is implausible for a healthy
+///    pack: `Elevated`
+///  * [Self-discharge](struct.Battery.html#method.self_discharge_rate) faster than `ELEVATED_SELF_DISCHARGE` of the
+///    full energy per day: `Elevated`, faster than `HIGH_SELF_DISCHARGE`: `High`
+///  * [Temperature](struct.Battery.html#method.temperature) above `ELEVATED_TEMPERATURE`: `Elevated`, above
+///    `HIGH_TEMPERATURE`: `High`
+///
+/// Variants are ordered from the least concerning one.
+///
+/// See [Battery::failure_risk](struct.Battery.html#method.failure_risk).
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum RiskLevel {
+    /// Designed or full capacity is not reported, so the state of health can't be checked,
+    /// and none of the other signals is concerning.
+    Unknown,
+    /// None of the signals is concerning.
+    Low,
+    /// Some of the signals are concerning, battery should be watched.
+    Elevated,
+    /// Battery is likely failing, consider replacing it.
+    High,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl RiskLevel {
+    /// State of health, in percents, below which the risk is `Elevated`.
+    pub const ELEVATED_RISK_HEALTH: f32 = 70.0;
+    /// State of health, in percents, below which the risk is `High`.
+    pub const HIGH_RISK_HEALTH: f32 = 50.0;
+    /// How much, in percents of the designed energy, the full energy can exceed it.
+    ///
+    /// New batteries are often a few percents above the design, but the greater drift
+    /// means that the fuel gauge lost track of the pack.
+    pub const MAX_FULL_DRIFT: f32 = 10.0;
+    /// Self-discharge, in percents of the full energy per day, above which the risk is `Elevated`.
+    ///
+    /// Healthy Li-ion cells are losing a few percents per month.
+    pub const ELEVATED_SELF_DISCHARGE: f32 = 3.0;
+    /// Self-discharge, in percents of the full energy per day, above which the risk is `High`.
+    pub const HIGH_SELF_DISCHARGE: f32 = 10.0;
+    /// Temperature, in degrees Celsius, above which the risk is `Elevated`.
+    pub const ELEVATED_TEMPERATURE: f32 = 45.0;
+    /// Temperature, in degrees Celsius, above which the risk is `High`.
+    pub const HIGH_TEMPERATURE: f32 = 60.0;
+
+    pub(crate) fn detect(battery: &Battery) -> RiskLevel {
+        if battery.is_failed() {
+            return RiskLevel::High;
+        }
+
+        let energy_full = battery.energy_full();
+        let energy_full_design = battery.energy_full_design();
+        // Estimated full energy always gives the 100% health, and the zero one is a read failure,
+        // not a dead battery
+        let mut risk = if energy_full_design.is_zero() || energy_full.is_zero() || battery.energy_full_is_estimated() {
+            RiskLevel::Unknown
+        } else {
+            let health = battery.state_of_health().get::<percent>();
+            let drift = ((energy_full - energy_full_design) / energy_full_design).get::<percent>();
+            if health < Self::HIGH_RISK_HEALTH {
+                RiskLevel::High
+            } else if health < Self::ELEVATED_RISK_HEALTH || drift > Self::MAX_FULL_DRIFT {
+                RiskLevel::Elevated
+            } else {
+                RiskLevel::Low
+            }
+        };
+
+        if let Some(rate) = battery.self_discharge_rate() {
+            if !energy_full.is_zero() {
+                let daily: Ratio = rate * Time::new::<hour>(24.0) / energy_full;
+                let daily = daily.get::<percent>();
+                if daily > Self::HIGH_SELF_DISCHARGE {
+                    risk = risk.max(RiskLevel::High);
+                } else if daily > Self::ELEVATED_SELF_DISCHARGE {
+                    risk = risk.max(RiskLevel::Elevated);
+                }
+            }
+        }
+
+        if let Some(temperature) = battery.temperature() {
+            let celsius = temperature.get::<degree_celsius>();
+            if celsius > Self::HIGH_TEMPERATURE {
+                risk = risk.max(RiskLevel::High);
+            } else if celsius > Self::ELEVATED_TEMPERATURE {
+                risk = risk.max(RiskLevel::Elevated);
+            }
+        }
+
+        risk
+    }
+}
+
+impl fmt::Display for RiskLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            RiskLevel::Unknown => "unknown",
+            RiskLevel::Low => "low",
+            RiskLevel::Elevated => "elevated",
+            RiskLevel::High => "high",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
@@ -0,0 +1,17 @@
+use schemars::schema::{InstanceType, Schema, SchemaObject};
+
+/// JSON schema of the string enum, which is serialized with its `Display` implementation.
+pub fn string_enum<T, I>(variants: I) -> Schema
+where
+    T: ToString,
+    I: IntoIterator<Item = T>,
+{
+    let values = variants.into_iter().map(|variant| variant.to_string().into()).collect();
+
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        enum_values: Some(values),
+        ..Default::default()
+    }
+    .into()
+}
@@ -0,0 +1,72 @@
+use std::fmt;
+
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+
+/// How urgent the battery [state of charge](struct.Battery.html#method.state_of_charge) is,
+/// ex. for picking the status bar color or icon.
+///
+/// Thresholds are available as the associated constants; variants are ordered from the least urgent one.
+///
+/// See [Battery::severity](struct.Battery.html#method.severity)
+/// and [Battery::severity_stable](struct.Battery.html#method.severity_stable).
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum Severity {
+    /// State of charge is at `LOW_THRESHOLD` or above.
+    Normal,
+    /// State of charge is below `LOW_THRESHOLD`.
+    Low,
+    /// State of charge is below `CRITICAL_THRESHOLD`.
+    Critical,
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+impl Severity {
+    /// State of charge, in percents, below which the battery is `Low`.
+    pub const LOW_THRESHOLD: f32 = 20.0;
+    /// State of charge, in percents, below which the battery is `Critical`.
+    pub const CRITICAL_THRESHOLD: f32 = 5.0;
+
+    pub(crate) fn detect(soc: Ratio) -> Severity {
+        let soc = soc.get::<percent>();
+        if soc < Self::CRITICAL_THRESHOLD {
+            Severity::Critical
+        } else if soc < Self::LOW_THRESHOLD {
+            Severity::Low
+        } else {
+            Severity::Normal
+        }
+    }
+
+    /// Moves from the `previous` severity only if `soc` is further than `margin` past the threshold,
+    /// in either direction.
+    pub(crate) fn detect_with_hysteresis(previous: Severity, soc: Ratio, margin: Ratio) -> Severity {
+        let worse = Severity::detect(soc + margin);
+        if worse > previous {
+            return worse;
+        }
+        let better = Severity::detect(soc - margin);
+        if better < previous {
+            return better;
+        }
+
+        previous
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display = match self {
+            Severity::Normal => "normal",
+            Severity::Low => "low",
+            Severity::Critical => "critical",
+            _ => "unknown",
+        };
+
+        write!(f, "{}", display)
+    }
+}
@@ -0,0 +1,84 @@
+use std::cell::RefCell;
+
+use crate::{Batteries, Manager, Result, SystemBatteryReport};
+
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)] // `const` initializers require Rust 1.59
+    static MANAGER: RefCell<Option<Manager>> = RefCell::new(None);
+}
+
+/// Gets an iterator over available [batteries](struct.Battery.html)
+/// using the lazily-initialized shared [Manager](struct.Manager.html).
+///
+/// This is a shortcut for the simple programs, which are only needed to look at
+/// the batteries once, and it works the same as the [Manager::batteries](struct.Manager.html#method.batteries)
+/// method with the [default configuration](struct.Config.html).
+///
+/// Since the `Manager` is not thread-safe, the shared instance is a thread-local one:
+/// it is created on the first call in each thread and lives until that thread exits.
+/// If manager creation fails, an error is returned and the next call will try again.
+///
+/// Batteries obtained this way can't be refreshed in-place, because the shared
+/// manager is not exposed; create a [Manager](struct.Manager.html) explicitly for that.
+///
+/// # Example
+///
+/// ```edition2018
+/// # fn main() -> battery::Result<()> {
+/// for battery in battery::batteries()? {
+///     println!("{:?}", battery?.state_of_charge());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn batteries() -> Result<Batteries> {
+    with_manager(Manager::batteries)
+}
+
+/// Loads all available batteries and the external power supply status in one call,
+/// using the same shared [Manager](struct.Manager.html) as the [batteries](fn.batteries.html) function.
+///
+/// This is the [Manager::full_scan](struct.Manager.html#method.full_scan) shortcut for the quick scripts;
+/// report gives access to the [primary battery](struct.SystemBatteryReport.html#method.primary_battery)
+/// and the [combined state of charge](struct.SystemBatteryReport.html#method.state_of_charge) as well.
+///
+/// # Returns
+///
+/// `None` if there are no batteries in the system.
+///
+/// # Example
+///
+/// ```edition2018
+/// # fn main() -> battery::Result<()> {
+/// match battery::status()? {
+///     Some(report) => println!("{:?}", report.state_of_charge()),
+///     None => println!("No batteries found"),
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn status() -> Result<Option<SystemBatteryReport>> {
+    let report = with_manager(Manager::full_scan)?;
+    if report.batteries().is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(report))
+    }
+}
+
+fn with_manager<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce(&Manager) -> Result<T>,
+{
+    MANAGER.with(|cell| {
+        let mut manager = cell.borrow_mut();
+        if manager.is_none() {
+            *manager = Some(Manager::new()?);
+        }
+
+        match *manager {
+            Some(ref manager) => f(manager),
+            None => unreachable!(),
+        }
+    })
+}
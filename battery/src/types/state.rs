@@ -19,6 +19,21 @@ pub enum State {
     __Nonexhaustive,
 }
 
+impl State {
+    /// All possible states, in the declaration order, ex. for the UI filters.
+    ///
+    /// New states might be added in the future versions.
+    pub fn all() -> &'static [State] {
+        &[
+            State::Unknown,
+            State::Charging,
+            State::Discharging,
+            State::Empty,
+            State::Full,
+        ]
+    }
+}
+
 impl str::FromStr for State {
     type Err = io::Error;
 
@@ -58,3 +73,63 @@ impl Default for State {
         State::Unknown
     }
 }
+
+/// Serialized as the same lowercase string as the `Display` implementation produces.
+#[cfg(feature = "serde")]
+impl serde::Serialize for State {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Values are matched case-insensitively; unknown values, ex. written by the newer crate versions,
+/// are deserialized as a `State::Unknown`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for State {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        Ok(value.parse().unwrap_or(State::Unknown))
+    }
+}
+
+/// Schema matches the `Serialize` implementation output.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for State {
+    fn schema_name() -> String {
+        "State".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        super::schema::string_enum(State::all())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::State;
+
+    // Exhaustive match fails to compile once a new variant is added, reminding to update `State::all`
+    fn index(state: State) -> usize {
+        match state {
+            State::Unknown => 0,
+            State::Charging => 1,
+            State::Discharging => 2,
+            State::Empty => 3,
+            State::Full => 4,
+            State::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        let indices = State::all().iter().cloned().map(index).collect::<Vec<_>>();
+        assert_eq!(indices, (0..=4).collect::<Vec<_>>());
+    }
+}
@@ -21,6 +21,25 @@ pub enum Technology {
     __Nonexhaustive,
 }
 
+impl Technology {
+    /// All possible technologies, in the declaration order, ex. for the UI filters.
+    ///
+    /// New technologies might be added in the future versions.
+    pub fn all() -> &'static [Technology] {
+        &[
+            Technology::Unknown,
+            Technology::LithiumIon,
+            Technology::LeadAcid,
+            Technology::LithiumPolymer,
+            Technology::NickelMetalHydride,
+            Technology::NickelCadmium,
+            Technology::NickelZinc,
+            Technology::LithiumIronPhosphate,
+            Technology::RechargeableAlkalineManganese,
+        ]
+    }
+}
+
 impl str::FromStr for Technology {
     type Err = Error;
 
@@ -39,6 +58,15 @@ impl str::FromStr for Technology {
             _ if s.eq_ignore_ascii_case("nizn") => Technology::NickelZinc,
             _ if s.eq_ignore_ascii_case("life") => Technology::LithiumIronPhosphate,
             _ if s.eq_ignore_ascii_case("ram") => Technology::RechargeableAlkalineManganese,
+            // Values produced by the `Display` implementation
+            _ if s.eq_ignore_ascii_case("lithium-ion") => Technology::LithiumIon,
+            _ if s.eq_ignore_ascii_case("lead-acid") => Technology::LeadAcid,
+            _ if s.eq_ignore_ascii_case("lithium-polymer") => Technology::LithiumPolymer,
+            _ if s.eq_ignore_ascii_case("nickel-metal-hydride") => Technology::NickelMetalHydride,
+            _ if s.eq_ignore_ascii_case("nickel-cadmium") => Technology::NickelCadmium,
+            _ if s.eq_ignore_ascii_case("nickel-zinc") => Technology::NickelZinc,
+            _ if s.eq_ignore_ascii_case("lithium-iron-phosphate") => Technology::LithiumIronPhosphate,
+            _ if s.eq_ignore_ascii_case("rechargeable-alkaline-manganese") => Technology::RechargeableAlkalineManganese,
             // TODO: warn!
             _ => Technology::Unknown,
         };
@@ -71,3 +99,67 @@ impl Default for Technology {
         Technology::Unknown
     }
 }
+
+/// Serialized as the same string as the `Display` implementation produces.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Technology {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Accepts all the values `FromStr` implementation does,
+/// unrecognized ones are deserialized as a `Technology::Unknown`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Technology {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        Ok(value.parse().unwrap_or(Technology::Unknown))
+    }
+}
+
+/// Schema matches the `Serialize` implementation output.
+#[cfg(feature = "schemars")]
+impl schemars::JsonSchema for Technology {
+    fn schema_name() -> String {
+        "Technology".to_string()
+    }
+
+    fn json_schema(_gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        super::schema::string_enum(Technology::all())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Technology;
+
+    // Exhaustive match fails to compile once a new variant is added, reminding to update `Technology::all`
+    fn index(technology: Technology) -> usize {
+        match technology {
+            Technology::Unknown => 0,
+            Technology::LithiumIon => 1,
+            Technology::LeadAcid => 2,
+            Technology::LithiumPolymer => 3,
+            Technology::NickelMetalHydride => 4,
+            Technology::NickelCadmium => 5,
+            Technology::NickelZinc => 6,
+            Technology::LithiumIronPhosphate => 7,
+            Technology::RechargeableAlkalineManganese => 8,
+            Technology::__Nonexhaustive => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_all() {
+        let indices = Technology::all().iter().cloned().map(index).collect::<Vec<_>>();
+        assert_eq!(indices, (0..=8).collect::<Vec<_>>());
+    }
+}
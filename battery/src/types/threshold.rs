@@ -0,0 +1,161 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use super::watcher::watch_id;
+use crate::units::ratio::percent;
+use crate::units::Ratio;
+use crate::{Battery, Event, Manager, Result, State};
+
+/// Default gap between the level and the state of charge, at which the crossed level is re-armed, in percents.
+const DEFAULT_HYSTERESIS: f32 = 2.0;
+
+/// Notification about the battery [state of charge](struct.Battery.html#method.state_of_charge)
+/// crossing one of the [ThresholdWatcher](struct.ThresholdWatcher.html) levels.
+///
+/// Batteries are told apart by their [stable identifiers](struct.Battery.html#method.stable_id),
+/// or by their [device paths](struct.Battery.html#method.device_path) if the stable identifier is empty,
+/// same as for the [watcher events](enum.Event.html); these are the first value of each event,
+/// and the second one is the level crossed.
+#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum ThresholdEvent {
+    /// Discharging battery state of charge went below the level.
+    CrossedBelow(String, Ratio),
+    /// State of charge went back to the level plus hysteresis (or higher) after crossing below it,
+    /// no matter if the battery is charging or not. Level is re-armed and can be crossed again.
+    RecoveredAbove(String, Ratio),
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Tracks the batteries state of charge against a set of levels,
+/// ex. for the "battery low" and "battery critical" notifications.
+///
+/// Level is crossed once the *discharging* battery state of charge goes below it,
+/// after which it is not reported again until the state of charge recovers to the level
+/// plus [hysteresis](#method.hysteresis), so the value oscillating around the level under load
+/// does not cause the notifications storm. Charging battery never crosses the levels,
+/// but the one unplugged below the level does it right away.
+///
+/// Watcher does not read the batteries itself, feed it with the refreshed batteries or
+/// with the [Manager::spawn_watcher](struct.Manager.html#method.spawn_watcher) events,
+/// or let the [Manager::spawn_threshold_watcher](struct.Manager.html#method.spawn_threshold_watcher)
+/// do it in the background.
+///
+/// # Example
+///
+/// ```edition2018,no_run
+/// # use battery::{Manager, Result, ThresholdEvent, ThresholdWatcher};
+/// # use std::sync::mpsc;
+/// # use std::thread;
+/// # use std::time::Duration;
+/// use battery::units::ratio::percent;
+/// use battery::units::Ratio;
+///
+/// # fn main() -> Result<()> {
+/// let manager = Manager::new()?;
+/// let mut watcher = ThresholdWatcher::new(&[Ratio::new::<percent>(20.0), Ratio::new::<percent>(5.0)]);
+/// let mut battery = manager.batteries()?.next().unwrap()?;
+/// loop {
+///     for event in watcher.update(&battery) {
+///         if let ThresholdEvent::CrossedBelow(_, level) = event {
+///             println!("Battery is below {}%", level.get::<percent>());
+///         }
+///     }
+///     thread::sleep(Duration::from_secs(10));
+///     manager.refresh(&mut battery)?;
+/// }
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThresholdWatcher {
+    // Sorted in ascending order
+    levels: Vec<Ratio>,
+    hysteresis: Ratio,
+    // Crossed flags for each battery, indexed the same way as `levels`
+    crossed: HashMap<String, Vec<bool>>,
+}
+
+impl ThresholdWatcher {
+    /// Creates a watcher for the given state of charge `levels` with a 2% hysteresis.
+    pub fn new(levels: &[Ratio]) -> ThresholdWatcher {
+        let mut levels = levels.to_vec();
+        levels.sort_by(|left, right| left.partial_cmp(right).unwrap_or(Ordering::Equal));
+        levels.dedup();
+
+        ThresholdWatcher {
+            levels,
+            hysteresis: Ratio::new::<percent>(DEFAULT_HYSTERESIS),
+            crossed: HashMap::new(),
+        }
+    }
+
+    /// Sets how far above the crossed level state of charge should go to re-arm it, in the absolute terms:
+    /// `20%` level with a `2%` hysteresis is re-armed at `22%`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hysteresis` is negative or NaN.
+    pub fn hysteresis(mut self, hysteresis: Ratio) -> ThresholdWatcher {
+        assert!(hysteresis.get::<percent>() >= 0.0, "Invalid threshold hysteresis");
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    /// Watched levels, in ascending order.
+    pub fn levels(&self) -> &[Ratio] {
+        &self.levels
+    }
+
+    /// Checks the `battery` against the levels, returning the levels crossed since the previous update.
+    ///
+    /// Levels crossed below are returned from the highest one, and the recovered ones from the lowest one,
+    /// which is the order they would be crossed in with a gradual state of charge change.
+    pub fn update(&mut self, battery: &Battery) -> Vec<ThresholdEvent> {
+        let id = watch_id(battery);
+        let soc = battery.state_of_charge();
+        let discharging = battery.state() == State::Discharging;
+        let count = self.levels.len();
+        let crossed = self.crossed.entry(id.clone()).or_insert_with(|| vec![false; count]);
+        let mut events = vec![];
+
+        for (idx, level) in self.levels.iter().enumerate().rev() {
+            if !crossed[idx] && discharging && soc < *level {
+                crossed[idx] = true;
+                events.push(ThresholdEvent::CrossedBelow(id.clone(), *level));
+            }
+        }
+        for (idx, level) in self.levels.iter().enumerate() {
+            if crossed[idx] && soc >= *level + self.hysteresis {
+                crossed[idx] = false;
+                events.push(ThresholdEvent::RecoveredAbove(id.clone(), *level));
+            }
+        }
+
+        events
+    }
+
+    /// Same as [update](#method.update), but for the [watcher events](enum.Event.html);
+    /// removed batteries are forgotten, so they are starting from scratch if they are plugged again.
+    pub fn handle(&mut self, event: &Event) -> Vec<ThresholdEvent> {
+        match event {
+            Event::Added(battery) | Event::Changed(battery) => self.update(battery),
+            Event::Removed(id) => {
+                self.crossed.remove(id);
+                vec![]
+            }
+            _ => vec![],
+        }
+    }
+
+    /// Updates all batteries available, forgetting the missing ones.
+    pub(crate) fn poll(&mut self, manager: &Manager) -> Result<Vec<ThresholdEvent>> {
+        let batteries = manager.batteries()?.collect::<Result<Vec<_>>>()?;
+        let seen = batteries.iter().map(watch_id).collect::<HashSet<_>>();
+        self.crossed.retain(|id, _| seen.contains(id));
+
+        Ok(batteries.iter().flat_map(|battery| self.update(battery)).collect())
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::io;
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::units::ratio::percent;
+use crate::{Battery, Error, Manager, Result, State};
+
+/// Battery change notification, sent by the watcher spawned with
+/// [Manager::spawn_watcher](struct.Manager.html#method.spawn_watcher).
+///
+/// Batteries are told apart by their [stable identifiers](struct.Battery.html#method.stable_id),
+/// or by their [device paths](struct.Battery.html#method.device_path) if the stable identifier is empty.
+#[derive(Debug)]
+#[allow(clippy::manual_non_exhaustive)] // `#[non_exhaustive]` requires Rust 1.40
+pub enum Event {
+    /// Battery had appeared; sent for each battery found at the watcher start too.
+    Added(Battery),
+    /// Battery [state](struct.Battery.html#method.state) or its
+    /// [state of charge](struct.Battery.html#method.state_of_charge) (in whole percents) had changed.
+    Changed(Battery),
+    /// Battery with the given stable identifier is gone.
+    Removed(String),
+    /// Batteries enumeration keeps failing, and the last information of the battery
+    /// with the given stable identifier had became [stale](struct.Battery.html#method.is_stale).
+    ///
+    /// Sent once, when the [maximum data age](struct.Config.html#method.max_data_age) is crossed;
+    /// the battery is reported as `Changed` once the enumeration succeeds again.
+    /// Never sent if the maximum data age is not configured.
+    Stale(String),
+
+    // Awaiting for https://github.com/rust-lang/rust/issues/44109
+    #[doc(hidden)]
+    __Nonexhaustive,
+}
+
+/// Handle of the background watcher thread.
+///
+/// Watcher is stopped when the handle is dropped: the thread is signaled to stop
+/// and the drop blocks until it exits, which takes at most one
+/// [enumeration](struct.Manager.html#method.batteries) of the batteries.
+/// Watcher thread also stops by itself when the events receiver is dropped.
+pub struct WatcherHandle {
+    stop: Option<mpsc::Sender<()>>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl WatcherHandle {
+    /// Spawns the watcher thread with a manager created by the `factory`,
+    /// since `Manager` itself can't be moved between threads.
+    pub(crate) fn spawn<F>(factory: F, interval: Duration, tx: mpsc::Sender<Result<Event>>) -> Result<WatcherHandle>
+    where
+        F: FnOnce() -> Result<Manager> + Send + 'static,
+    {
+        let mut known: HashMap<String, Seen> = HashMap::new();
+
+        WatcherHandle::spawn_with(factory, interval, tx, move |manager| poll(manager, &mut known))
+    }
+
+    /// Same as `spawn`, but events are produced by the `poll` function, called every `interval`.
+    ///
+    /// Errors returned by `poll` are sent into the channel together with the events.
+    pub(crate) fn spawn_with<F, P, T>(
+        factory: F,
+        interval: Duration,
+        tx: mpsc::Sender<Result<T>>,
+        poll: P,
+    ) -> Result<WatcherHandle>
+    where
+        F: FnOnce() -> Result<Manager> + Send + 'static,
+        P: FnMut(&Manager) -> Vec<Result<T>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let (stop, stopped) = mpsc::channel();
+        let (ready, started) = mpsc::channel();
+
+        let thread = thread::Builder::new()
+            .name("battery-watcher".to_string())
+            .spawn(move || {
+                let manager = match factory() {
+                    Ok(manager) => {
+                        let _ = ready.send(Ok(()));
+                        manager
+                    }
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+
+                watch(&manager, interval, &tx, &stopped, poll);
+            })?;
+
+        match started.recv() {
+            Ok(Ok(())) => Ok(WatcherHandle {
+                stop: Some(stop),
+                thread: Some(thread),
+            }),
+            Ok(Err(e)) => Err(e),
+            Err(_) => {
+                let inner = io::Error::from(io::ErrorKind::Other);
+                Err(Error::new(inner, "Watcher thread had terminated unexpectedly"))
+            }
+        }
+    }
+}
+
+impl Drop for WatcherHandle {
+    fn drop(&mut self) {
+        // Disconnecting the stop channel wakes the thread up
+        drop(self.stop.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl fmt::Debug for WatcherHandle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WatcherHandle").finish()
+    }
+}
+
+/// Watch loop, ends when either `stopped` or `tx` channel is disconnected.
+fn watch<P, T>(
+    manager: &Manager,
+    interval: Duration,
+    tx: &mpsc::Sender<Result<T>>,
+    stopped: &mpsc::Receiver<()>,
+    mut poll: P,
+) where
+    P: FnMut(&Manager) -> Vec<Result<T>>,
+{
+    loop {
+        for event in poll(manager) {
+            if tx.send(event).is_err() {
+                return;
+            }
+        }
+
+        match stopped.recv_timeout(interval) {
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            _ => return,
+        }
+    }
+}
+
+/// Flattens the `poll` result into the events sent by the watcher.
+pub(crate) fn flatten<T>(result: Result<Vec<T>>) -> Vec<Result<T>> {
+    match result {
+        Ok(events) => events.into_iter().map(Ok).collect(),
+        Err(e) => vec![Err(e)],
+    }
+}
+
+/// Last seen information of the battery.
+struct Seen {
+    state: State,
+    // State of charge, in whole percents
+    percent: u32,
+    updated_at: Option<SystemTime>,
+    stale: bool,
+}
+
+fn poll(manager: &Manager, known: &mut HashMap<String, Seen>) -> Vec<Result<Event>> {
+    let batteries = match manager
+        .batteries()
+        .and_then(|batteries| batteries.collect::<Result<Vec<_>>>())
+    {
+        Ok(batteries) => batteries,
+        Err(e) => {
+            // Batteries are kept as is, since they might be back on the next poll
+            let now = manager.config().clock.system_now();
+            let mut stale = known
+                .iter_mut()
+                .filter(|(_, seen)| !seen.stale && manager.config().is_stale(seen.updated_at, now))
+                .map(|(id, seen)| {
+                    seen.stale = true;
+                    id.clone()
+                })
+                .collect::<Vec<_>>();
+            stale.sort();
+
+            let mut events = vec![Err(e)];
+            events.extend(stale.into_iter().map(|id| Ok(Event::Stale(id))));
+            return events;
+        }
+    };
+    let mut events = vec![];
+    let mut seen = HashMap::with_capacity(batteries.len());
+
+    for battery in batteries {
+        let id = watch_id(&battery);
+        let current = Seen {
+            state: battery.state(),
+            percent: battery.state_of_charge().get::<percent>().round() as u32,
+            updated_at: battery.last_updated(),
+            stale: false,
+        };
+        match known.get(&id) {
+            None => events.push(Ok(Event::Added(battery))),
+            Some(previous)
+                if previous.stale || previous.state != current.state || previous.percent != current.percent =>
+            {
+                events.push(Ok(Event::Changed(battery)))
+            }
+            Some(_) => {}
+        }
+        seen.insert(id, current);
+    }
+
+    let mut removed = known
+        .keys()
+        .filter(|id| !seen.contains_key(*id))
+        .cloned()
+        .collect::<Vec<_>>();
+    removed.sort();
+    events.extend(removed.into_iter().map(|id| Ok(Event::Removed(id))));
+    *known = seen;
+
+    events
+}
+
+/// Identifier the battery is watched by: its stable identifier, or the device path if the former is empty,
+/// so the batteries without any vendor data and name are not mixed up.
+pub(crate) fn watch_id(battery: &Battery) -> String {
+    let id = battery.stable_id();
+    if id.is_empty() {
+        battery.device_path().unwrap_or_default().to_string()
+    } else {
+        id
+    }
+}
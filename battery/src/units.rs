@@ -274,3 +274,6 @@ impl Bound for Ratio {
         self
     }
 }
+
+// Declared after the conversion macros, so they can be used there too
+pub mod human;
@@ -0,0 +1,209 @@
+//! Human-readable formatting of the battery values.
+//!
+//! Wrappers are implementing `Display` with the same rounding rules used by the crate itself
+//! (ex. by the [Battery::summary_line](../../struct.Battery.html#method.summary_line)),
+//! so the frontends do not need to reimplement them:
+//!
+//! ```edition2018
+//! # use battery::Result;
+//! use battery::units::human::{HumanEnergy, HumanPercent, HumanTime};
+//! use battery::RoundingMode;
+//!
+//! # fn main() -> Result<()> {
+//! for bat in battery::Manager::new()?.batteries()? {
+//!     let bat = bat?;
+//!     print!("{} ", HumanPercent(bat.state_of_charge(), RoundingMode::Round));
+//!     print!("{:>9}", HumanEnergy(bat.energy()));
+//!     if let Some(time) = bat.time_to_empty() {
+//!         print!(", {} left", HumanTime(time));
+//!     }
+//!     println!();
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! All wrappers are respecting the width and alignment formatting flags (ex. `{:>9}`),
+//! which helps to keep the status bars output stable while the values change.
+//! Fractional values are always rendered with the same amount of decimal places for the same reason.
+//!
+//! Not finite values (`NaN` or infinity) are rendered as `-`.
+
+use std::fmt;
+
+use super::energy::watt_hour;
+use super::power::watt;
+use super::ratio::percent;
+use super::thermodynamic_temperature::{degree_celsius, degree_fahrenheit};
+use super::time::second;
+use super::{Energy, Power, Ratio, ThermodynamicTemperature, Time};
+use crate::{RoundingMode, TemperatureUnit};
+
+/// Placeholder for the values which can't be displayed.
+const NOT_AVAILABLE: &str = "-";
+
+/// Energy in watt-hours with one decimal place, ex. `41.2 Wh`.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanEnergy(pub Energy);
+
+impl fmt::Display for HumanEnergy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad_decimal(f, self.0.get::<watt_hour>(), " Wh")
+    }
+}
+
+/// Power in watts with one decimal place, ex. `12.5 W`.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanPower(pub Power);
+
+impl fmt::Display for HumanPower {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        pad_decimal(f, self.0.get::<watt>(), " W")
+    }
+}
+
+/// Time rounded to the nearest minute, as hours and minutes: `2h 14m`, `1h 05m`, `45m`.
+///
+/// Time shorter than half of a minute is rendered as `<1m`, and negative time as `-`.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanTime(pub Time);
+
+impl fmt::Display for HumanTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let seconds = self.0.get::<second>();
+        if !seconds.is_finite() || seconds < 0.0 {
+            return f.pad(NOT_AVAILABLE);
+        }
+
+        let minutes = (f64::from(seconds) / 60.0).round() as u64;
+        match minutes {
+            0 => f.pad("<1m"),
+            1..=59 => f.pad(&format!("{}m", minutes)),
+            _ => f.pad(&format!("{}h {:02}m", minutes / 60, minutes % 60)),
+        }
+    }
+}
+
+/// Temperature in the given unit with one decimal place, ex. `34.5 °C` or `94.1 °F`.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanTemperature(pub ThermodynamicTemperature, pub TemperatureUnit);
+
+impl fmt::Display for HumanTemperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.1 {
+            TemperatureUnit::Fahrenheit => pad_decimal(f, self.0.get::<degree_fahrenheit>(), " °F"),
+            _ => pad_decimal(f, self.0.get::<degree_celsius>(), " °C"),
+        }
+    }
+}
+
+/// Ratio in integer percents, rounded with the given mode and clamped into the `0..=100` range, ex. `41%`.
+///
+/// See [RoundingMode](../../enum.RoundingMode.html) for the rounding rules.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanPercent(pub Ratio, pub RoundingMode);
+
+impl fmt::Display for HumanPercent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = self.0.get::<percent>();
+        if value.is_finite() {
+            f.pad(&format!("{}%", self.1.apply(value)))
+        } else {
+            f.pad(NOT_AVAILABLE)
+        }
+    }
+}
+
+/// Writes `value` with one decimal place and the `suffix`, padded according to the formatter flags.
+fn pad_decimal(f: &mut fmt::Formatter, value: f32, suffix: &str) -> fmt::Result {
+    if !value.is_finite() {
+        return f.pad(NOT_AVAILABLE);
+    }
+
+    let mut rendered = format!("{:.1}", value);
+    // Values rounded to zero should not keep the minus sign, ex. `-0.0`
+    if rendered == "-0.0" {
+        rendered.remove(0);
+    }
+    rendered.push_str(suffix);
+
+    f.pad(&rendered)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32;
+
+    use super::*;
+
+    #[test]
+    fn test_energy() {
+        assert_eq!(HumanEnergy(Energy::new::<watt_hour>(0.0)).to_string(), "0.0 Wh");
+        assert_eq!(HumanEnergy(Energy::new::<watt_hour>(41.24)).to_string(), "41.2 Wh");
+        assert_eq!(HumanEnergy(Energy::new::<watt_hour>(41.26)).to_string(), "41.3 Wh");
+        assert_eq!(
+            HumanEnergy(Energy::new::<watt_hour>(12_345.0)).to_string(),
+            "12345.0 Wh"
+        );
+        assert_eq!(HumanEnergy(Energy::new::<watt_hour>(f32::NAN)).to_string(), "-");
+        assert_eq!(
+            format!("{:>9}", HumanEnergy(Energy::new::<watt_hour>(5.0))),
+            "   5.0 Wh"
+        );
+    }
+
+    #[test]
+    fn test_power() {
+        assert_eq!(HumanPower(watt!(0.0)).to_string(), "0.0 W");
+        assert_eq!(HumanPower(watt!(-0.01)).to_string(), "0.0 W");
+        assert_eq!(HumanPower(watt!(12.5)).to_string(), "12.5 W");
+        assert_eq!(HumanPower(watt!(f32::INFINITY)).to_string(), "-");
+        assert_eq!(format!("{:<7}|", HumanPower(watt!(7.0))), "7.0 W  |");
+    }
+
+    #[test]
+    fn test_time() {
+        assert_eq!(HumanTime(second!(0)).to_string(), "<1m");
+        assert_eq!(HumanTime(second!(29)).to_string(), "<1m");
+        assert_eq!(HumanTime(second!(30)).to_string(), "1m");
+        assert_eq!(HumanTime(minute!(45)).to_string(), "45m");
+        assert_eq!(HumanTime(second!(3_570)).to_string(), "1h 00m");
+        assert_eq!(HumanTime(minute!(65)).to_string(), "1h 05m");
+        assert_eq!(HumanTime(minute!(134)).to_string(), "2h 14m");
+        assert_eq!(HumanTime(minute!(60_000)).to_string(), "1000h 00m");
+        assert_eq!(HumanTime(second!(-60)).to_string(), "-");
+        assert_eq!(HumanTime(second!(f32::NAN)).to_string(), "-");
+        assert_eq!(format!("{:>6}", HumanTime(minute!(5))), "    5m");
+    }
+
+    #[test]
+    fn test_temperature() {
+        let value = celsius!(34.5);
+        assert_eq!(HumanTemperature(value, TemperatureUnit::Celsius).to_string(), "34.5 °C");
+        assert_eq!(
+            HumanTemperature(value, TemperatureUnit::Fahrenheit).to_string(),
+            "94.1 °F"
+        );
+        assert_eq!(
+            HumanTemperature(celsius!(-5.0), TemperatureUnit::Celsius).to_string(),
+            "-5.0 °C"
+        );
+        assert_eq!(
+            HumanTemperature(celsius!(f32::NAN), TemperatureUnit::Celsius).to_string(),
+            "-"
+        );
+    }
+
+    #[test]
+    fn test_percent() {
+        assert_eq!(HumanPercent(percent!(0.0), RoundingMode::Round).to_string(), "0%");
+        assert_eq!(HumanPercent(percent!(49.5), RoundingMode::Round).to_string(), "50%");
+        assert_eq!(HumanPercent(percent!(49.5), RoundingMode::Floor).to_string(), "49%");
+        assert_eq!(HumanPercent(percent!(150.0), RoundingMode::Round).to_string(), "100%");
+        assert_eq!(HumanPercent(percent!(f32::NAN), RoundingMode::Round).to_string(), "-");
+        assert_eq!(
+            format!("{:>4}", HumanPercent(percent!(7.0), RoundingMode::Round)),
+            "  7%"
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Allocations made by the battery refresh, counted by the global allocator,
+//! which is installed for this test binary only.
+
+#![cfg(target_os = "linux")]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs;
+use std::path::Path;
+use std::time::Instant;
+
+use battery::{Battery, Config, Manager};
+
+/// Counts the allocations made by the current thread, so the tests running in parallel do not interfere.
+struct CountingAllocator;
+
+thread_local! {
+    #[allow(clippy::missing_const_for_thread_local)] // `const` initializers require Rust 1.59
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+fn count() {
+    // Thread-local might be unavailable already while the thread is being destroyed
+    let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count();
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count();
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations<F: FnOnce()>(f: F) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+/// Creates the `BAT0` battery with the essential attributes and the `extra` ones.
+fn create_battery(root: &Path, extra: &[(&str, &str)]) {
+    let path = root.join("BAT0");
+    fs::create_dir(&path).unwrap();
+
+    let files = [
+        ("type", "Battery"),
+        ("status", "Discharging"),
+        ("energy_now", "25000000"),
+        ("energy_full", "50000000"),
+        ("energy_full_design", "50000000"),
+        ("voltage_now", "12000000"),
+    ];
+    for (name, value) in files.iter().chain(extra.iter()) {
+        fs::write(path.join(name), format!("{}\n", value)).unwrap();
+    }
+}
+
+fn first_battery(manager: &Manager) -> Battery {
+    manager.batteries().unwrap().next().unwrap().unwrap()
+}
+
+/// Allocations made by the second refresh, the first one might be opening the attribute files.
+fn refresh_allocations(extra: &[(&str, &str)]) -> usize {
+    let root = tempfile::tempdir().unwrap();
+    create_battery(root.path(), extra);
+    let manager = Manager::with_sysfs_root(root.path(), Config::default());
+    let mut battery = first_battery(&manager);
+    manager.refresh(&mut battery).unwrap();
+
+    allocations(|| manager.refresh(&mut battery).unwrap())
+}
+
+/// Refresh allocations are bounded by the constant number.
+#[test]
+fn test_refresh_allocations() {
+    let root = tempfile::tempdir().unwrap();
+    create_battery(root.path(), &[]);
+    let manager = Manager::with_sysfs_root(root.path(), Config::default());
+    let mut battery = first_battery(&manager);
+    let expected = (
+        battery.energy(),
+        battery.energy_full(),
+        battery.voltage(),
+        battery.state(),
+    );
+    manager.refresh(&mut battery).unwrap();
+
+    let refresh_allocations = allocations(|| manager.refresh(&mut battery).unwrap());
+    assert!(
+        refresh_allocations <= 8,
+        "{} allocations per refresh",
+        refresh_allocations
+    );
+    assert_eq!(
+        (
+            battery.energy(),
+            battery.energy_full(),
+            battery.voltage(),
+            battery.state()
+        ),
+        expected
+    );
+}
+
+/// Attribute values are read without allocations, so the refresh allocations
+/// do not depend on how many attributes are read.
+#[test]
+fn test_attributes_read_does_not_allocate() {
+    let extra = [
+        ("power_now", "10000000"),
+        ("voltage_min_design", "11100000"),
+        ("voltage_max_design", "12600000"),
+        ("temp", "305"),
+        ("temp_ambient", "250"),
+        ("temp_max", "600"),
+        ("cycle_count", "42"),
+        ("capacity_level", "Normal"),
+        ("alarm", "2500000"),
+        ("charge_control_end_threshold", "80"),
+    ];
+
+    assert_eq!(refresh_allocations(&extra), refresh_allocations(&[]));
+}
+
+/// Measures the refresh time and allocations, run with
+///
+/// ```text
+/// cargo test --release -p battery --test refresh_allocations bench_refresh -- --ignored --nocapture
+/// ```
+#[test]
+#[ignore]
+fn bench_refresh() {
+    const ROUNDS: u32 = 10_000;
+
+    let root = tempfile::tempdir().unwrap();
+    create_battery(root.path(), &[]);
+    let manager = Manager::with_sysfs_root(root.path(), Config::default());
+    let mut battery = first_battery(&manager);
+
+    let started = Instant::now();
+    let total = allocations(|| {
+        for _ in 0..ROUNDS {
+            manager.refresh(&mut battery).unwrap();
+        }
+    });
+    println!(
+        "{:?} and {} allocations per refresh",
+        started.elapsed() / ROUNDS,
+        total as f64 / f64::from(ROUNDS)
+    );
+}